@@ -0,0 +1,5 @@
+ALTER TABLE transactions
+    ADD COLUMN message_id       INTEGER,
+    ADD COLUMN reply_message_id INTEGER;
+
+CREATE INDEX transactions_chat_id_message_id_idx ON transactions (chat_id, message_id);
@@ -0,0 +1,12 @@
+CREATE TABLE actions
+(
+    id          BIGSERIAL PRIMARY KEY,
+    chat_id     BIGINT      NOT NULL,
+    kind        INTEGER     NOT NULL,
+    entity_id   BIGINT      NOT NULL,
+    snapshot    JSONB       NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL,
+    reverted_at TIMESTAMPTZ
+);
+
+CREATE INDEX actions_chat_id_idx ON actions (chat_id, id DESC);
\ No newline at end of file
@@ -0,0 +1,16 @@
+ALTER TABLE actions
+    ADD COLUMN user_id         BIGINT,
+    ADD COLUMN snapshot_before JSONB,
+    ADD COLUMN snapshot_after  JSONB,
+    ADD COLUMN reverted_by     BIGINT;
+
+UPDATE actions
+SET snapshot_after = snapshot
+WHERE kind IN (1, 4);
+
+UPDATE actions
+SET snapshot_before = snapshot
+WHERE kind NOT IN (1, 4);
+
+ALTER TABLE actions
+    DROP COLUMN snapshot;
\ No newline at end of file
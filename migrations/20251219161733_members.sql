@@ -0,0 +1,20 @@
+CREATE TABLE members
+(
+    chat_id    BIGINT      NOT NULL,
+    user_id    BIGINT      NOT NULL,
+    username   TEXT,
+    full_name  TEXT        NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (chat_id, user_id)
+);
+
+ALTER TABLE transactions
+    ADD COLUMN user_id BIGINT;
+
+UPDATE transactions t
+SET user_id = a.user_id
+FROM actions a
+WHERE a.kind = 1
+  AND a.chat_id = t.chat_id
+  AND a.entity_id = t.id;
\ No newline at end of file
@@ -0,0 +1,19 @@
+CREATE TABLE transaction_participants
+(
+    transaction_id BIGINT NOT NULL REFERENCES transactions (id),
+    user_id        BIGINT NOT NULL,
+    PRIMARY KEY (transaction_id, user_id)
+);
+
+CREATE TABLE settlements
+(
+    id            BIGSERIAL PRIMARY KEY,
+    chat_id       BIGINT      NOT NULL,
+    from_user_id  BIGINT      NOT NULL,
+    to_user_id    BIGINT      NOT NULL,
+    amount        BIGINT      NOT NULL,
+    currency_code CHAR(3)     NOT NULL,
+    created_at    TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX settlements_chat_id_idx ON settlements (chat_id);
\ No newline at end of file
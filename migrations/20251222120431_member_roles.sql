@@ -0,0 +1,11 @@
+ALTER TABLE members
+    ADD COLUMN role INTEGER NOT NULL DEFAULT 2;
+
+-- The first member seen in a chat owns it.
+UPDATE members m
+SET role = 1
+FROM (SELECT DISTINCT ON (chat_id) chat_id, user_id
+      FROM members
+      ORDER BY chat_id, created_at, user_id) first
+WHERE m.chat_id = first.chat_id
+  AND m.user_id = first.user_id;
\ No newline at end of file
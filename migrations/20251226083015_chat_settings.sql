@@ -0,0 +1,9 @@
+CREATE TABLE chat_settings
+(
+    chat_id          BIGINT PRIMARY KEY,
+    digest_frequency INTEGER     NOT NULL DEFAULT 0,
+    digest_hour      INTEGER     NOT NULL DEFAULT 9,
+    digest_sent_at   TIMESTAMPTZ,
+    created_at       TIMESTAMPTZ NOT NULL,
+    updated_at       TIMESTAMPTZ NOT NULL
+);
\ No newline at end of file
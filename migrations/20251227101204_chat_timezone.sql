@@ -0,0 +1,2 @@
+ALTER TABLE chat_settings
+    ADD COLUMN timezone TEXT NOT NULL DEFAULT 'UTC';
\ No newline at end of file
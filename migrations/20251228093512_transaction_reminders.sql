@@ -0,0 +1,5 @@
+CREATE TABLE transaction_reminders
+(
+    transaction_id BIGINT PRIMARY KEY REFERENCES transactions (id),
+    reminded_at    TIMESTAMPTZ NOT NULL
+);
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE categories
+    ADD COLUMN parent_id BIGINT REFERENCES categories (id);
\ No newline at end of file
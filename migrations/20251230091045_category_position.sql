@@ -0,0 +1,13 @@
+ALTER TABLE categories
+    ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+-- Keeps the order categories were listed in so far, newest first.
+UPDATE categories
+SET position = ordered.position
+FROM (SELECT id,
+             ROW_NUMBER() OVER (PARTITION BY chat_id, direction ORDER BY created_at DESC, id DESC) AS position
+      FROM categories) AS ordered
+WHERE categories.id = ordered.id;
+
+ALTER TABLE chat_settings
+    ADD COLUMN category_sort INTEGER NOT NULL DEFAULT 0;
\ No newline at end of file
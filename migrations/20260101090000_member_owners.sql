@@ -0,0 +1,6 @@
+-- Group owners used to be the first member the bot saw. They are taken from the chat
+-- administrators in Telegram instead, which the bot does for every group left without one.
+UPDATE members
+SET role = 2
+WHERE role = 1
+  AND chat_id < 0;
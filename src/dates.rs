@@ -0,0 +1,94 @@
+//! Calendar math in a chat's timezone. Timestamps are stored in UTC, while days and
+//! months are the ones on the chat's wall clock.
+
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// The moment the wall clock of the timezone shows `date_time`. Times skipped by a DST
+/// change are moved forward and repeated ones resolve to the first occurrence.
+pub fn to_utc(timezone: Tz, date_time: NaiveDateTime) -> DateTime<Utc> {
+    match timezone.from_local_datetime(&date_time) {
+        LocalResult::Single(date_time) | LocalResult::Ambiguous(date_time, _) => {
+            date_time.with_timezone(&Utc)
+        }
+        LocalResult::None => timezone
+            .from_local_datetime(&(date_time + Duration::hours(1)))
+            .earliest()
+            .map(|date_time| date_time.with_timezone(&Utc))
+            .unwrap_or_else(|| date_time.and_utc()),
+    }
+}
+
+pub fn start_of_day(timezone: Tz, date: NaiveDate) -> DateTime<Utc> {
+    to_utc(timezone, date.and_hms_opt(0, 0, 0).unwrap_or_default())
+}
+
+/// The date on the wall clock of the timezone at `date_time`.
+pub fn local_date(timezone: Tz, date_time: DateTime<Utc>) -> NaiveDate {
+    date_time.with_timezone(&timezone).date_naive()
+}
+
+pub fn today(timezone: Tz) -> NaiveDate {
+    local_date(timezone, Utc::now())
+}
+
+/// Start of the given month and of the one after it.
+pub fn month_bounds(timezone: Tz, year: i32, month: u32) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let from = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let (next_year, next_month) = add_months(year, month, 1);
+    let to = NaiveDate::from_ymd_opt(next_year, next_month, 1)?;
+
+    Some((start_of_day(timezone, from), start_of_day(timezone, to)))
+}
+
+/// The month `months` months after the given one, or before it if negative.
+pub fn add_months(year: i32, month: u32, months: i32) -> (i32, u32) {
+    let index = year * 12 + month as i32 - 1 + months;
+
+    (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_bounds() {
+        let (from, to) = month_bounds(Tz::UTC, 2025, 12).unwrap();
+        assert_eq!(from.to_rfc3339(), "2025-12-01T00:00:00+00:00");
+        assert_eq!(to.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+
+        let (from, _) = month_bounds(Tz::Asia__Vladivostok, 2025, 12).unwrap();
+        assert_eq!(from.to_rfc3339(), "2025-11-30T14:00:00+00:00");
+
+        assert!(month_bounds(Tz::UTC, 2025, 13).is_none());
+    }
+
+    #[test]
+    fn test_add_months() {
+        assert_eq!(add_months(2025, 12, 1), (2026, 1));
+        assert_eq!(add_months(2025, 3, -11), (2024, 4));
+        assert_eq!(add_months(2025, 1, 0), (2025, 1));
+    }
+
+    #[test]
+    fn test_local_date() {
+        // 23:30 in Vladivostok is still the same day there.
+        let purchase = to_utc(
+            Tz::Asia__Vladivostok,
+            NaiveDate::from_ymd_opt(2025, 12, 5)
+                .unwrap()
+                .and_hms_opt(23, 30, 0)
+                .unwrap(),
+        );
+
+        assert_eq!(
+            purchase.date_naive(),
+            NaiveDate::from_ymd_opt(2025, 12, 5).unwrap()
+        );
+        assert_eq!(
+            local_date(Tz::Asia__Vladivostok, purchase),
+            NaiveDate::from_ymd_opt(2025, 12, 5).unwrap()
+        );
+    }
+}
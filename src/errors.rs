@@ -0,0 +1,153 @@
+use crate::services::categories;
+use std::sync::Arc;
+use teloxide::dispatching::dialogue::InMemStorageError;
+use teloxide::types::Update;
+use teloxide::{DownloadError, RequestError};
+
+/// An error of handling an update.
+#[derive(Debug)]
+pub enum Error {
+    /// A category operation was rejected: its message can be shown to the user as is.
+    Category(categories::ServiceError),
+    /// A request to the Telegram Bot API failed.
+    Telegram(RequestError),
+    /// An update the bot cannot handle, e.g. a button of an unknown kind.
+    Unexpected(String),
+    /// Any other failure, usually of the database.
+    Internal(Box<dyn std::error::Error + Send + Sync>),
+    /// An error together with the update that caused it, so that the error handler can reply.
+    Update {
+        update: Arc<Update>,
+        error: Box<Error>,
+    },
+}
+
+impl Error {
+    /// Attaches the update to the error, unless it already has one.
+    pub fn with_update(self, update: Arc<Update>) -> Self {
+        match self {
+            Error::Update { .. } => self,
+            error => Error::Update {
+                update,
+                error: Box::new(error),
+            },
+        }
+    }
+
+    /// The error without the update attached to it.
+    pub fn kind(&self) -> &Error {
+        match self {
+            Error::Update { error, .. } => error.kind(),
+            error => error,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Category(error) => write!(f, "{error}"),
+            Error::Telegram(error) => write!(f, "telegram: {error}"),
+            Error::Unexpected(message) => write!(f, "{message}"),
+            Error::Internal(error) => write!(f, "{error}"),
+            Error::Update { update, error } => write!(f, "update {}: {error}", update.id.0),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Category(error) => Some(error),
+            Error::Telegram(error) => Some(error),
+            Error::Unexpected(_) => None,
+            Error::Internal(error) => Some(error.as_ref()),
+            Error::Update { error, .. } => Some(error.as_ref()),
+        }
+    }
+}
+
+impl From<categories::ServiceError> for Error {
+    fn from(error: categories::ServiceError) -> Self {
+        match error {
+            categories::ServiceError::Repository(error) => Error::from(error),
+            error => Error::Category(error),
+        }
+    }
+}
+
+impl From<RequestError> for Error {
+    fn from(error: RequestError) -> Self {
+        Error::Telegram(error)
+    }
+}
+
+impl From<DownloadError> for Error {
+    fn from(error: DownloadError) -> Self {
+        Error::Internal(Box::new(error))
+    }
+}
+
+impl From<InMemStorageError> for Error {
+    fn from(error: InMemStorageError) -> Self {
+        Error::Internal(Box::new(error))
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::Unexpected(message.to_string())
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Unexpected(message)
+    }
+}
+
+impl From<prost::UnknownEnumValue> for Error {
+    fn from(error: prost::UnknownEnumValue) -> Self {
+        Error::Unexpected(error.to_string())
+    }
+}
+
+/// Services and message builders return boxed errors: the known ones are unboxed back.
+impl From<Box<dyn std::error::Error + Send + Sync>> for Error {
+    fn from(error: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        let error = match error.downcast::<Error>() {
+            Ok(error) => return *error,
+            Err(error) => error,
+        };
+        let error = match error.downcast::<categories::ServiceError>() {
+            Ok(error) => return Error::from(*error),
+            Err(error) => error,
+        };
+        match error.downcast::<RequestError>() {
+            Ok(error) => Error::Telegram(*error),
+            Err(error) => Error::Internal(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::categories::ServiceError;
+
+    #[test]
+    fn unboxes_known_errors() {
+        let error: Box<dyn std::error::Error + Send + Sync> = Box::new(ServiceError::NotFound);
+        assert!(matches!(
+            Error::from(error),
+            Error::Category(ServiceError::NotFound)
+        ));
+
+        let error: Box<dyn std::error::Error + Send + Sync> =
+            Box::new(ServiceError::Repository("connection reset".into()));
+        assert!(matches!(Error::from(error), Error::Internal(_)));
+
+        let error: Box<dyn std::error::Error + Send + Sync> = "connection reset".into();
+        assert!(matches!(Error::from(error), Error::Internal(_)));
+    }
+}
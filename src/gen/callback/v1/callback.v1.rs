@@ -7,6 +7,9 @@ pub struct ShowMainSettings {
 pub struct ShowCategoryList {
     #[prost(enumeration="CategoryDirection", tag="1")]
     pub category_direction: i32,
+    /// Lists the subcategories of the category, top-level categories if zero.
+    #[prost(int64, tag="2")]
+    pub parent_id: i64,
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ShowCategorySettings {
@@ -19,6 +22,9 @@ pub struct ShowCategorySettings {
 pub struct CreateCategory {
     #[prost(enumeration="CategoryDirection", tag="1")]
     pub category_direction: i32,
+    /// Creates a subcategory of the category, a top-level one if zero.
+    #[prost(int64, tag="2")]
+    pub parent_id: i64,
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct CancelCreateCategory {
@@ -90,6 +96,9 @@ pub struct CreateTransaction {
     pub category_direction: i32,
     #[prost(bool, tag="3")]
     pub is_short_mode: bool,
+    /// Offers the subcategories of the category, top-level categories if zero.
+    #[prost(int64, tag="4")]
+    pub parent_id: i64,
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct CancelCreateTransaction {
@@ -104,8 +113,348 @@ pub struct ApproveTransactionCategory {
     pub category_id: i64,
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowTransaction {
+    #[prost(int64, tag="1")]
+    pub transaction_id: i64,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UpdateTransaction {
+    #[prost(int64, tag="1")]
+    pub transaction_id: i64,
+    #[prost(enumeration="update_transaction::Field", tag="2")]
+    pub field: i32,
+}
+/// Nested message and enum types in `UpdateTransaction`.
+pub mod update_transaction {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Field {
+        Unspecified = 0,
+        Amount = 1,
+        Description = 2,
+        Category = 3,
+        Date = 4,
+        Currency = 5,
+    }
+    impl Field {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Self::Unspecified => "FIELD_UNSPECIFIED",
+                Self::Amount => "FIELD_AMOUNT",
+                Self::Description => "FIELD_DESCRIPTION",
+                Self::Category => "FIELD_CATEGORY",
+                Self::Date => "FIELD_DATE",
+                Self::Currency => "FIELD_CURRENCY",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "FIELD_UNSPECIFIED" => Some(Self::Unspecified),
+                "FIELD_AMOUNT" => Some(Self::Amount),
+                "FIELD_DESCRIPTION" => Some(Self::Description),
+                "FIELD_CATEGORY" => Some(Self::Category),
+                "FIELD_DATE" => Some(Self::Date),
+                "FIELD_CURRENCY" => Some(Self::Currency),
+                _ => None,
+            }
+        }
+    }
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct CancelUpdateTransaction {
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ApproveTransactionCurrency {
+    #[prost(int64, tag="1")]
+    pub transaction_id: i64,
+    #[prost(enumeration="Currency", tag="2")]
+    pub currency: i32,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowActionLog {
+    #[prost(int64, tag="1")]
+    pub page: i64,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowHistory {
+    #[prost(int64, tag="1")]
+    pub page: i64,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowReport {
+    #[prost(int32, tag="1")]
+    pub year: i32,
+    #[prost(uint32, tag="2")]
+    pub month: u32,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SettleDebt {
+    #[prost(int64, tag="1")]
+    pub from_user_id: i64,
+    #[prost(int64, tag="2")]
+    pub to_user_id: i64,
+    #[prost(int64, tag="3")]
+    pub amount: i64,
+    #[prost(enumeration="Currency", tag="4")]
+    pub currency: i32,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowMemberList {
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowMemberSettings {
+    #[prost(int64, tag="1")]
+    pub user_id: i64,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UpdateMemberRole {
+    #[prost(int64, tag="1")]
+    pub user_id: i64,
+    #[prost(enumeration="MemberRole", tag="2")]
+    pub role: i32,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ExportTransactions {
+    #[prost(int64, tag="1")]
+    pub from: i64,
+    #[prost(int64, tag="2")]
+    pub to: i64,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SelectImportColumn {
+    #[prost(enumeration="select_import_column::Field", tag="1")]
+    pub field: i32,
+    /// Negative to skip an optional field.
+    #[prost(int32, tag="2")]
+    pub column: i32,
+}
+/// Nested message and enum types in `SelectImportColumn`.
+pub mod select_import_column {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Field {
+        Unspecified = 0,
+        Date = 1,
+        Amount = 2,
+        Description = 3,
+    }
+    impl Field {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Self::Unspecified => "FIELD_UNSPECIFIED",
+                Self::Date => "FIELD_DATE",
+                Self::Amount => "FIELD_AMOUNT",
+                Self::Description => "FIELD_DESCRIPTION",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "FIELD_UNSPECIFIED" => Some(Self::Unspecified),
+                "FIELD_DATE" => Some(Self::Date),
+                "FIELD_AMOUNT" => Some(Self::Amount),
+                "FIELD_DESCRIPTION" => Some(Self::Description),
+                _ => None,
+            }
+        }
+    }
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ApplyImport {
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct CancelImport {
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowChart {
+    #[prost(int32, tag="1")]
+    pub year: i32,
+    #[prost(uint32, tag="2")]
+    pub month: u32,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowTrend {
+    /// Zero for all categories.
+    #[prost(int64, tag="1")]
+    pub category_id: i64,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowDigestSettings {
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UpdateDigestSettings {
+    #[prost(enumeration="DigestFrequency", tag="1")]
+    pub frequency: i32,
+    /// Local hour the digest is sent at.
+    #[prost(uint32, tag="2")]
+    pub hour: u32,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowTimezoneSettings {
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UpdateTimezone {
+    /// Index in the list of suggested timezones.
+    #[prost(uint32, tag="1")]
+    pub preset: u32,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowTriage {
+    /// Position among the uncategorized transactions, oldest first.
+    #[prost(int64, tag="1")]
+    pub offset: i64,
+    #[prost(enumeration="CategoryDirection", tag="2")]
+    pub category_direction: i32,
+    /// Whether the chosen category also goes to transactions with the same description.
+    #[prost(bool, tag="3")]
+    pub apply_to_similar: bool,
+    /// Offers the subcategories of the category, top-level categories if zero.
+    #[prost(int64, tag="4")]
+    pub parent_id: i64,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct TriageTransaction {
+    #[prost(int64, tag="1")]
+    pub transaction_id: i64,
+    #[prost(enumeration="triage_transaction::Action", tag="2")]
+    pub action: i32,
+    #[prost(int64, tag="3")]
+    pub category_id: i64,
+    #[prost(message, optional, tag="4")]
+    pub navigated_from: ::core::option::Option<ShowTriage>,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct MoveCategory {
+    #[prost(int64, tag="1")]
+    pub category_id: i64,
+    #[prost(enumeration="move_category::Shift", tag="2")]
+    pub shift: i32,
+    #[prost(message, optional, tag="3")]
+    pub navigated_from: ::core::option::Option<ShowCategoryList>,
+}
+/// Nested message and enum types in `MoveCategory`.
+pub mod move_category {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Shift {
+        Unspecified = 0,
+        Up = 1,
+        Down = 2,
+    }
+    impl Shift {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Self::Unspecified => "SHIFT_UNSPECIFIED",
+                Self::Up => "SHIFT_UP",
+                Self::Down => "SHIFT_DOWN",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "SHIFT_UNSPECIFIED" => Some(Self::Unspecified),
+                "SHIFT_UP" => Some(Self::Up),
+                "SHIFT_DOWN" => Some(Self::Down),
+                _ => None,
+            }
+        }
+    }
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UpdateCategorySort {
+    #[prost(enumeration="CategorySort", tag="1")]
+    pub sort: i32,
+    #[prost(message, optional, tag="2")]
+    pub navigated_from: ::core::option::Option<ShowCategoryList>,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SelectCategoryParent {
+    #[prost(int64, tag="1")]
+    pub category_id: i64,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UpdateCategoryParent {
+    #[prost(int64, tag="1")]
+    pub category_id: i64,
+    /// Moves the category to the top level if zero.
+    #[prost(int64, tag="2")]
+    pub parent_id: i64,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ShowCategoryArchive {
+    #[prost(enumeration="CategoryDirection", tag="1")]
+    pub category_direction: i32,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct RestoreCategory {
+    #[prost(int64, tag="1")]
+    pub category_id: i64,
+    #[prost(message, optional, tag="2")]
+    pub navigated_from: ::core::option::Option<ShowCategoryArchive>,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ApplyCategoryPack {
+    #[prost(enumeration="CategoryPack", tag="1")]
+    pub pack: i32,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct MergeCategory {
+    #[prost(int64, tag="1")]
+    pub category_id: i64,
+    /// Offers the categories to merge into if zero.
+    #[prost(int64, tag="2")]
+    pub target_id: i64,
+    #[prost(bool, tag="3")]
+    pub is_confirmed: bool,
+}
+/// Nested message and enum types in `TriageTransaction`.
+pub mod triage_transaction {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Action {
+        Unspecified = 0,
+        Categorize = 1,
+        Delete = 2,
+    }
+    impl Action {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                Self::Unspecified => "ACTION_UNSPECIFIED",
+                Self::Categorize => "ACTION_CATEGORIZE",
+                Self::Delete => "ACTION_DELETE",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "ACTION_UNSPECIFIED" => Some(Self::Unspecified),
+                "ACTION_CATEGORIZE" => Some(Self::Categorize),
+                "ACTION_DELETE" => Some(Self::Delete),
+                _ => None,
+            }
+        }
+    }
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct Callback {
-    #[prost(oneof="callback::Query", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12")]
+    #[prost(oneof="callback::Query", tags="1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43")]
     pub query: ::core::option::Option<callback::Query>,
 }
 /// Nested message and enum types in `Callback`.
@@ -136,6 +485,68 @@ pub mod callback {
         CancelCreateTransaction(super::CancelCreateTransaction),
         #[prost(message, tag="12")]
         ApproveTransactionCategory(super::ApproveTransactionCategory),
+        #[prost(message, tag="13")]
+        ShowTransaction(super::ShowTransaction),
+        #[prost(message, tag="14")]
+        UpdateTransaction(super::UpdateTransaction),
+        #[prost(message, tag="15")]
+        CancelUpdateTransaction(super::CancelUpdateTransaction),
+        #[prost(message, tag="16")]
+        ApproveTransactionCurrency(super::ApproveTransactionCurrency),
+        #[prost(message, tag="17")]
+        ShowActionLog(super::ShowActionLog),
+        #[prost(message, tag="18")]
+        ShowHistory(super::ShowHistory),
+        #[prost(message, tag="19")]
+        ShowReport(super::ShowReport),
+        #[prost(message, tag="20")]
+        SettleDebt(super::SettleDebt),
+        #[prost(message, tag="21")]
+        ShowMemberList(super::ShowMemberList),
+        #[prost(message, tag="22")]
+        ShowMemberSettings(super::ShowMemberSettings),
+        #[prost(message, tag="23")]
+        UpdateMemberRole(super::UpdateMemberRole),
+        #[prost(message, tag="24")]
+        ExportTransactions(super::ExportTransactions),
+        #[prost(message, tag="25")]
+        SelectImportColumn(super::SelectImportColumn),
+        #[prost(message, tag="26")]
+        ApplyImport(super::ApplyImport),
+        #[prost(message, tag="27")]
+        CancelImport(super::CancelImport),
+        #[prost(message, tag="28")]
+        ShowChart(super::ShowChart),
+        #[prost(message, tag="29")]
+        ShowTrend(super::ShowTrend),
+        #[prost(message, tag="30")]
+        ShowDigestSettings(super::ShowDigestSettings),
+        #[prost(message, tag="31")]
+        UpdateDigestSettings(super::UpdateDigestSettings),
+        #[prost(message, tag="32")]
+        ShowTimezoneSettings(super::ShowTimezoneSettings),
+        #[prost(message, tag="33")]
+        UpdateTimezone(super::UpdateTimezone),
+        #[prost(message, tag="34")]
+        ShowTriage(super::ShowTriage),
+        #[prost(message, tag="35")]
+        TriageTransaction(super::TriageTransaction),
+        #[prost(message, tag="36")]
+        SelectCategoryParent(super::SelectCategoryParent),
+        #[prost(message, tag="37")]
+        UpdateCategoryParent(super::UpdateCategoryParent),
+        #[prost(message, tag="38")]
+        MoveCategory(super::MoveCategory),
+        #[prost(message, tag="39")]
+        UpdateCategorySort(super::UpdateCategorySort),
+        #[prost(message, tag="40")]
+        ShowCategoryArchive(super::ShowCategoryArchive),
+        #[prost(message, tag="41")]
+        RestoreCategory(super::RestoreCategory),
+        #[prost(message, tag="42")]
+        ApplyCategoryPack(super::ApplyCategoryPack),
+        #[prost(message, tag="43")]
+        MergeCategory(super::MergeCategory),
     }
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -167,4 +578,161 @@ impl CategoryDirection {
         }
     }
 }
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Currency {
+    Unspecified = 0,
+    Usd = 1,
+    Eur = 2,
+    Jpy = 3,
+    Rub = 4,
+}
+impl Currency {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "CURRENCY_UNSPECIFIED",
+            Self::Usd => "CURRENCY_USD",
+            Self::Eur => "CURRENCY_EUR",
+            Self::Jpy => "CURRENCY_JPY",
+            Self::Rub => "CURRENCY_RUB",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CURRENCY_UNSPECIFIED" => Some(Self::Unspecified),
+            "CURRENCY_USD" => Some(Self::Usd),
+            "CURRENCY_EUR" => Some(Self::Eur),
+            "CURRENCY_JPY" => Some(Self::Jpy),
+            "CURRENCY_RUB" => Some(Self::Rub),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MemberRole {
+    Unspecified = 0,
+    Owner = 1,
+    Editor = 2,
+    Viewer = 3,
+}
+impl MemberRole {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "MEMBER_ROLE_UNSPECIFIED",
+            Self::Owner => "MEMBER_ROLE_OWNER",
+            Self::Editor => "MEMBER_ROLE_EDITOR",
+            Self::Viewer => "MEMBER_ROLE_VIEWER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "MEMBER_ROLE_UNSPECIFIED" => Some(Self::Unspecified),
+            "MEMBER_ROLE_OWNER" => Some(Self::Owner),
+            "MEMBER_ROLE_EDITOR" => Some(Self::Editor),
+            "MEMBER_ROLE_VIEWER" => Some(Self::Viewer),
+            _ => None,
+        }
+    }
+}
+/// Digests are off until a frequency is chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum DigestFrequency {
+    Unspecified = 0,
+    Daily = 1,
+    Weekly = 2,
+}
+impl DigestFrequency {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "DIGEST_FREQUENCY_UNSPECIFIED",
+            Self::Daily => "DIGEST_FREQUENCY_DAILY",
+            Self::Weekly => "DIGEST_FREQUENCY_WEEKLY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "DIGEST_FREQUENCY_UNSPECIFIED" => Some(Self::Unspecified),
+            "DIGEST_FREQUENCY_DAILY" => Some(Self::Daily),
+            "DIGEST_FREQUENCY_WEEKLY" => Some(Self::Weekly),
+            _ => None,
+        }
+    }
+}
+/// Categories are offered in their manual order unless sorted by usage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CategorySort {
+    Unspecified = 0,
+    Usage = 1,
+}
+impl CategorySort {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "CATEGORY_SORT_UNSPECIFIED",
+            Self::Usage => "CATEGORY_SORT_USAGE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CATEGORY_SORT_UNSPECIFIED" => Some(Self::Unspecified),
+            "CATEGORY_SORT_USAGE" => Some(Self::Usage),
+            _ => None,
+        }
+    }
+}
+/// Preset categories offered to new chats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CategoryPack {
+    Unspecified = 0,
+    Household = 1,
+    Student = 2,
+    Freelancer = 3,
+}
+impl CategoryPack {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "CATEGORY_PACK_UNSPECIFIED",
+            Self::Household => "CATEGORY_PACK_HOUSEHOLD",
+            Self::Student => "CATEGORY_PACK_STUDENT",
+            Self::Freelancer => "CATEGORY_PACK_FREELANCER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CATEGORY_PACK_UNSPECIFIED" => Some(Self::Unspecified),
+            "CATEGORY_PACK_HOUSEHOLD" => Some(Self::Household),
+            "CATEGORY_PACK_STUDENT" => Some(Self::Student),
+            "CATEGORY_PACK_FREELANCER" => Some(Self::Freelancer),
+            _ => None,
+        }
+    }
+}
 // @@protoc_insertion_point(module)
@@ -0,0 +1,38 @@
+use crate::handlers::actions::message_builders::show_action_log;
+use crate::handlers::callback::MessageBuilder;
+use crate::proto::callback::v1::ShowActionLog;
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::sugar::bot::BotMessagesExt;
+
+#[allow(clippy::too_many_arguments)]
+pub async fn show_action_log(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    actions_service: Arc<dyn services::actions::Service>,
+    categories_service: Arc<dyn services::categories::Service>,
+    members_service: Arc<dyn services::members::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    callback: ShowActionLog,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let builder = show_action_log::MessageBuilder::new(
+            message.chat.id,
+            actions_service,
+            categories_service,
+            members_service,
+            settings_service,
+            callback,
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use crate::handlers::actions::message_builders::{show_action_log, undo_action};
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::transactions;
+use crate::models::action::{ActionKind, Snapshot};
+use crate::proto::callback::v1::ShowActionLog;
+use crate::services;
+use crate::telegram;
+use crate::telegram::{Dialog, HandlerResult};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::types::{InlineKeyboardMarkup, MessageId};
+
+pub async fn undo(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    actions_service: Arc<dyn services::actions::Service>,
+    categories_service: Arc<dyn services::categories::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+) -> HandlerResult {
+    let action = actions_service
+        .undo_last_action(
+            message.chat.id.0,
+            message.from.as_ref().map(telegram::user_id),
+        )
+        .await?;
+
+    // Imported transactions are undone by deleting them and have no reply messages.
+    if let Some(action) = &action
+        && action.kind == ActionKind::TransactionsCategorized
+        && let Some(Snapshot::Transactions(transactions)) = action.snapshot()
+    {
+        for transaction in transactions {
+            let transaction = transactions_service
+                .get_transaction(message.chat.id.0, transaction.id)
+                .await?;
+            transactions::message_handlers::refresh_reply_message(
+                &bot,
+                &transaction,
+                categories_service.clone(),
+                transactions_service.clone(),
+            )
+            .await?;
+        }
+    }
+
+    if let Some(action) = &action
+        && let Some(Snapshot::Transaction(transaction)) = action.snapshot()
+    {
+        match action.kind {
+            ActionKind::TransactionCreated => {
+                if let Some(reply_message_id) = transaction.reply_message_id {
+                    bot.edit_message_text(
+                        message.chat.id,
+                        MessageId(reply_message_id),
+                        "↩️ Операция отменена.",
+                    )
+                    .reply_markup(InlineKeyboardMarkup::default())
+                    .await?;
+                }
+            }
+            _ => {
+                let transaction = transactions_service
+                    .get_transaction(message.chat.id.0, transaction.id)
+                    .await?;
+                transactions::message_handlers::refresh_reply_message(
+                    &bot,
+                    &transaction,
+                    categories_service,
+                    transactions_service,
+                )
+                .await?;
+            }
+        }
+    }
+
+    let builder = undo_action::MessageBuilder::new(action);
+
+    bot.send_message(message.chat.id, builder.text().await?)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn show_action_log(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    actions_service: Arc<dyn services::actions::Service>,
+    categories_service: Arc<dyn services::categories::Service>,
+    members_service: Arc<dyn services::members::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+) -> HandlerResult {
+    let builder = show_action_log::MessageBuilder::new(
+        message.chat.id,
+        actions_service,
+        categories_service,
+        members_service,
+        settings_service,
+        ShowActionLog { page: 0 },
+    );
+
+    bot.send_message(message.chat.id, builder.text().await?)
+        .reply_markup(builder.reply_markup().await?)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,2 @@
+pub mod show_action_log;
+pub mod undo_action;
@@ -0,0 +1,317 @@
+use crate::dates;
+use crate::handlers::callback;
+use crate::models::action::{Action, ActionKind, Snapshot};
+use crate::models::category::Category;
+use crate::models::transaction::{Amount, Transaction};
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, ShowActionLog};
+use crate::services;
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use std::sync::Arc;
+use teloxide::prelude::ChatId;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+const PAGE_SIZE: i64 = 10;
+
+pub struct MessageBuilder {
+    chat_id: ChatId,
+    actions_service: Arc<dyn services::actions::Service>,
+    categories_service: Arc<dyn services::categories::Service>,
+    members_service: Arc<dyn services::members::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    callback: ShowActionLog,
+}
+
+impl MessageBuilder {
+    pub fn new(
+        chat_id: ChatId,
+        actions_service: Arc<dyn services::actions::Service>,
+        categories_service: Arc<dyn services::categories::Service>,
+        members_service: Arc<dyn services::members::Service>,
+        settings_service: Arc<dyn services::settings::Service>,
+        callback: ShowActionLog,
+    ) -> Self {
+        Self {
+            chat_id,
+            actions_service,
+            categories_service,
+            members_service,
+            settings_service,
+            callback,
+        }
+    }
+
+    async fn page_count(&self) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        let count = self.actions_service.count_actions(self.chat_id.0).await?;
+
+        Ok(((count + PAGE_SIZE - 1) / PAGE_SIZE).max(1))
+    }
+}
+
+fn describe_transaction(transaction: &Transaction) -> String {
+    format!(
+        "{} {} {}",
+        Amount(transaction.amount),
+        transaction.currency_code.as_symbol_str(),
+        transaction.description
+    )
+}
+
+fn describe_transaction_changes(
+    before: &Transaction,
+    after: &Transaction,
+    category_names: &HashMap<i64, String>,
+    timezone: Tz,
+) -> Vec<String> {
+    let category_name = |category_id: Option<i64>| match category_id {
+        Some(category_id) => category_names
+            .get(&category_id)
+            .cloned()
+            .unwrap_or_else(|| "удалённая категория".to_string()),
+        None => "без категории".to_string(),
+    };
+
+    let mut changes = Vec::new();
+    if before.amount != after.amount {
+        changes.push(format!(
+            "сумма {} → {}",
+            Amount(before.amount),
+            Amount(after.amount)
+        ));
+    }
+    if before.description != after.description {
+        changes.push(format!(
+            "описание «{}» → «{}»",
+            before.description, after.description
+        ));
+    }
+    if before.category_id != after.category_id {
+        changes.push(format!(
+            "категория {} → {}",
+            category_name(before.category_id),
+            category_name(after.category_id)
+        ));
+    }
+    if before.currency_code != after.currency_code {
+        changes.push(format!(
+            "валюта {} → {}",
+            before.currency_code.as_code_str(),
+            after.currency_code.as_code_str()
+        ));
+    }
+    let (before_date, after_date) = (
+        dates::local_date(timezone, before.created_at),
+        dates::local_date(timezone, after.created_at),
+    );
+    if before_date != after_date {
+        changes.push(format!(
+            "дата {} → {}",
+            before_date.format("%d.%m.%Y"),
+            after_date.format("%d.%m.%Y")
+        ));
+    }
+
+    changes
+}
+
+fn describe_category_changes(before: &Category, after: &Category) -> Vec<String> {
+    let mut changes = Vec::new();
+    if before.name != after.name {
+        changes.push(format!("название «{}» → «{}»", before.name, after.name));
+    }
+    if before.label != after.label {
+        changes.push(format!("ярлык {} → {}", before.label, after.label));
+    }
+    if before.direction != after.direction {
+        changes.push("тип".to_string());
+    }
+    if before.is_regular != after.is_regular {
+        changes.push("регулярность".to_string());
+    }
+    if before.parent_id != after.parent_id {
+        changes.push("родительская категория".to_string());
+    }
+    if before.target_amount != after.target_amount {
+        changes.push(format!(
+            "сумма {} → {}",
+            Amount(before.target_amount.unwrap_or(0)),
+            Amount(after.target_amount.unwrap_or(0))
+        ));
+    }
+
+    changes
+}
+
+fn describe_action(action: &Action, category_names: &HashMap<i64, String>, timezone: Tz) -> String {
+    match (action.kind, &action.before, &action.after) {
+        (ActionKind::TransactionCreated, _, Some(Snapshot::Transaction(after))) => {
+            format!("➕ Операция: {}", describe_transaction(after))
+        }
+        (
+            ActionKind::TransactionUpdated,
+            Some(Snapshot::Transaction(before)),
+            Some(Snapshot::Transaction(after)),
+        ) => format!(
+            "✏️ Операция {}: {}",
+            describe_transaction(before),
+            describe_transaction_changes(before, after, category_names, timezone).join(", ")
+        ),
+        (ActionKind::TransactionUpdated, Some(Snapshot::Transaction(before)), None) => {
+            format!("✏️ Операция: {}", describe_transaction(before))
+        }
+        (ActionKind::TransactionDeleted, Some(Snapshot::Transaction(before)), _) => {
+            format!("🗑 Операция: {}", describe_transaction(before))
+        }
+        (
+            ActionKind::TransactionsCategorized,
+            Some(Snapshot::Transactions(before)),
+            Some(Snapshot::Transactions(after)),
+        ) => match (before.first(), after.first()) {
+            (Some(first_before), Some(first_after)) => format!(
+                "🏷 Операции «{}», {} шт.: {}",
+                first_before.description,
+                before.len(),
+                describe_transaction_changes(first_before, first_after, category_names, timezone)
+                    .join(", ")
+            ),
+            _ => "🏷 Операции".to_string(),
+        },
+        (ActionKind::TransactionsImported, _, Some(Snapshot::Transactions(after))) => {
+            format!("📥 Импорт операций: {} шт.", after.len())
+        }
+        (ActionKind::CategoriesCreated, _, Some(Snapshot::Categories(after))) => format!(
+            "➕ Категории: {}",
+            after
+                .iter()
+                .map(|category| format!("{} {}", category.label, category.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        (ActionKind::CategoryCreated, _, Some(Snapshot::Category(after))) => {
+            format!("➕ Категория: {} {}", after.label, after.name)
+        }
+        (
+            ActionKind::CategoryUpdated,
+            Some(Snapshot::Category(before)),
+            Some(Snapshot::Category(after)),
+        ) => format!(
+            "✏️ Категория {} {}: {}",
+            before.label,
+            before.name,
+            describe_category_changes(before, after).join(", ")
+        ),
+        (ActionKind::CategoryUpdated, Some(Snapshot::Category(before)), None) => {
+            format!("✏️ Категория: {} {}", before.label, before.name)
+        }
+        (ActionKind::CategoryDeleted, Some(Snapshot::Category(before)), _) => {
+            format!("🗑 Категория: {} {}", before.label, before.name)
+        }
+        (
+            ActionKind::CategoryMerged,
+            Some(Snapshot::Merge {
+                source,
+                target,
+                transaction_ids,
+                ..
+            }),
+            _,
+        ) => format!(
+            "🔀 Категория {} {} → {} {}, операций: {}",
+            source.label,
+            source.name,
+            target.label,
+            target.name,
+            transaction_ids.len()
+        ),
+        (ActionKind::CategoryRestored, _, Some(Snapshot::Category(after))) => {
+            format!("♻️ Категория: {} {}", after.label, after.name)
+        }
+        (kind, _, _) => format!("{kind:?}"),
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let page = self.callback.page.clamp(0, self.page_count().await? - 1);
+        let actions = self
+            .actions_service
+            .select_actions(self.chat_id.0, PAGE_SIZE, page * PAGE_SIZE)
+            .await?;
+
+        if actions.is_empty() {
+            return Ok("📜 Журнал изменений пуст.".to_string());
+        }
+
+        let category_names: HashMap<i64, String> = self
+            .categories_service
+            .select_all_categories(self.chat_id.0)
+            .await?
+            .into_iter()
+            .map(|category| (category.id, format!("{} {}", category.label, category.name)))
+            .collect();
+        let member_names = self.members_service.member_names(self.chat_id.0).await?;
+        let member_name = |user_id: Option<i64>| match user_id {
+            Some(user_id) => member_names
+                .get(&user_id)
+                .cloned()
+                .unwrap_or_else(|| format!("id{user_id}")),
+            None => "бот".to_string(),
+        };
+        let settings = self.settings_service.get_settings(self.chat_id.0).await?;
+
+        let mut text = format!(
+            "📜 Журнал изменений (стр. {}/{})",
+            page + 1,
+            self.page_count().await?
+        );
+        for action in actions {
+            text.push_str(&format!(
+                "\n\n{} · {}\n{}",
+                action
+                    .created_at
+                    .with_timezone(&settings.timezone)
+                    .format("%d.%m.%Y %H:%M"),
+                member_name(action.user_id),
+                describe_action(&action, &category_names, settings.timezone)
+            ));
+
+            if action.reverted_at.is_some() {
+                text.push_str(&format!(
+                    "\n↩️ отменено ({})",
+                    member_name(action.reverted_by)
+                ));
+            }
+        }
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let page_count = self.page_count().await?;
+        let page = self.callback.page.clamp(0, page_count - 1);
+
+        let button = |text: &str, page: i64| {
+            InlineKeyboardButton::callback(
+                text,
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowActionLog(ShowActionLog { page })),
+                })
+                .unwrap(),
+            )
+        };
+
+        let mut row = Vec::new();
+        if page > 0 {
+            row.push(button("⬅️ Новее", page - 1));
+        }
+        if page + 1 < page_count {
+            row.push(button("Старее ➡️", page + 1));
+        }
+
+        Ok(InlineKeyboardMarkup::default().append_row(row))
+    }
+}
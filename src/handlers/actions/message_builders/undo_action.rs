@@ -0,0 +1,83 @@
+use crate::handlers::callback;
+use crate::models::action::{Action, ActionKind, Snapshot};
+use crate::models::transaction::Amount;
+use teloxide::types::InlineKeyboardMarkup;
+
+pub struct MessageBuilder {
+    action: Option<Action>,
+}
+
+impl MessageBuilder {
+    pub fn new(action: Option<Action>) -> Self {
+        Self { action }
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(action) = &self.action else {
+            return Ok("🤷 Нечего отменять.".to_string());
+        };
+
+        let what = match action.kind {
+            ActionKind::TransactionCreated => "добавление операции",
+            ActionKind::TransactionUpdated => "изменение операции",
+            ActionKind::TransactionDeleted => "удаление операции",
+            ActionKind::CategoryCreated => "создание категории",
+            ActionKind::CategoryUpdated => "изменение категории",
+            ActionKind::CategoryDeleted => "удаление категории",
+            ActionKind::CategoryRestored => "восстановление категории",
+            ActionKind::TransactionsCategorized => "выбор категории для похожих операций",
+            ActionKind::TransactionsImported => "импорт операций",
+            ActionKind::CategoriesCreated => "создание категорий",
+            ActionKind::CategoryMerged => "объединение категорий",
+        };
+
+        // The merge is described by what it was applied to.
+        let snapshot = match action.kind {
+            ActionKind::CategoryMerged => action.before.as_ref(),
+            _ => action.snapshot(),
+        };
+        let subject = match snapshot {
+            Some(Snapshot::Transaction(transaction)) => format!(
+                "{} {} {}",
+                Amount(transaction.amount),
+                transaction.currency_code.as_symbol_str(),
+                transaction.description
+            ),
+            Some(Snapshot::Category(category)) => format!("{} {}", category.label, category.name),
+            Some(Snapshot::Transactions(transactions))
+                if action.kind == ActionKind::TransactionsImported =>
+            {
+                format!("операций: {}", transactions.len())
+            }
+            Some(Snapshot::Transactions(transactions)) => format!(
+                "«{}», операций: {}",
+                transactions
+                    .first()
+                    .map(|transaction| transaction.description.as_str())
+                    .unwrap_or_default(),
+                transactions.len()
+            ),
+            Some(Snapshot::Categories(categories)) => categories
+                .iter()
+                .map(|category| format!("{} {}", category.label, category.name))
+                .collect::<Vec<_>>()
+                .join(", "),
+            Some(Snapshot::Merge { source, target, .. }) => format!(
+                "{} {} → {} {}",
+                source.label, source.name, target.label, target.name
+            ),
+            None => String::new(),
+        };
+
+        Ok(format!("↩️ Отменено {what}: {subject}"))
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(InlineKeyboardMarkup::default())
+    }
+}
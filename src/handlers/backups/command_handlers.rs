@@ -0,0 +1,65 @@
+use crate::handlers::members::message_handlers::is_owner;
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult, State};
+use chrono::Utc;
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::types::InputFile;
+
+pub async fn backup(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    backups_service: Arc<dyn services::backups::Service>,
+    members_service: Arc<dyn services::members::Service>,
+) -> HandlerResult {
+    if !is_owner(message.to_owned(), members_service).await {
+        bot.send_message(
+            message.chat.id,
+            "⛔ Резервную копию может сделать только владелец.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let backup = backups_service.backup(message.chat.id.0).await?;
+    let file_name = format!("budgetgram_backup_{}.json", Utc::now().format("%Y-%m-%d"));
+
+    bot.send_document(
+        message.chat.id,
+        InputFile::memory(backup).file_name(file_name),
+    )
+    .caption("💾 Резервная копия. Загрузить её в пустой чат можно командой /restore.")
+    .await?;
+
+    Ok(())
+}
+
+pub async fn restore(
+    bot: Bot,
+    dialog: Dialog,
+    message: Message,
+    members_service: Arc<dyn services::members::Service>,
+) -> HandlerResult {
+    if !is_owner(message.to_owned(), members_service).await {
+        bot.send_message(
+            message.chat.id,
+            "⛔ Восстановить данные может только владелец.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    bot.send_message(
+        message.chat.id,
+        "📥 Отправьте файл резервной копии, сделанный командой /backup. \
+        Данные можно загрузить только в чат без категорий и операций.\n\n\
+        Чтобы отменить, отправьте любое сообщение.",
+    )
+    .await?;
+
+    dialog.update(State::RestoreBackup).await?;
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::handlers::members::message_handlers::is_owner;
+use crate::services;
+use crate::services::backups::RestoreResult;
+use crate::telegram::{Dialog, HandlerResult};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::net::Download;
+use teloxide::prelude::*;
+
+/// Telegram does not let bots download larger files.
+const MAX_FILE_SIZE: u32 = 20 * 1024 * 1024;
+
+pub async fn restore_backup(
+    bot: Bot,
+    dialog: Dialog,
+    message: Message,
+    backups_service: Arc<dyn services::backups::Service>,
+    members_service: Arc<dyn services::members::Service>,
+) -> HandlerResult {
+    let Some(document) = message.document() else {
+        dialog.exit().await?;
+        bot.send_message(message.chat.id, "Восстановление отменено.")
+            .await?;
+        return Ok(());
+    };
+
+    if !is_owner(message.to_owned(), members_service).await {
+        bot.send_message(
+            message.chat.id,
+            "⛔ Восстановить данные может только владелец.",
+        )
+        .await?;
+        return Ok(());
+    }
+
+    if document.file.size > MAX_FILE_SIZE {
+        bot.send_message(message.chat.id, "Файл слишком большой: не больше 20 МБ.")
+            .await?;
+        return Ok(());
+    }
+
+    let file = bot.get_file(document.file.id.to_owned()).await?;
+    let mut data = Vec::new();
+    bot.download_file(&file.path, &mut data).await?;
+
+    let text = match backups_service.restore(message.chat.id.0, &data).await? {
+        RestoreResult::Restored {
+            categories,
+            transactions,
+        } => {
+            format!("✅ Данные восстановлены: категорий — {categories}, операций — {transactions}.")
+        }
+        RestoreResult::NotEmpty => {
+            "В этом чате уже есть данные. Восстановить копию можно только в пустой чат.".to_string()
+        }
+        RestoreResult::Invalid(reason) => {
+            bot.send_message(
+                message.chat.id,
+                format!(
+                    "❌ {reason}\n\nОтправьте другой файл или любое сообщение, чтобы отменить."
+                ),
+            )
+            .await?;
+            return Ok(());
+        }
+    };
+
+    dialog.exit().await?;
+    bot.send_message(message.chat.id, text).await?;
+
+    Ok(())
+}
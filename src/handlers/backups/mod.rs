@@ -0,0 +1,2 @@
+pub mod command_handlers;
+pub mod message_handlers;
\ No newline at end of file
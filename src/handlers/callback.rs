@@ -1,18 +1,29 @@
-use crate::handlers::{categories, settings, transactions};
-use crate::proto::callback::v1::Callback;
+use crate::handlers::{
+    actions, categories, charts, debts, exports, imports, members, reports, settings, transactions,
+};
+use crate::models::member::has_role;
 use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, MemberRole};
 use crate::services;
+use crate::telegram;
 use crate::telegram::{Dialog, HandlerResult};
 use std::sync::Arc;
 use teloxide::Bot;
 use teloxide::prelude::*;
 use teloxide::types::InlineKeyboardMarkup;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn match_callback_query(
     bot: Bot,
     dialog: Dialog,
     callback_query: CallbackQuery,
+    actions_service: Arc<dyn services::actions::Service>,
     categories_service: Arc<dyn services::categories::Service>,
+    debts_service: Arc<dyn services::debts::Service>,
+    exports_service: Arc<dyn services::exports::Service>,
+    members_service: Arc<dyn services::members::Service>,
+    reports_service: Arc<dyn services::reports::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
     transactions_service: Arc<dyn services::transactions::Service>,
 ) -> HandlerResult {
     let Some(data) = callback_query.to_owned().data else {
@@ -24,6 +35,23 @@ pub async fn match_callback_query(
     let Some(query) = callback.query else {
         return Ok(());
     };
+    let Some(chat_id) = callback_query
+        .regular_message()
+        .map(|message| message.chat.id)
+    else {
+        return Ok(());
+    };
+
+    let role = members_service
+        .get_role(chat_id.0, telegram::user_id(&callback_query.from))
+        .await?;
+    if !has_role(role, required_role(&query)) {
+        bot.answer_callback_query(callback_query.id.to_owned())
+            .text("⛔ Недостаточно прав")
+            .show_alert(true)
+            .await?;
+        return Ok(());
+    }
 
     match query {
         Query::ShowMainSettings(show_setting) => {
@@ -42,6 +70,79 @@ pub async fn match_callback_query(
                 callback_query.to_owned(),
                 show_categories_settings.to_owned(),
                 categories_service,
+                settings_service,
+            )
+            .await?;
+        }
+        Query::MoveCategory(move_category) => {
+            categories::callback_handlers::move_category(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                move_category.to_owned(),
+                categories_service,
+            )
+            .await?;
+        }
+        Query::UpdateCategorySort(update_category_sort) => {
+            categories::callback_handlers::update_category_sort(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                update_category_sort.to_owned(),
+                categories_service,
+                settings_service,
+            )
+            .await?;
+        }
+        Query::DeleteCategory(delete_category) => {
+            categories::callback_handlers::delete_category(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                delete_category.to_owned(),
+                categories_service,
+                settings_service,
+            )
+            .await?;
+        }
+        Query::ShowCategoryArchive(show_category_archive) => {
+            categories::callback_handlers::show_category_archive(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                show_category_archive.to_owned(),
+                categories_service,
+            )
+            .await?;
+        }
+        Query::RestoreCategory(restore_category) => {
+            categories::callback_handlers::restore_category(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                restore_category.to_owned(),
+                categories_service,
+            )
+            .await?;
+        }
+        Query::ApplyCategoryPack(apply_category_pack) => {
+            categories::callback_handlers::apply_category_pack(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                apply_category_pack.to_owned(),
+                categories_service,
+            )
+            .await?;
+        }
+        Query::MergeCategory(merge_category) => {
+            categories::callback_handlers::merge_category(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                merge_category.to_owned(),
+                categories_service,
             )
             .await?;
         }
@@ -55,6 +156,26 @@ pub async fn match_callback_query(
             )
             .await?;
         }
+        Query::SelectCategoryParent(select_category_parent) => {
+            categories::callback_handlers::select_category_parent(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                select_category_parent.to_owned(),
+                categories_service,
+            )
+            .await?;
+        }
+        Query::UpdateCategoryParent(update_category_parent) => {
+            categories::callback_handlers::update_category_parent(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                update_category_parent.to_owned(),
+                categories_service,
+            )
+            .await?;
+        }
         Query::CreateCategory(create_category) => {
             categories::callback_handlers::create_category(
                 bot.to_owned(),
@@ -82,6 +203,14 @@ pub async fn match_callback_query(
             )
             .await?;
         }
+        Query::CancelUpdateCategory(_) => {
+            categories::callback_handlers::cancel_update_category(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+            )
+            .await?;
+        }
         Query::CreateTransaction(create_transaction) => {
             transactions::callback_handlers::create_transaction(
                 bot.to_owned(),
@@ -93,6 +222,265 @@ pub async fn match_callback_query(
             )
             .await?;
         }
+        Query::ApproveTransactionCategory(approve_transaction_category) => {
+            transactions::callback_handlers::approve_transaction_category(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                categories_service,
+                transactions_service,
+                approve_transaction_category.to_owned(),
+            )
+            .await?;
+        }
+        Query::ShowTransaction(show_transaction) => {
+            transactions::callback_handlers::show_transaction(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                categories_service,
+                settings_service,
+                transactions_service,
+                show_transaction.to_owned(),
+            )
+            .await?;
+        }
+        Query::UpdateTransaction(update_transaction) => {
+            transactions::callback_handlers::update_transaction(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                categories_service,
+                settings_service,
+                transactions_service,
+                update_transaction.to_owned(),
+            )
+            .await?;
+        }
+        Query::CancelUpdateTransaction(_) => {
+            transactions::callback_handlers::cancel_update_transaction(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+            )
+            .await?;
+        }
+        Query::ApproveTransactionCurrency(approve_transaction_currency) => {
+            transactions::callback_handlers::approve_transaction_currency(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                categories_service,
+                settings_service,
+                transactions_service,
+                approve_transaction_currency.to_owned(),
+            )
+            .await?;
+        }
+        Query::ShowActionLog(show_action_log) => {
+            actions::callback_handlers::show_action_log(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                actions_service,
+                categories_service,
+                members_service,
+                settings_service,
+                show_action_log.to_owned(),
+            )
+            .await?;
+        }
+        Query::ShowHistory(show_history) => {
+            transactions::callback_handlers::show_history(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                categories_service,
+                members_service,
+                settings_service,
+                transactions_service,
+                show_history.to_owned(),
+            )
+            .await?;
+        }
+        Query::ShowTriage(show_triage) => {
+            transactions::callback_handlers::show_triage(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                categories_service,
+                settings_service,
+                transactions_service,
+                show_triage.to_owned(),
+            )
+            .await?;
+        }
+        Query::TriageTransaction(triage_transaction) => {
+            transactions::callback_handlers::triage_transaction(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                categories_service,
+                settings_service,
+                transactions_service,
+                triage_transaction.to_owned(),
+            )
+            .await?;
+        }
+        Query::ShowReport(show_report) => {
+            reports::callback_handlers::show_report(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                members_service,
+                reports_service,
+                show_report.to_owned(),
+            )
+            .await?;
+        }
+        Query::SettleDebt(settle_debt) => {
+            debts::callback_handlers::settle_debt(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                debts_service,
+                members_service,
+                settle_debt.to_owned(),
+            )
+            .await?;
+        }
+        Query::ShowMemberList(show_member_list) => {
+            members::callback_handlers::show_member_list(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                members_service,
+                show_member_list.to_owned(),
+            )
+            .await?;
+        }
+        Query::ShowMemberSettings(show_member_settings) => {
+            members::callback_handlers::show_member_settings(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                members_service,
+                show_member_settings.to_owned(),
+            )
+            .await?;
+        }
+        Query::UpdateMemberRole(update_member_role) => {
+            members::callback_handlers::update_member_role(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                members_service,
+                update_member_role.to_owned(),
+            )
+            .await?;
+        }
+        Query::ExportTransactions(export_transactions) => {
+            exports::callback_handlers::export_transactions(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                exports_service,
+                settings_service,
+                export_transactions.to_owned(),
+            )
+            .await?;
+        }
+        Query::SelectImportColumn(select_import_column) => {
+            imports::callback_handlers::select_import_column(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                select_import_column.to_owned(),
+            )
+            .await?;
+        }
+        Query::ApplyImport(apply_import) => {
+            imports::callback_handlers::apply_import(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                settings_service,
+                transactions_service,
+                apply_import.to_owned(),
+            )
+            .await?;
+        }
+        Query::CancelImport(cancel_import) => {
+            imports::callback_handlers::cancel_import(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                cancel_import.to_owned(),
+            )
+            .await?;
+        }
+        Query::ShowChart(show_chart) => {
+            charts::callback_handlers::show_chart(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                reports_service,
+                show_chart.to_owned(),
+            )
+            .await?;
+        }
+        Query::ShowTrend(show_trend) => {
+            charts::callback_handlers::show_trend(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                categories_service,
+                reports_service,
+                settings_service,
+                show_trend.to_owned(),
+            )
+            .await?;
+        }
+        Query::ShowDigestSettings(show_digest_settings) => {
+            settings::callback_handlers::show_digest_settings(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                settings_service,
+                show_digest_settings.to_owned(),
+            )
+            .await?;
+        }
+        Query::UpdateDigestSettings(update_digest_settings) => {
+            settings::callback_handlers::update_digest_settings(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                settings_service,
+                update_digest_settings.to_owned(),
+            )
+            .await?;
+        }
+        Query::ShowTimezoneSettings(show_timezone_settings) => {
+            settings::callback_handlers::show_timezone_settings(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                settings_service,
+                show_timezone_settings.to_owned(),
+            )
+            .await?;
+        }
+        Query::UpdateTimezone(update_timezone) => {
+            settings::callback_handlers::update_timezone(
+                bot.to_owned(),
+                dialog.to_owned(),
+                callback_query.to_owned(),
+                settings_service,
+                update_timezone.to_owned(),
+            )
+            .await?;
+        }
         _ => {}
     }
 
@@ -102,6 +490,17 @@ pub async fn match_callback_query(
     Ok(())
 }
 
+/// Viewers may only look at reports, and only owners may manage members.
+fn required_role(query: &Query) -> MemberRole {
+    match query {
+        Query::ShowReport(_) | Query::ShowChart(_) | Query::ShowTrend(_) => MemberRole::Viewer,
+        Query::ShowMemberList(_) | Query::ShowMemberSettings(_) | Query::UpdateMemberRole(_) => {
+            MemberRole::Owner
+        }
+        _ => MemberRole::Editor,
+    }
+}
+
 #[async_trait::async_trait]
 pub trait MessageBuilder {
     async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>>;
@@ -1,11 +1,20 @@
 use crate::handlers::callback::CancellableMessageBuilder;
 use crate::handlers::callback::MessageBuilder;
 use crate::handlers::categories::message_builders::{
-    cancel_update_category, create_category, show_categories_settings, show_category_settings,
-    update_category,
+    apply_category_pack, cancel_update_category, create_category, merge_category,
+    select_category_parent, show_categories_settings, show_category_archive,
+    show_category_settings, update_category,
+};
+use crate::models::category_pack::templates;
+use crate::proto::callback::v1::move_category::Shift;
+use crate::proto::callback::v1::{
+    ApplyCategoryPack, CategoryPack, CategorySort, CreateCategory, DeleteCategory, MergeCategory,
+    MoveCategory, RestoreCategory, SelectCategoryParent, ShowCategoryArchive, ShowCategoryList,
+    ShowCategorySettings, UpdateCategory, UpdateCategoryParent, UpdateCategorySort,
 };
-use crate::proto::callback::v1::{CancelUpdateCategory, CreateCategory, ShowCategoryList, ShowCategorySettings, UpdateCategory};
 use crate::services;
+use crate::services::categories::CreateCategoryRequest;
+use crate::telegram;
 use crate::telegram::{State};
 use crate::telegram::{Dialog, HandlerResult};
 use std::sync::Arc;
@@ -25,28 +34,15 @@ pub async fn create_category(
     let keyboard = builder.reply_markup().await?;
 
     if let Some(message) = callback_query.regular_message() {
-        match dialog.get().await? {
-            Some(state) => match state {
-                State::None => {}
-                State::UpdateCategory {
-                    answer_message_id,
-                    callback,
-                } => {}
-                State::CreateCategory {
-                    answer_message_id,
-                    callback,
-                } => {
-                    bot.send_message(
-                        message.chat.id,
-                        "Пожалуйста, сначала завершите создание текущей категории.",
-                    )
-                    .reply_markup(keyboard)
-                    .parse_mode(ParseMode::MarkdownV2)
-                    .await?;
-                    return Ok(());
-                }
-            },
-            None => {}
+        if let Some(State::CreateCategory { .. }) = dialog.get().await? {
+            bot.send_message(
+                message.chat.id,
+                "Пожалуйста, сначала завершите создание текущей категории.",
+            )
+            .reply_markup(keyboard)
+            .parse_mode(ParseMode::MarkdownV2)
+            .await?;
+            return Ok(());
         }
 
         let answer_message = bot
@@ -72,10 +68,12 @@ pub async fn show_categories_settings(
     callback_query: CallbackQuery,
     query: ShowCategoryList,
     categories_service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
 ) -> HandlerResult {
     let builder = show_categories_settings::MessageBuilder::new(
         callback_query.to_owned(),
         categories_service,
+        settings_service,
         query,
     );
     let text = builder.text().await?;
@@ -147,19 +145,28 @@ pub async fn cancel_update_category(
     bot: Bot,
     dialog: Dialog,
     callback_query: CallbackQuery,
-    callback: CancelUpdateCategory,
-    categories_service: Arc<dyn services::categories::Service>,
 ) -> HandlerResult {
-    let builder = cancel_update_category::MessageBuilder::new(
-        callback_query.to_owned(),
-        categories_service,
-        callback,
-    );
+    let Some(State::UpdateCategory {
+        callback: update_category_callback,
+        answer_message_id,
+    }) = dialog.get().await?
+    else {
+        return Ok(());
+    };
+
+    let builder = cancel_update_category::MessageBuilder::new(update_category_callback);
     let text = builder.text().await?;
     let keyboard = builder.reply_markup().await?;
 
     if let Some(message) = callback_query.regular_message() {
-        bot.edit_text(message, "").await?;
+        bot.send_message(message.chat.id, text)
+            .reply_markup(keyboard)
+            .await?;
+
+        bot.edit_message_reply_markup(message.chat.id, answer_message_id)
+            .reply_markup(InlineKeyboardMarkup::default())
+            .await?;
+
         dialog.exit().await?;
     }
 
@@ -197,3 +204,302 @@ pub async fn cancel_create_category(
 
     Ok(())
 }
+
+pub async fn select_category_parent(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    query: SelectCategoryParent,
+    categories_service: Arc<dyn services::categories::Service>,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let builder = select_category_parent::MessageBuilder::new(
+            message.to_owned(),
+            categories_service,
+            query,
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn update_category_parent(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    query: UpdateCategoryParent,
+    categories_service: Arc<dyn services::categories::Service>,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        categories_service
+            .update_parent(
+                message.chat.id.0,
+                query.category_id,
+                (query.parent_id != 0).then_some(query.parent_id),
+                Some(telegram::user_id(&callback_query.from)),
+            )
+            .await?;
+
+        let builder = show_category_settings::MessageBuilder::new(
+            message.to_owned(),
+            categories_service,
+            ShowCategorySettings {
+                category_id: query.category_id,
+                navigated_from: None,
+            },
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text)
+            .reply_markup(keyboard)
+            .parse_mode(ParseMode::MarkdownV2)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn move_category(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    query: MoveCategory,
+    categories_service: Arc<dyn services::categories::Service>,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let is_moved = categories_service
+            .move_category(
+                message.chat.id.0,
+                query.category_id,
+                Shift::try_from(query.shift)?,
+            )
+            .await?;
+        // Telegram refuses to edit a message without changes.
+        if !is_moved {
+            return Ok(());
+        }
+
+        let builder = show_category_settings::MessageBuilder::new(
+            message.to_owned(),
+            categories_service,
+            ShowCategorySettings {
+                category_id: query.category_id,
+                navigated_from: query.navigated_from,
+            },
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text)
+            .reply_markup(keyboard)
+            .parse_mode(ParseMode::MarkdownV2)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn update_category_sort(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    query: UpdateCategorySort,
+    categories_service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        settings_service
+            .update_category_sort(message.chat.id.0, CategorySort::try_from(query.sort)?)
+            .await?;
+
+        let builder = show_categories_settings::MessageBuilder::new(
+            callback_query.to_owned(),
+            categories_service,
+            settings_service,
+            query.navigated_from.unwrap_or_default(),
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn delete_category(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    query: DeleteCategory,
+    categories_service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let category = categories_service
+            .get_category(message.chat.id.0, query.category_id)
+            .await?;
+        categories_service
+            .delete_category(
+                message.chat.id.0,
+                category.id,
+                Some(telegram::user_id(&callback_query.from)),
+            )
+            .await?;
+
+        let builder = show_categories_settings::MessageBuilder::new(
+            callback_query.to_owned(),
+            categories_service,
+            settings_service,
+            ShowCategoryList {
+                category_direction: i32::from(category.direction),
+                parent_id: category.parent_id.unwrap_or(0),
+            },
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn show_category_archive(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    query: ShowCategoryArchive,
+    categories_service: Arc<dyn services::categories::Service>,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let builder = show_category_archive::MessageBuilder::new(
+            message.to_owned(),
+            categories_service,
+            query,
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn restore_category(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    query: RestoreCategory,
+    categories_service: Arc<dyn services::categories::Service>,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let category = categories_service
+            .restore_category(
+                message.chat.id.0,
+                query.category_id,
+                Some(telegram::user_id(&callback_query.from)),
+            )
+            .await?;
+
+        let builder = show_category_archive::MessageBuilder::new(
+            message.to_owned(),
+            categories_service,
+            query.navigated_from.unwrap_or(ShowCategoryArchive {
+                category_direction: i32::from(category.direction),
+            }),
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn apply_category_pack(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    query: ApplyCategoryPack,
+    categories_service: Arc<dyn services::categories::Service>,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let requests = templates(CategoryPack::try_from(query.pack)?)
+            .iter()
+            .map(|template| CreateCategoryRequest {
+                chat_id: message.chat.id.0,
+                user_id: Some(telegram::user_id(&callback_query.from)),
+                name: template.name.to_string(),
+                label: template.label.to_string(),
+                direction: template.direction,
+                is_regular: template.is_regular,
+                target_amount: None,
+                parent_id: None,
+            })
+            .collect();
+        let created = categories_service.create_categories(requests).await?;
+
+        let builder = apply_category_pack::MessageBuilder::new(created);
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn merge_category(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    query: MergeCategory,
+    categories_service: Arc<dyn services::categories::Service>,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        if !query.is_confirmed {
+            let builder =
+                merge_category::MessageBuilder::new(message.to_owned(), categories_service, query);
+            let text = builder.text().await?;
+            let keyboard = builder.reply_markup().await?;
+
+            bot.edit_text(message, text).reply_markup(keyboard).await?;
+            return Ok(());
+        }
+
+        let target = categories_service
+            .merge_category(
+                message.chat.id.0,
+                query.category_id,
+                query.target_id,
+                Some(telegram::user_id(&callback_query.from)),
+            )
+            .await?;
+
+        let builder = show_category_settings::MessageBuilder::new(
+            message.to_owned(),
+            categories_service,
+            ShowCategorySettings {
+                category_id: target.id,
+                navigated_from: None,
+            },
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text)
+            .reply_markup(keyboard)
+            .parse_mode(ParseMode::MarkdownV2)
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::categories::message_builders::show_category_packs;
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+
+/// Greets the chat and offers preset categories to start with.
+pub async fn start(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    categories_service: Arc<dyn services::categories::Service>,
+) -> HandlerResult {
+    let builder = show_category_packs::MessageBuilder::new(message.chat.id, categories_service);
+
+    bot.send_message(message.chat.id, builder.text().await?)
+        .reply_markup(builder.reply_markup().await?)
+        .await?;
+
+    Ok(())
+}
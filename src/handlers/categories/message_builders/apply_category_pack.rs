@@ -0,0 +1,49 @@
+use crate::handlers::callback;
+use crate::models::category::Category;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, CategoryDirection, ShowCategoryList};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    created: Vec<Category>,
+}
+
+impl MessageBuilder {
+    pub fn new(created: Vec<Category>) -> Self {
+        Self { created }
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        if self.created.is_empty() {
+            return Ok("👌 Все категории из набора уже есть в чате.".to_string());
+        }
+
+        let mut text = format!("✅ Добавлено категорий: {}\n", self.created.len());
+        for category in &self.created {
+            text.push_str(&format!("\n{} {}", category.label, category.name));
+        }
+        text.push_str("\n\nТеперь отправьте сумму и описание операции, например «350 такси».");
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(
+            InlineKeyboardMarkup::default().append_row(vec![InlineKeyboardButton::callback(
+                "⚙️ Настроить категории",
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowCategoryList(ShowCategoryList {
+                        category_direction: i32::from(CategoryDirection::Expense),
+                        parent_id: 0,
+                    })),
+                })
+                .unwrap(),
+            )]),
+        )
+    }
+}
@@ -1,39 +1,38 @@
 use crate::handlers::callback;
-use crate::proto::callback::v1::CancelUpdateCategory;
-use crate::services;
-use std::sync::Arc;
-use teloxide::prelude::CallbackQuery;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, ShowCategorySettings, UpdateCategory};
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 
 pub struct MessageBuilder {
-    callback_query: CallbackQuery,
-    service: Arc<dyn services::categories::Service>,
-    callback: CancelUpdateCategory,
+    callback: UpdateCategory,
 }
 
 impl MessageBuilder {
-    pub fn new(
-        callback_query: CallbackQuery,
-        service: Arc<dyn services::categories::Service>,
-        callback: CancelUpdateCategory,
-    ) -> Self {
-        Self {
-            callback_query,
-            service,
-            callback,
-        }
+    pub fn new(callback: UpdateCategory) -> Self {
+        Self { callback }
     }
 }
 
 #[async_trait::async_trait]
-impl callback::MessageBuilder for MessageBuilder {
+impl callback::CancellableMessageBuilder for MessageBuilder {
     async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        Ok("".to_string())
+        Ok("⚠️ Изменение категории отменено.".to_string())
     }
 
     async fn reply_markup(
         &self,
     ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
-        Ok(InlineKeyboardMarkup::default())
+        Ok(
+            InlineKeyboardMarkup::default().append_row(vec![InlineKeyboardButton::callback(
+                "🔙 К настройкам категории",
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowCategorySettings(ShowCategorySettings {
+                        category_id: self.callback.category_id,
+                        navigated_from: None,
+                    })),
+                })
+                .unwrap(),
+            )]),
+        )
     }
 }
@@ -27,9 +27,7 @@ impl callback::MessageBuilder for MessageBuilder {
 
 🍎 Фрукты \\- 300".to_string())
             }
-            CategoryDirection::Unspecified => {
-                unreachable!()
-            }
+            CategoryDirection::Unspecified => Err("unspecified category direction".into()),
         }
     }
 
@@ -66,7 +64,7 @@ impl callback::CancellableMessageBuilder for CancellableMessageBuilder {
                 Ok("⚠️ Создание категории расходов отменено.".to_string())
             }
             CategoryDirection::Income => Ok("⚠️ Создание категории доходов отменено.".to_string()),
-            CategoryDirection::Unspecified => unreachable!(),
+            CategoryDirection::Unspecified => Err("unspecified category direction".into()),
         }
     }
 
@@ -79,6 +77,7 @@ impl callback::CancellableMessageBuilder for CancellableMessageBuilder {
                 String::try_from(Callback {
                     query: Option::from(Query::ShowCategoryList(ShowCategoryList {
                         category_direction: self.callback.category_direction,
+                        parent_id: self.callback.parent_id,
                     })),
                 })
                 .unwrap(),
@@ -0,0 +1,145 @@
+use crate::handlers::callback;
+use crate::models::category::subcategories;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, MergeCategory, ShowCategorySettings};
+use crate::services;
+use std::sync::Arc;
+use teloxide::prelude::Message;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    message: Message,
+    service: Arc<dyn services::categories::Service>,
+    callback: MergeCategory,
+}
+
+impl MessageBuilder {
+    pub fn new(
+        message: Message,
+        service: Arc<dyn services::categories::Service>,
+        callback: MergeCategory,
+    ) -> Self {
+        Self {
+            message,
+            service,
+            callback,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let chat_id = self.message.chat.id.0;
+        let category = self
+            .service
+            .get_category(chat_id, self.callback.category_id)
+            .await?;
+
+        if self.callback.target_id == 0 {
+            return Ok(format!(
+                "🔀 С какой категорией объединить «{} {}»? Её операции перейдут в выбранную категорию, а сама она уйдёт в архив.",
+                category.label, category.name
+            ));
+        }
+
+        let target = self
+            .service
+            .get_category(chat_id, self.callback.target_id)
+            .await?;
+        let count = self
+            .service
+            .count_transactions(chat_id, category.id)
+            .await?;
+        let categories = self
+            .service
+            .select_categories(chat_id, category.direction)
+            .await?;
+
+        let mut text = format!(
+            "🔀 Объединить «{} {}» с «{} {}»?\n\nОпераций перейдёт: {count}.",
+            category.label, category.name, target.label, target.name
+        );
+        if subcategories(&categories, category.id)
+            .iter()
+            .any(|subcategory| subcategory.id != target.id)
+        {
+            text.push_str(" Подкатегории тоже перейдут в новую категорию.");
+        }
+        text.push_str(" Лимиты сложатся, а исходная категория уйдёт в архив.");
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let chat_id = self.message.chat.id.0;
+        let category = self
+            .service
+            .get_category(chat_id, self.callback.category_id)
+            .await?;
+
+        let button = |text: String, target_id: i64, is_confirmed: bool| {
+            InlineKeyboardButton::callback(
+                text,
+                String::try_from(Callback {
+                    query: Option::from(Query::MergeCategory(MergeCategory {
+                        category_id: category.id,
+                        target_id,
+                        is_confirmed,
+                    })),
+                })
+                .unwrap(),
+            )
+        };
+
+        if self.callback.target_id != 0 {
+            return Ok(InlineKeyboardMarkup::default().append_row(vec![
+                button("✅ Объединить".to_string(), self.callback.target_id, true),
+                button("🔙 Назад".to_string(), 0, false),
+            ]));
+        }
+
+        let categories = self
+            .service
+            .select_categories(chat_id, category.direction)
+            .await?;
+        let has_subcategories = !subcategories(&categories, category.id).is_empty();
+        // Subcategories cannot have their own, so they only take the subcategories of their parent.
+        let targets: Vec<_> = categories
+            .iter()
+            .filter(|target| target.id != category.id)
+            .filter(|target| {
+                !has_subcategories || target.parent_id.is_none_or(|id| id == category.id)
+            })
+            .collect();
+
+        let mut reply_markup = InlineKeyboardMarkup::default();
+        for chunk in targets.chunks(2) {
+            let row: Vec<InlineKeyboardButton> = chunk
+                .iter()
+                .map(|target| {
+                    button(
+                        format!("{} {}", target.label, target.name),
+                        target.id,
+                        false,
+                    )
+                })
+                .collect();
+
+            reply_markup = reply_markup.append_row(row);
+        }
+
+        Ok(reply_markup.append_row(vec![InlineKeyboardButton::callback(
+            "🔙 Назад",
+            String::try_from(Callback {
+                query: Option::from(Query::ShowCategorySettings(ShowCategorySettings {
+                    category_id: category.id,
+                    navigated_from: None,
+                })),
+            })
+            .unwrap(),
+        )]))
+    }
+}
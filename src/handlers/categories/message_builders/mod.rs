@@ -1,5 +1,10 @@
+pub mod apply_category_pack;
 pub mod create_category;
+pub mod merge_category;
+pub mod select_category_parent;
+pub mod show_category_archive;
+pub mod show_category_packs;
 pub mod show_categories_settings;
 pub mod show_category_settings;
 pub mod update_category;
-pub mod cancel_update_category;
+pub mod cancel_update_category;
\ No newline at end of file
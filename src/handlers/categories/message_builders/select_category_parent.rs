@@ -0,0 +1,119 @@
+use crate::handlers::callback;
+use crate::models::category::{subcategories, top_level};
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{
+    Callback, SelectCategoryParent, ShowCategorySettings, UpdateCategoryParent,
+};
+use crate::services;
+use std::sync::Arc;
+use teloxide::prelude::Message;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    message: Message,
+    service: Arc<dyn services::categories::Service>,
+    callback: SelectCategoryParent,
+}
+
+impl MessageBuilder {
+    pub fn new(
+        message: Message,
+        service: Arc<dyn services::categories::Service>,
+        callback: SelectCategoryParent,
+    ) -> Self {
+        Self {
+            message,
+            service,
+            callback,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let chat_id = self.message.chat.id.0;
+        let category = self
+            .service
+            .get_category(chat_id, self.callback.category_id)
+            .await?;
+        let categories = self
+            .service
+            .select_categories(chat_id, category.direction)
+            .await?;
+
+        if !subcategories(&categories, category.id).is_empty() {
+            return Ok(format!(
+                "↪️ У категории «{} {}» есть подкатегории, поэтому она может быть только верхнего уровня.",
+                category.label, category.name
+            ));
+        }
+
+        Ok(format!(
+            "↪️ Выберите, в какую категорию переместить «{} {}»",
+            category.label, category.name
+        ))
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let chat_id = self.message.chat.id.0;
+        let category = self
+            .service
+            .get_category(chat_id, self.callback.category_id)
+            .await?;
+        let categories = self
+            .service
+            .select_categories(chat_id, category.direction)
+            .await?;
+
+        let button = |text: String, parent_id: i64| {
+            InlineKeyboardButton::callback(
+                text,
+                String::try_from(Callback {
+                    query: Option::from(Query::UpdateCategoryParent(UpdateCategoryParent {
+                        category_id: category.id,
+                        parent_id,
+                    })),
+                })
+                .unwrap(),
+            )
+        };
+
+        let mut reply_markup = InlineKeyboardMarkup::default();
+
+        if subcategories(&categories, category.id).is_empty() {
+            let parents: Vec<_> = top_level(&categories)
+                .into_iter()
+                .filter(|parent| parent.id != category.id && parent.parent_id.is_none())
+                .filter(|parent| Some(parent.id) != category.parent_id)
+                .collect();
+
+            for chunk in parents.chunks(2) {
+                let row: Vec<InlineKeyboardButton> = chunk
+                    .iter()
+                    .map(|parent| button(format!("{} {}", parent.label, parent.name), parent.id))
+                    .collect();
+
+                reply_markup = reply_markup.append_row(row);
+            }
+        }
+
+        if category.parent_id.is_some() {
+            reply_markup =
+                reply_markup.append_row(vec![button("⬆️ На верхний уровень".to_string(), 0)]);
+        }
+
+        Ok(reply_markup.append_row(vec![InlineKeyboardButton::callback(
+            "🔙 Назад",
+            String::try_from(Callback {
+                query: Option::from(Query::ShowCategorySettings(ShowCategorySettings {
+                    category_id: category.id,
+                    navigated_from: None,
+                })),
+            })
+            .unwrap(),
+        )]))
+    }
+}
@@ -1,9 +1,10 @@
 use crate::handlers::callback;
 use crate::models::callback::{Declinable, GrammaticalNumber, Labeled, NameCase};
+use crate::models::category::{subcategories, top_level};
 use crate::proto::callback::v1::callback::Query;
 use crate::proto::callback::v1::{
-    Callback, CategoryDirection, CreateCategory, ShowCategoryList, ShowCategorySettings,
-    ShowMainSettings,
+    Callback, CategoryDirection, CategorySort, CreateCategory, ShowCategoryArchive,
+    ShowCategoryList, ShowCategorySettings, ShowMainSettings, UpdateCategorySort,
 };
 use crate::services;
 use std::sync::Arc;
@@ -13,6 +14,7 @@ use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 pub struct MessageBuilder {
     callback_query: CallbackQuery,
     service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
     query: ShowCategoryList,
 }
 
@@ -20,20 +22,41 @@ impl MessageBuilder {
     pub fn new(
         callback_query: CallbackQuery,
         service: Arc<dyn services::categories::Service>,
+        settings_service: Arc<dyn services::settings::Service>,
         query: ShowCategoryList,
     ) -> Self {
         Self {
             callback_query,
             service,
+            settings_service,
             query,
         }
     }
+
+    fn chat_id(&self) -> Result<i64, &'static str> {
+        self.callback_query
+            .regular_message()
+            .map(|message| message.chat.id.0)
+            .ok_or("callback message is inaccessible")
+    }
 }
 
 #[async_trait::async_trait]
 impl callback::MessageBuilder for MessageBuilder {
     async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let direction = CategoryDirection::try_from(self.query.category_direction)?;
+        if self.query.parent_id != 0 {
+            let parent = self
+                .service
+                .get_category(self.chat_id()?, self.query.parent_id)
+                .await?;
+
+            return Ok(format!(
+                "📂 Подкатегории «{} {}»",
+                parent.label, parent.name
+            ));
+        }
+
         Ok(format!(
             "{} Выберите категорию {}",
             direction.label(),
@@ -47,11 +70,16 @@ impl callback::MessageBuilder for MessageBuilder {
         let list = self
             .service
             .select_categories(
-                self.callback_query.regular_message().unwrap().chat.id.0,
-                CategoryDirection::try_from(self.query.category_direction).unwrap(),
+                self.chat_id()?,
+                CategoryDirection::try_from(self.query.category_direction)?,
             )
             .await?;
 
+        let list = match self.query.parent_id {
+            0 => top_level(&list),
+            parent_id => subcategories(&list, parent_id),
+        };
+
         let mut reply_markup = InlineKeyboardMarkup::default();
 
         for chunk in list.chunks(2) {
@@ -76,12 +104,42 @@ impl callback::MessageBuilder for MessageBuilder {
             reply_markup = reply_markup.append_row(row);
         }
 
+        // The picker order is shared by both directions, so it is set from the top level only.
+        if self.query.parent_id == 0 {
+            let settings = self.settings_service.get_settings(self.chat_id()?).await?;
+            let (text, category_sort) = match settings.category_sort {
+                CategorySort::Usage => ("↕️ При выборе: по частоте", CategorySort::Unspecified),
+                CategorySort::Unspecified => ("↕️ При выборе: по порядку", CategorySort::Usage),
+            };
+
+            reply_markup = reply_markup.append_row(vec![InlineKeyboardButton::callback(
+                text,
+                String::try_from(Callback {
+                    query: Option::from(Query::UpdateCategorySort(UpdateCategorySort {
+                        sort: i32::from(category_sort),
+                        navigated_from: Some(self.query),
+                    })),
+                })
+                .unwrap(),
+            )]);
+            reply_markup = reply_markup.append_row(vec![InlineKeyboardButton::callback(
+                "🗄 Архив",
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowCategoryArchive(ShowCategoryArchive {
+                        category_direction: self.query.category_direction,
+                    })),
+                })
+                .unwrap(),
+            )]);
+        }
+
         Ok(reply_markup.append_row(vec![]).append_row(vec![
             InlineKeyboardButton::callback(
                 "➕ Добавить",
                 String::try_from(Callback {
                     query: Option::from(Query::CreateCategory(CreateCategory {
                         category_direction: self.query.category_direction,
+                        parent_id: self.query.parent_id,
                     })),
                 })
                 .unwrap(),
@@ -89,7 +147,16 @@ impl callback::MessageBuilder for MessageBuilder {
             InlineKeyboardButton::callback(
                 "🔙 Назад",
                 String::try_from(Callback {
-                    query: Option::from(Query::ShowMainSettings(ShowMainSettings {})),
+                    query: Option::from(match self.query.parent_id {
+                        0 => Query::ShowMainSettings(ShowMainSettings {}),
+                        parent_id => Query::ShowCategorySettings(ShowCategorySettings {
+                            category_id: parent_id,
+                            navigated_from: Some(ShowCategoryList {
+                                parent_id: 0,
+                                ..self.query
+                            }),
+                        }),
+                    }),
                 })
                 .unwrap(),
             ),
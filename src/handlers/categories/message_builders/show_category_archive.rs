@@ -0,0 +1,96 @@
+use crate::handlers::callback;
+use crate::models::callback::{Declinable, GrammaticalNumber, NameCase};
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{
+    Callback, CategoryDirection, RestoreCategory, ShowCategoryArchive, ShowCategoryList,
+};
+use crate::services;
+use std::sync::Arc;
+use teloxide::prelude::Message;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    message: Message,
+    service: Arc<dyn services::categories::Service>,
+    callback: ShowCategoryArchive,
+}
+
+impl MessageBuilder {
+    pub fn new(
+        message: Message,
+        service: Arc<dyn services::categories::Service>,
+        callback: ShowCategoryArchive,
+    ) -> Self {
+        Self {
+            message,
+            service,
+            callback,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let direction = CategoryDirection::try_from(self.callback.category_direction)?;
+        let categories = self
+            .service
+            .select_archived_categories(self.message.chat.id.0, direction)
+            .await?;
+
+        if categories.is_empty() {
+            return Ok(format!(
+                "🗄 В архиве нет категорий {}.",
+                direction.decline(NameCase::Genitive, GrammaticalNumber::Plural)
+            ));
+        }
+
+        Ok(format!(
+            "🗄 Архив категорий {}\n\nОперации архивных категорий остаются в истории и отчётах. Нажмите на категорию, чтобы восстановить её.",
+            direction.decline(NameCase::Genitive, GrammaticalNumber::Plural)
+        ))
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let direction = CategoryDirection::try_from(self.callback.category_direction)?;
+        let categories = self
+            .service
+            .select_archived_categories(self.message.chat.id.0, direction)
+            .await?;
+
+        let mut reply_markup = InlineKeyboardMarkup::default();
+
+        for chunk in categories.chunks(2) {
+            let row: Vec<InlineKeyboardButton> = chunk
+                .iter()
+                .map(|category| {
+                    InlineKeyboardButton::callback(
+                        format!("♻️ {} {}", category.label, category.name),
+                        String::try_from(Callback {
+                            query: Option::from(Query::RestoreCategory(RestoreCategory {
+                                category_id: category.id,
+                                navigated_from: Some(self.callback),
+                            })),
+                        })
+                        .unwrap(),
+                    )
+                })
+                .collect();
+
+            reply_markup = reply_markup.append_row(row);
+        }
+
+        Ok(reply_markup.append_row(vec![InlineKeyboardButton::callback(
+            "🔙 Назад",
+            String::try_from(Callback {
+                query: Option::from(Query::ShowCategoryList(ShowCategoryList {
+                    category_direction: self.callback.category_direction,
+                    parent_id: 0,
+                })),
+            })
+            .unwrap(),
+        )]))
+    }
+}
@@ -0,0 +1,80 @@
+use crate::handlers::callback;
+use crate::models::category_pack::{PACKS, templates, title};
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{ApplyCategoryPack, Callback, CategoryDirection, ShowCategoryList};
+use crate::services;
+use std::sync::Arc;
+use teloxide::prelude::ChatId;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    chat_id: ChatId,
+    service: Arc<dyn services::categories::Service>,
+}
+
+impl MessageBuilder {
+    pub fn new(chat_id: ChatId, service: Arc<dyn services::categories::Service>) -> Self {
+        Self { chat_id, service }
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut text = "👋 Привет! Я помогу вести учёт расходов и доходов.
+
+Отправьте сумму и описание, например «350 такси», а я предложу выбрать категорию. Для начала выберите готовый набор категорий, потом его можно изменить в /settings."
+            .to_string();
+
+        for pack in PACKS {
+            let labels: String = templates(pack)
+                .iter()
+                .map(|template| template.label)
+                .collect();
+            text.push_str(&format!("\n\n{}: {labels}", title(pack)));
+        }
+
+        let has_categories = self
+            .service
+            .select_all_categories(self.chat_id.0)
+            .await?
+            .iter()
+            .any(|category| category.deleted_at.is_none());
+        if has_categories {
+            text.push_str(
+                "\n\nВ чате уже есть категории: они останутся, а из набора добавятся только недостающие.",
+            );
+        }
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let mut reply_markup = InlineKeyboardMarkup::default();
+
+        for pack in PACKS {
+            reply_markup = reply_markup.append_row(vec![InlineKeyboardButton::callback(
+                title(pack),
+                String::try_from(Callback {
+                    query: Option::from(Query::ApplyCategoryPack(ApplyCategoryPack {
+                        pack: i32::from(pack),
+                    })),
+                })
+                .unwrap(),
+            )]);
+        }
+
+        Ok(reply_markup.append_row(vec![InlineKeyboardButton::callback(
+            "✍️ Создам сам",
+            String::try_from(Callback {
+                query: Option::from(Query::ShowCategoryList(ShowCategoryList {
+                    category_direction: i32::from(CategoryDirection::Expense),
+                    parent_id: 0,
+                })),
+            })
+            .unwrap(),
+        )]))
+    }
+}
@@ -1,12 +1,15 @@
 use crate::handlers::callback;
+use crate::models::category::siblings;
 use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::move_category::Shift;
 use crate::proto::callback::v1::update_category::Field;
 use crate::proto::callback::v1::{
-    Callback, CategoryDirection, DeleteCategory, ShowCategorySettings, UpdateCategory,
+    Callback, CategoryDirection, DeleteCategory, MergeCategory, MoveCategory, SelectCategoryParent,
+    ShowCategoryList, ShowCategorySettings, ShowTrend, UpdateCategory,
 };
 use crate::services;
 use std::sync::Arc;
-use teloxide::prelude::{CallbackQuery, Message};
+use teloxide::prelude::Message;
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 
 pub struct MessageBuilder {
@@ -37,6 +40,18 @@ impl callback::MessageBuilder for MessageBuilder {
             .get_category(self.message.chat.id.0, self.callback.category_id)
             .await?;
 
+        let categories = self
+            .service
+            .select_categories(self.message.chat.id.0, category.direction)
+            .await?;
+        let siblings = siblings(&categories, &category);
+        let position = siblings
+            .iter()
+            .position(|sibling| sibling.id == category.id)
+            .unwrap_or_default()
+            + 1;
+        let count = siblings.len();
+
         let name = category.name;
         let label = category.label;
         let (direction, target_limit_title, is_regular_title, target_limit) =
@@ -64,7 +79,7 @@ impl callback::MessageBuilder for MessageBuilder {
                     },
                 ),
                 CategoryDirection::Unspecified => {
-                    unreachable!()
+                    return Err("unspecified category direction".into());
                 }
             };
 
@@ -74,11 +89,23 @@ impl callback::MessageBuilder for MessageBuilder {
             "_Нет_"
         };
 
+        let parent = match category.parent_id {
+            Some(parent_id) => {
+                let parent = self
+                    .service
+                    .get_category(self.message.chat.id.0, parent_id)
+                    .await?;
+                format!("\n*Родитель:* {} {}", parent.label, parent.name)
+            }
+            None => String::new(),
+        };
+
         Ok(format!(
             "⚙️ *Настройки категории*
 *Название:* {name}
 *Ярлык:* {label}
-*Тип:* {direction}
+*Тип:* {direction}{parent}
+*Позиция:* {position} из {count}
 *{target_limit_title}:* {target_limit}
 *Постоянный {is_regular_title}:* {is_regular}",
         ))
@@ -90,8 +117,44 @@ impl callback::MessageBuilder for MessageBuilder {
         let category = self
             .service
             .get_category(self.message.chat.id.0, self.callback.category_id)
-            .await
-            .unwrap();
+            .await?;
+
+        let mut hierarchy_row = vec![];
+        if category.parent_id.is_none() {
+            hierarchy_row.push(InlineKeyboardButton::callback(
+                "📂 Подкатегории",
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowCategoryList(ShowCategoryList {
+                        category_direction: i32::from(category.direction),
+                        parent_id: category.id,
+                    })),
+                })
+                .unwrap(),
+            ));
+        }
+        hierarchy_row.push(InlineKeyboardButton::callback(
+            "↪️ Переместить",
+            String::try_from(Callback {
+                query: Option::from(Query::SelectCategoryParent(SelectCategoryParent {
+                    category_id: category.id,
+                })),
+            })
+            .unwrap(),
+        ));
+
+        let move_button = |text: &str, shift: Shift| {
+            InlineKeyboardButton::callback(
+                text,
+                String::try_from(Callback {
+                    query: Option::from(Query::MoveCategory(MoveCategory {
+                        category_id: self.callback.category_id,
+                        shift: i32::from(shift),
+                        navigated_from: self.callback.navigated_from,
+                    })),
+                })
+                .unwrap(),
+            )
+        };
 
         Ok(InlineKeyboardMarkup::default()
             .append_row(vec![
@@ -138,7 +201,7 @@ impl callback::MessageBuilder for MessageBuilder {
                                 "план"
                             }
                             CategoryDirection::Unspecified => {
-                                unreachable!()
+                                return Err("unspecified category direction".into());
                             }
                         }
                     ),
@@ -161,9 +224,36 @@ impl callback::MessageBuilder for MessageBuilder {
                 })
                 .unwrap(),
             )])
+            .append_row(hierarchy_row)
+            .append_row(vec![
+                move_button("⬆️ Выше", Shift::Up),
+                move_button("⬇️ Ниже", Shift::Down),
+            ])
+            .append_row(vec![
+                InlineKeyboardButton::callback(
+                    "📈 Динамика за год",
+                    String::try_from(Callback {
+                        query: Option::from(Query::ShowTrend(ShowTrend {
+                            category_id: self.callback.category_id,
+                        })),
+                    })
+                    .unwrap(),
+                ),
+                InlineKeyboardButton::callback(
+                    "🔀 Объединить",
+                    String::try_from(Callback {
+                        query: Option::from(Query::MergeCategory(MergeCategory {
+                            category_id: self.callback.category_id,
+                            target_id: 0,
+                            is_confirmed: false,
+                        })),
+                    })
+                    .unwrap(),
+                ),
+            ])
             .append_row(vec![
                 InlineKeyboardButton::callback(
-                    "❌ Удалить",
+                    "🗄 В архив",
                     String::try_from(Callback {
                         query: Option::from(Query::DeleteCategory(DeleteCategory {
                             category_id: self.callback.category_id,
@@ -175,7 +265,10 @@ impl callback::MessageBuilder for MessageBuilder {
                     "🔙 Назад",
                     String::try_from(Callback {
                         query: Option::from(Query::ShowCategoryList(
-                            self.callback.navigated_from.unwrap(),
+                            self.callback.navigated_from.unwrap_or(ShowCategoryList {
+                                category_direction: i32::from(category.direction),
+                                parent_id: category.parent_id.unwrap_or(0),
+                            }),
                         )),
                     })
                     .unwrap(),
@@ -27,6 +27,13 @@ impl MessageBuilder {
             query,
         }
     }
+
+    fn chat_id(&self) -> Result<i64, &'static str> {
+        self.callback_query
+            .regular_message()
+            .map(|message| message.chat.id.0)
+            .ok_or("callback message is inaccessible")
+    }
 }
 
 #[async_trait::async_trait]
@@ -34,10 +41,7 @@ impl callback::MessageBuilder for MessageBuilder {
     async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         let category = self
             .service
-            .get_category(
-                self.callback_query.regular_message().unwrap().chat.id.0,
-                self.query.category_id,
-            )
+            .get_category(self.chat_id()?, self.query.category_id)
             .await?;
 
         Ok(match Field::try_from(self.query.field).ok() {
@@ -54,7 +58,7 @@ _Введите новое название категории\\._",
 
 *Текущий ярлык:* {}
 
-Отправьте новый символ \\(например, 🛒, 🍎, 🏷️\\)\\.",
+Отправьте новый эмодзи \\(например, 🛒, 🍎, 🏷️\\)\\.",
                 category.label, category.name, category.label
             ),
             Some(Field::Direction) => "todo".to_string(),
@@ -82,11 +86,11 @@ _Введите новую целевую сумму \\(или «0» для уд
                     category.target_amount.unwrap_or(0),
                 ),
                 CategoryDirection::Unspecified => {
-                    unreachable!()
+                    return Err("unspecified category direction".into());
                 }
             },
             _ => {
-                unreachable!()
+                return Err(format!("unsupported category field {}", self.query.field).into());
             }
         })
     }
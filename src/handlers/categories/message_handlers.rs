@@ -1,6 +1,7 @@
 use crate::proto::callback::v1::CategoryDirection;
 use crate::services;
 use crate::services::categories::CreateCategoryRequest;
+use crate::telegram;
 use crate::telegram::{Dialog, HandlerResult, State};
 use regex::Regex;
 use std::sync::Arc;
@@ -10,7 +11,7 @@ use teloxide::prelude::{Message, Requester};
 use teloxide::types::InlineKeyboardMarkup;
 
 static RE: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
-    Regex::new(r"^(?:(?P<label>[^\p{L}\p{N}\s])\s+)?(?P<name>[^-]+?)\s*(?:-\s*(?P<amount>.+))?$")
+    Regex::new(r"^(?:(?P<label>[^\p{L}\p{N}\s]+)\s+)?(?P<name>[^-]+?)\s*(?:-\s*(?P<amount>.+))?$")
         .expect("invalid regex")
 });
 
@@ -83,15 +84,18 @@ pub async fn create_category(
         return Ok(());
     };
 
+    // The dialog stays open on errors, so the category can be sent again.
     categories_service
         .create_category(CreateCategoryRequest {
             chat_id: message.chat.id.0,
+            user_id: message.from.as_ref().map(telegram::user_id),
             name,
             label,
             direction: CategoryDirection::try_from(callback.category_direction)
                 .unwrap_or(CategoryDirection::Expense),
             is_regular: true,
             target_amount: Some((amount * 100.0) as i64),
+            parent_id: (callback.parent_id != 0).then_some(callback.parent_id),
         })
         .await?;
 
@@ -1,3 +1,4 @@
 pub mod callback_handlers;
+pub mod command_handlers;
 mod message_builders;
 pub mod message_handlers;
\ No newline at end of file
@@ -0,0 +1,56 @@
+use crate::handlers::charts::command_handlers::{send_chart, send_trend};
+use crate::proto::callback::v1::{ShowChart, ShowTrend};
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+
+pub async fn show_chart(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    reports_service: Arc<dyn services::reports::Service>,
+    callback: ShowChart,
+) -> HandlerResult {
+    if !(1..=12).contains(&callback.month) {
+        return Ok(());
+    }
+
+    if let Some(message) = callback_query.regular_message() {
+        send_chart(
+            &bot,
+            message.chat.id,
+            reports_service,
+            callback.year,
+            callback.month,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn show_trend(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    categories_service: Arc<dyn services::categories::Service>,
+    reports_service: Arc<dyn services::reports::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    callback: ShowTrend,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        send_trend(
+            &bot,
+            message.chat.id,
+            categories_service,
+            reports_service,
+            settings_service,
+            Some(callback.category_id).filter(|category_id| *category_id != 0),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
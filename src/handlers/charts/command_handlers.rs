@@ -0,0 +1,282 @@
+use crate::dates;
+use crate::handlers::charts::render::{self, Bar, Series};
+use crate::models::report::{MONTHS, MonthTotals, Totals};
+use crate::models::transaction::{Amount, CurrencyCode};
+use crate::proto::callback::v1::CategoryDirection;
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use chrono::Datelike;
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::types::InputFile;
+
+/// Categories beyond this are drawn as a single "other" bar.
+const MAX_BARS: usize = 12;
+/// Months shown on a trend chart.
+const TREND_MONTHS: u32 = 12;
+
+pub async fn show_chart(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    reports_service: Arc<dyn services::reports::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+) -> HandlerResult {
+    let settings = settings_service.get_settings(message.chat.id.0).await?;
+    let today = dates::today(settings.timezone);
+
+    send_chart(
+        &bot,
+        message.chat.id,
+        reports_service,
+        today.year(),
+        today.month(),
+    )
+    .await
+}
+
+pub async fn show_trend(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    categories_service: Arc<dyn services::categories::Service>,
+    reports_service: Arc<dyn services::reports::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+) -> HandlerResult {
+    send_trend(
+        &bot,
+        message.chat.id,
+        categories_service,
+        reports_service,
+        settings_service,
+        None,
+    )
+    .await
+}
+
+/// The currency most of the money was spent in; amounts in different currencies
+/// cannot share one axis.
+fn main_currency(totals: &Totals) -> CurrencyCode {
+    CurrencyCode::ALL
+        .iter()
+        .copied()
+        .max_by_key(|currency_code| totals.get(*currency_code))
+        .unwrap_or(CurrencyCode::RUB)
+}
+
+/// Sends the month's expenses per category as a bar chart.
+pub async fn send_chart(
+    bot: &Bot,
+    chat_id: ChatId,
+    reports_service: Arc<dyn services::reports::Service>,
+    year: i32,
+    month: u32,
+) -> HandlerResult {
+    let report = reports_service
+        .monthly_report(chat_id.0, year, month)
+        .await?;
+    let period = format!("{} {}", MONTHS[month as usize - 1], year);
+    let currency_code = main_currency(&report.expenses);
+    let total = report.expenses.get(currency_code);
+
+    if total <= 0 {
+        bot.send_message(chat_id, format!("За {period} расходов нет."))
+            .await?;
+        return Ok(());
+    }
+
+    let mut bars: Vec<Bar> = report
+        .expense_categories
+        .iter()
+        .filter(|category_total| category_total.totals.get(currency_code) > 0)
+        .map(|category_total| Bar {
+            label: category_total.title(),
+            value: category_total.totals.get(currency_code),
+        })
+        .collect();
+    bars.sort_by_key(|bar| -bar.value);
+    if bars.len() > MAX_BARS {
+        let other = bars
+            .split_off(MAX_BARS - 1)
+            .iter()
+            .map(|bar| bar.value)
+            .sum();
+        bars.push(Bar {
+            label: "Прочее".to_string(),
+            value: other,
+        });
+    }
+
+    let symbol = currency_code.as_symbol_str();
+    let png = render::bar_chart(&format!("Расходы: {period}"), &bars, symbol)?;
+
+    let mut caption = format!("📊 Расходы за {period}: {} {symbol}\n", Amount(total));
+    for bar in &bars {
+        caption.push_str(&format!(
+            "\n{} — {} {symbol} ({}%)",
+            bar.label,
+            Amount(bar.value),
+            bar.value * 100 / total
+        ));
+    }
+
+    bot.send_photo(chat_id, InputFile::memory(png).file_name("chart.png"))
+        .caption(caption)
+        .await?;
+
+    Ok(())
+}
+
+/// Sends expenses and incomes of the last months as a line chart, or only the amounts
+/// of one category.
+pub async fn send_trend(
+    bot: &Bot,
+    chat_id: ChatId,
+    categories_service: Arc<dyn services::categories::Service>,
+    reports_service: Arc<dyn services::reports::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    category_id: Option<i64>,
+) -> HandlerResult {
+    let settings = settings_service.get_settings(chat_id.0).await?;
+    let today = dates::today(settings.timezone);
+    let months = reports_service
+        .monthly_trend(
+            chat_id.0,
+            category_id,
+            today.year(),
+            today.month(),
+            TREND_MONTHS,
+        )
+        .await?;
+
+    let mut totals = Totals::default();
+    for month in &months {
+        for currency_code in CurrencyCode::ALL {
+            totals.add(currency_code, month.expenses.get(currency_code));
+            totals.add(currency_code, month.incomes.get(currency_code));
+        }
+    }
+    if totals.is_empty() {
+        bot.send_message(chat_id, "За последний год операций нет.")
+            .await?;
+        return Ok(());
+    }
+
+    let currency_code = main_currency(&totals);
+    let symbol = currency_code.as_symbol_str();
+    let labels: Vec<String> = months
+        .iter()
+        .map(|month| {
+            let name: String = MONTHS[month.month as usize - 1].chars().take(3).collect();
+            format!("{name} {:02}", month.year % 100)
+        })
+        .collect();
+    let values = |totals: fn(&MonthTotals) -> &Totals| -> Vec<i64> {
+        months
+            .iter()
+            .map(|month| totals(month).get(currency_code))
+            .collect()
+    };
+
+    let (title, series, caption) = match category_id {
+        Some(category_id) => {
+            let category = categories_service
+                .get_category(chat_id.0, category_id)
+                .await?;
+            let values = if category.direction == CategoryDirection::Income {
+                values(|month| &month.incomes)
+            } else {
+                values(|month| &month.expenses)
+            };
+
+            let caption = describe_trend(
+                &format!("{} {}", category.label, category.name),
+                &values,
+                symbol,
+            );
+            (
+                format!("{}: {TREND_MONTHS} мес.", category.name),
+                vec![Series {
+                    name: category.name,
+                    values,
+                }],
+                caption,
+            )
+        }
+        None => {
+            let expenses = values(|month| &month.expenses);
+            let incomes = values(|month| &month.incomes);
+
+            let caption = format!(
+                "{}\n{}",
+                describe_trend("📉 Расходы", &expenses, symbol),
+                describe_trend("📈 Доходы", &incomes, symbol)
+            );
+            (
+                format!("Расходы и доходы: {TREND_MONTHS} мес."),
+                vec![
+                    Series {
+                        name: "Расходы".to_string(),
+                        values: expenses,
+                    },
+                    Series {
+                        name: "Доходы".to_string(),
+                        values: incomes,
+                    },
+                ],
+                caption,
+            )
+        }
+    };
+
+    let png = render::trend_chart(&title, &labels, &series, symbol)?;
+
+    bot.send_photo(chat_id, InputFile::memory(png).file_name("trend.png"))
+        .caption(caption)
+        .await?;
+
+    Ok(())
+}
+
+/// Compares the current month with the average of the previous ones.
+fn describe_trend(title: &str, values: &[i64], symbol: &str) -> String {
+    let Some((current, previous)) = values.split_last() else {
+        return title.to_string();
+    };
+    let average = if previous.is_empty() {
+        0
+    } else {
+        previous.iter().sum::<i64>() / previous.len() as i64
+    };
+
+    let change = if average == 0 {
+        String::new()
+    } else {
+        let percent = (current - average) * 100 / average;
+        format!(" ({}{percent}%)", if percent > 0 { "+" } else { "" })
+    };
+
+    format!(
+        "{title}: в этом месяце {} {symbol}, в среднем {} {symbol}{change}",
+        Amount(*current),
+        Amount(average)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_trend() {
+        assert_eq!(
+            describe_trend("Еда", &[10000, 30000, 30000], "₽"),
+            "Еда: в этом месяце 300 ₽, в среднем 200 ₽ (+50%)"
+        );
+        assert_eq!(
+            describe_trend("Еда", &[0, 5000], "₽"),
+            "Еда: в этом месяце 50 ₽, в среднем 0 ₽"
+        );
+    }
+}
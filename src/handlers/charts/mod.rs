@@ -0,0 +1,3 @@
+pub mod callback_handlers;
+pub mod command_handlers;
+mod render;
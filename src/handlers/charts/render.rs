@@ -0,0 +1,332 @@
+use crate::models::transaction::Amount;
+use ab_glyph::{Font, FontRef};
+use image::{ImageFormat, RgbImage};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::{FontStyle, register_font};
+use std::io::Cursor;
+
+const FONT_FAMILY: &str = "sans-serif";
+const FONT_DATA: &[u8] = include_bytes!("../../../assets/fonts/DejaVuSans.ttf");
+const EMOJI_FONT_FAMILY: &str = "emoji";
+/// Noto Emoji, read at startup relative to the working directory, like `.env`. Without it
+/// only the few emoji DejaVu Sans has are drawn.
+const EMOJI_FONT_PATH: &str = "assets/fonts/NotoEmoji-Regular.ttf";
+
+/// Registers the bundled fonts with plotters on first use, so rendering does not depend
+/// on the fonts installed on the host. The first font with a glyph for a character is
+/// used to draw it.
+static FONTS: once_cell::sync::Lazy<Vec<(&'static str, FontRef<'static>)>> =
+    once_cell::sync::Lazy::new(|| {
+        register_font(FONT_FAMILY, FontStyle::Normal, FONT_DATA)
+            .unwrap_or_else(|_| panic!("bundled font is invalid"));
+        let mut fonts = vec![(FONT_FAMILY, FontRef::try_from_slice(FONT_DATA).unwrap())];
+
+        match std::fs::read(EMOJI_FONT_PATH) {
+            Ok(data) => {
+                let data: &'static [u8] = Box::leak(data.into_boxed_slice());
+                match FontRef::try_from_slice(data) {
+                    Ok(font)
+                        if register_font(EMOJI_FONT_FAMILY, FontStyle::Normal, data).is_ok() =>
+                    {
+                        fonts.push((EMOJI_FONT_FAMILY, font))
+                    }
+                    _ => log::warn!("{EMOJI_FONT_PATH} is not a valid font, emoji are not drawn"),
+                }
+            }
+            Err(error) => {
+                log::warn!("failed to read {EMOJI_FONT_PATH}, emoji are not drawn: {error}")
+            }
+        }
+
+        fonts
+    });
+
+const WIDTH: u32 = 900;
+const PADDING: i32 = 24;
+const TITLE_HEIGHT: i32 = 64;
+const ROW_HEIGHT: i32 = 40;
+const LABEL_WIDTH: i32 = 300;
+const VALUE_WIDTH: i32 = 160;
+const MAX_LABEL_LENGTH: usize = 24;
+
+pub type RenderError = Box<dyn std::error::Error + Send + Sync>;
+
+pub struct Bar {
+    pub label: String,
+    /// Minor units.
+    pub value: i64,
+}
+
+/// The index of the first bundled font that has a glyph for the character.
+fn font_of(char: char) -> Option<usize> {
+    FONTS
+        .iter()
+        .position(|(_, font)| font.glyph_id(char).0 != 0)
+}
+
+/// Splits the text into runs drawn with the same font, dropping characters no font has a
+/// glyph for instead of drawing empty boxes, and shortens long labels.
+fn runs(text: &str, font_of: impl Fn(char) -> Option<usize>) -> Vec<(usize, String)> {
+    let text: String = text
+        .chars()
+        .filter(|char| font_of(*char).is_some())
+        .collect();
+    let text = text.trim();
+    let text = if text.chars().count() > MAX_LABEL_LENGTH {
+        let short: String = text.chars().take(MAX_LABEL_LENGTH - 1).collect();
+        format!("{}…", short.trim_end())
+    } else {
+        text.to_string()
+    };
+
+    let mut runs: Vec<(usize, String)> = Vec::new();
+    for char in text.chars() {
+        let font = font_of(char).unwrap_or_default();
+        match runs.last_mut() {
+            Some((last, run)) if *last == font => run.push(char),
+            _ => runs.push((font, char.to_string())),
+        }
+    }
+
+    runs
+}
+
+/// Draws the text run by run, each with the font that has its glyphs, and returns where
+/// the text ends.
+fn draw_label(
+    root: &DrawingArea<BitMapBackend, Shift>,
+    text: &str,
+    (mut x, y): (i32, i32),
+    size: u32,
+) -> Result<i32, RenderError> {
+    for (font, run) in runs(text, font_of) {
+        let font = (FONTS[font].0, size).into_font();
+        let (width, _) = font.box_size(&run)?;
+        root.draw(&Text::new(run, (x, y), font))?;
+        x += width as i32;
+    }
+
+    Ok(x)
+}
+
+/// A line on a trend chart, one value per month.
+pub struct Series {
+    pub name: String,
+    /// Minor units.
+    pub values: Vec<i64>,
+}
+
+fn encode_png(buffer: Vec<u8>, width: u32, height: u32) -> Result<Vec<u8>, RenderError> {
+    let image = RgbImage::from_raw(width, height, buffer).ok_or("invalid image buffer")?;
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+
+    Ok(png)
+}
+
+/// A horizontal bar chart, one bar per row in the given order, as a PNG image.
+pub fn bar_chart(title: &str, bars: &[Bar], unit: &str) -> Result<Vec<u8>, RenderError> {
+    once_cell::sync::Lazy::force(&FONTS);
+
+    let height = (TITLE_HEIGHT + ROW_HEIGHT * bars.len() as i32 + PADDING) as u32;
+    let mut buffer = vec![0; (WIDTH * height * 3) as usize];
+    {
+        let root = BitMapBackend::with_buffer(&mut buffer, (WIDTH, height)).into_drawing_area();
+        root.fill(&WHITE)?;
+        draw_label(&root, title, (PADDING, PADDING), 28)?;
+
+        let max = bars
+            .iter()
+            .map(|bar| bar.value)
+            .max()
+            .unwrap_or_default()
+            .max(1);
+        let bar_left = PADDING + LABEL_WIDTH;
+        let bar_width = WIDTH as i32 - bar_left - VALUE_WIDTH - PADDING;
+        let font = (FONT_FAMILY, 20).into_font();
+
+        for (index, bar) in bars.iter().enumerate() {
+            let top = TITLE_HEIGHT + ROW_HEIGHT * index as i32;
+            let length = ((bar.value.max(0) as f64 / max as f64) * bar_width as f64) as i32;
+
+            draw_label(&root, &bar.label, (PADDING, top + 10), 20)?;
+            root.draw(&Rectangle::new(
+                [
+                    (bar_left, top + 6),
+                    (bar_left + length.max(2), top + ROW_HEIGHT - 6),
+                ],
+                Palette99::pick(index).filled(),
+            ))?;
+            root.draw(&Text::new(
+                format!("{} {unit}", Amount(bar.value)),
+                (bar_left + length.max(2) + 8, top + 10),
+                font.to_owned(),
+            ))?;
+        }
+
+        root.present()?;
+    }
+
+    encode_png(buffer, WIDTH, height)
+}
+
+/// A line chart with one point per month for every series, as a PNG image.
+pub fn trend_chart(
+    title: &str,
+    months: &[String],
+    series: &[Series],
+    unit: &str,
+) -> Result<Vec<u8>, RenderError> {
+    once_cell::sync::Lazy::force(&FONTS);
+
+    let height = 500;
+    let mut buffer = vec![0; (WIDTH * height * 3) as usize];
+    {
+        let root = BitMapBackend::with_buffer(&mut buffer, (WIDTH, height)).into_drawing_area();
+        root.fill(&WHITE)?;
+        draw_label(&root, title, (PADDING, PADDING), 28)?;
+
+        // The legend is drawn above the chart rather than by plotters, which only draws
+        // text with a single font and so would lose emoji in category names.
+        let mut x = PADDING;
+        for (index, series) in series.iter().enumerate() {
+            let color = Palette99::pick(index).to_rgba();
+            let y = TITLE_HEIGHT + ROW_HEIGHT / 2 - 8;
+            root.draw(&PathElement::new(
+                vec![(x, y + 10), (x + 20, y + 10)],
+                color.stroke_width(3),
+            ))?;
+            x = draw_label(&root, &series.name, (x + 28, y), 18)? + PADDING;
+        }
+        let (_, area) = root.split_vertically(TITLE_HEIGHT + ROW_HEIGHT);
+
+        let max = series
+            .iter()
+            .flat_map(|series| series.values.iter())
+            .copied()
+            .max()
+            .unwrap_or_default()
+            .max(100) as f64
+            / 100.0;
+        let last = months.len().max(2) as i32 - 1;
+
+        let mut chart = ChartBuilder::on(&area)
+            .margin(PADDING)
+            .x_label_area_size(32)
+            .y_label_area_size(96)
+            .build_cartesian_2d(0..last, 0.0..max * 1.1)?;
+
+        chart
+            .configure_mesh()
+            .disable_x_mesh()
+            .x_labels(months.len())
+            .x_label_formatter(&|index| months.get(*index as usize).cloned().unwrap_or_default())
+            .y_label_formatter(&|value| format!("{} {unit}", Amount((*value * 100.0) as i64)))
+            .label_style((FONT_FAMILY, 16))
+            .draw()?;
+
+        for (index, series) in series.iter().enumerate() {
+            let color = Palette99::pick(index).to_rgba();
+            let points: Vec<(i32, f64)> = series
+                .values
+                .iter()
+                .enumerate()
+                .map(|(month, value)| (month as i32, *value as f64 / 100.0))
+                .collect();
+
+            chart.draw_series(LineSeries::new(points.to_owned(), color.stroke_width(3)))?;
+            chart.draw_series(
+                points
+                    .into_iter()
+                    .map(|point| Circle::new(point, 4, color.filled())),
+            )?;
+        }
+
+        root.present()?;
+    }
+
+    encode_png(buffer, WIDTH, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The main font has every glyph but emoji, the emoji font has emoji.
+    fn stub_font_of(char: char) -> Option<usize> {
+        match char {
+            '🍔' | '🚕' => Some(1),
+            '\u{fffe}' => None,
+            _ => Some(0),
+        }
+    }
+
+    #[test]
+    fn test_runs() {
+        assert_eq!(
+            runs("🍔 Еда", stub_font_of),
+            vec![(1, "🍔".to_string()), (0, " Еда".to_string())]
+        );
+        assert_eq!(
+            runs("\u{fffe} Еда", stub_font_of),
+            vec![(0, "Еда".to_string())]
+        );
+        assert_eq!(
+            runs("Очень длинное название категории", stub_font_of),
+            vec![(0, "Очень длинное название…".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_runs_keep_emoji_of_bundled_fonts() {
+        assert_eq!(runs("😀 Еда", font_of), vec![(0, "😀 Еда".to_string())]);
+    }
+
+    #[test]
+    fn test_bar_chart() {
+        let png = bar_chart(
+            "Расходы",
+            &[
+                Bar {
+                    label: "🍔 Еда".to_string(),
+                    value: 150000,
+                },
+                Bar {
+                    label: "🚕 Такси".to_string(),
+                    value: 35000,
+                },
+            ],
+            "₽",
+        )
+        .unwrap();
+
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn test_trend_chart() {
+        let png = trend_chart(
+            "Расходы и доходы",
+            &[
+                "Янв 25".to_string(),
+                "Фев 25".to_string(),
+                "Мар 25".to_string(),
+            ],
+            &[
+                Series {
+                    name: "Расходы".to_string(),
+                    values: vec![150000, 120000, 170000],
+                },
+                Series {
+                    name: "Доходы".to_string(),
+                    values: vec![200000, 0, 210000],
+                },
+            ],
+            "₽",
+        )
+        .unwrap();
+
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+}
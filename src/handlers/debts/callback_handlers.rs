@@ -0,0 +1,47 @@
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::debts::message_builders::show_debts;
+use crate::models::debt::Transfer;
+use crate::models::transaction::CurrencyCode;
+use crate::proto::callback::v1::{Currency, SettleDebt};
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::sugar::bot::BotMessagesExt;
+
+pub async fn settle_debt(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    debts_service: Arc<dyn services::debts::Service>,
+    members_service: Arc<dyn services::members::Service>,
+    callback: SettleDebt,
+) -> HandlerResult {
+    let Ok(currency_code) = CurrencyCode::try_from(Currency::try_from(callback.currency)?) else {
+        return Ok(());
+    };
+
+    if let Some(message) = callback_query.regular_message() {
+        debts_service
+            .settle_debt(
+                message.chat.id.0,
+                Transfer {
+                    from_user_id: callback.from_user_id,
+                    to_user_id: callback.to_user_id,
+                    amount: callback.amount,
+                    currency_code,
+                },
+            )
+            .await?;
+
+        let builder =
+            show_debts::MessageBuilder::new(message.chat.id, debts_service, members_service);
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::debts::message_builders::show_debts;
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+
+pub async fn show_debts(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    debts_service: Arc<dyn services::debts::Service>,
+    members_service: Arc<dyn services::members::Service>,
+) -> HandlerResult {
+    let builder = show_debts::MessageBuilder::new(message.chat.id, debts_service, members_service);
+
+    bot.send_message(message.chat.id, builder.text().await?)
+        .reply_markup(builder.reply_markup().await?)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use crate::handlers::callback;
+use crate::models::debt::Transfer;
+use crate::models::transaction::Amount;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, Currency, SettleDebt};
+use crate::services;
+use std::collections::HashMap;
+use std::sync::Arc;
+use teloxide::prelude::ChatId;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    chat_id: ChatId,
+    debts_service: Arc<dyn services::debts::Service>,
+    members_service: Arc<dyn services::members::Service>,
+}
+
+impl MessageBuilder {
+    pub fn new(
+        chat_id: ChatId,
+        debts_service: Arc<dyn services::debts::Service>,
+        members_service: Arc<dyn services::members::Service>,
+    ) -> Self {
+        Self {
+            chat_id,
+            debts_service,
+            members_service,
+        }
+    }
+
+    async fn describe_transfers(
+        &self,
+    ) -> Result<Vec<(Transfer, String)>, Box<dyn std::error::Error + Send + Sync>> {
+        let transfers = self.debts_service.settle_up(self.chat_id.0).await?;
+        let member_names: HashMap<i64, String> =
+            self.members_service.member_names(self.chat_id.0).await?;
+        let member_name = |user_id: i64| {
+            member_names
+                .get(&user_id)
+                .cloned()
+                .unwrap_or_else(|| format!("id{user_id}"))
+        };
+
+        Ok(transfers
+            .into_iter()
+            .map(|transfer| {
+                let description = format!(
+                    "{} → {}: {} {}",
+                    member_name(transfer.from_user_id),
+                    member_name(transfer.to_user_id),
+                    Amount(transfer.amount),
+                    transfer.currency_code.as_symbol_str()
+                );
+                (transfer, description)
+            })
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let transfers = self.describe_transfers().await?;
+
+        if transfers.is_empty() {
+            return Ok("🤝 Долгов нет, все в расчёте.".to_string());
+        }
+
+        let mut text = "💸 Кто кому должен".to_string();
+        for (_, description) in transfers {
+            text.push_str(&format!("\n{description}"));
+        }
+        text.push_str("\n\nНажмите на перевод, когда он будет сделан.");
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let mut keyboard = InlineKeyboardMarkup::default();
+
+        for (transfer, description) in self.describe_transfers().await? {
+            keyboard = keyboard.append_row(vec![InlineKeyboardButton::callback(
+                format!("✅ {description}"),
+                String::try_from(Callback {
+                    query: Option::from(Query::SettleDebt(SettleDebt {
+                        from_user_id: transfer.from_user_id,
+                        to_user_id: transfer.to_user_id,
+                        amount: transfer.amount,
+                        currency: i32::from(Currency::from(transfer.currency_code)),
+                    })),
+                })
+                .unwrap(),
+            )]);
+        }
+
+        Ok(keyboard)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::dates;
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::digests::message_builders::show_digest;
+use crate::services;
+use crate::telegram::HandlerResult;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use teloxide::Bot;
+use teloxide::prelude::*;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Sends scheduled digests for as long as the bot runs.
+pub async fn send_digests(bot: Bot, digests_service: Arc<dyn services::digests::Service>) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = send_due_digests(&bot, &digests_service).await {
+            log::error!("failed to send digests: {err}");
+        }
+    }
+}
+
+async fn send_due_digests(
+    bot: &Bot,
+    digests_service: &Arc<dyn services::digests::Service>,
+) -> HandlerResult {
+    let now = Utc::now();
+
+    for settings in digests_service.due_digests(now).await? {
+        // Marked first, so a chat the bot can no longer write to is not retried every minute.
+        digests_service.mark_sent(settings.chat_id, now).await?;
+
+        let Some(digest) = digests_service.digest(&settings, now).await? else {
+            continue;
+        };
+
+        let builder =
+            show_digest::MessageBuilder::new(digest, dates::local_date(settings.timezone, now));
+        if let Err(err) = bot
+            .send_message(ChatId(settings.chat_id), builder.text().await?)
+            .reply_markup(builder.reply_markup().await?)
+            .await
+        {
+            log::error!("failed to send digest to {}: {err}", settings.chat_id);
+        }
+    }
+
+    Ok(())
+}
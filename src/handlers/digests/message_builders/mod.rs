@@ -0,0 +1 @@
+pub mod show_digest;
\ No newline at end of file
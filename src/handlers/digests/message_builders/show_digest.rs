@@ -0,0 +1,80 @@
+use crate::handlers::callback;
+use crate::models::digest::Digest;
+use crate::models::transaction::{Amount, CurrencyCode};
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, DigestFrequency, ShowReport};
+use chrono::{Datelike, NaiveDate};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    digest: Digest,
+    /// Local date the digest is sent on.
+    today: NaiveDate,
+}
+
+impl MessageBuilder {
+    pub fn new(digest: Digest, today: NaiveDate) -> Self {
+        Self { digest, today }
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let digest = &self.digest;
+
+        let mut text = match digest.frequency {
+            DigestFrequency::Weekly => format!("🗓 Итоги недели\n\nРасходы: {}", digest.expenses),
+            _ => format!("☀️ Сводка за вчера\n\nРасходы: {}", digest.expenses),
+        };
+
+        if digest.frequency != DigestFrequency::Weekly {
+            text.push_str(&format!("\nС начала недели: {}", digest.week_expenses));
+        }
+
+        if let Some(week_budget) = digest.week_budget {
+            let spent = digest.week_expenses.get(CurrencyCode::RUB);
+            let symbol = CurrencyCode::RUB.as_symbol_str();
+            let mark = if spent > week_budget { "⚠️" } else { "✅" };
+
+            text.push_str(&format!(
+                "\n{mark} Бюджет недели: {} из {} {symbol}",
+                Amount(spent),
+                Amount(week_budget)
+            ));
+        }
+
+        if !digest.top_categories.is_empty() {
+            text.push_str("\n\n🔝 Больше всего потрачено");
+            for total in &digest.top_categories {
+                text.push_str(&format!("\n{}: {}", total.title(), total.totals));
+            }
+        }
+
+        if digest.uncategorized > 0 {
+            text.push_str(&format!(
+                "\n\n❔ Без категории: {} — /uncategorized",
+                digest.uncategorized
+            ));
+        }
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(
+            InlineKeyboardMarkup::default().append_row(vec![InlineKeyboardButton::callback(
+                "📊 Отчёт за месяц",
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowReport(ShowReport {
+                        year: self.today.year(),
+                        month: self.today.month(),
+                    })),
+                })
+                .unwrap(),
+            )]),
+        )
+    }
+}
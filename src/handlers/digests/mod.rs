@@ -0,0 +1,2 @@
+pub mod jobs;
+mod message_builders;
\ No newline at end of file
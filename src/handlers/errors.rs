@@ -0,0 +1,99 @@
+use crate::errors::Error;
+use std::ops::ControlFlow;
+use std::pin::Pin;
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::dispatching::UpdateHandler;
+use teloxide::prelude::*;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, UpdateKind};
+
+/// Attaches the update to the errors of the handler and of the handlers chained after it, so
+/// that [`ErrorHandler`] knows where to reply.
+pub fn with_update(handler: UpdateHandler<Error>) -> UpdateHandler<Error> {
+    let description = handler.description().clone();
+    let sig = handler.sig().clone();
+
+    dptree::from_fn_with_description(
+        description,
+        move |container: DependencyMap, cont| {
+            let handler = handler.clone();
+            async move {
+                let update = container.try_get::<Update>();
+                match handler.execute(container, cont).await {
+                    ControlFlow::Break(Err(error)) => ControlFlow::Break(Err(match update {
+                        Some(update) => error.with_update(update),
+                        None => error,
+                    })),
+                    result => result,
+                }
+            }
+        },
+        sig,
+    )
+}
+
+/// Logs errors of the dispatcher and tells the chat that its update was not handled.
+pub struct ErrorHandler {
+    bot: Bot,
+}
+
+impl ErrorHandler {
+    pub fn new(bot: Bot) -> Arc<Self> {
+        Arc::new(Self { bot })
+    }
+
+    async fn reply(&self, update: &Update, error: &Error) -> Result<(), Error> {
+        let Some(chat) = update.chat() else {
+            return Ok(());
+        };
+
+        if let UpdateKind::CallbackQuery(callback_query) = &update.kind {
+            self.bot
+                .answer_callback_query(callback_query.id.to_owned())
+                .await?;
+        }
+
+        // Rejected category changes explain themselves, retrying them would not help.
+        if let Error::Category(error) = error.kind() {
+            self.bot.send_message(chat.id, error.to_string()).await?;
+            return Ok(());
+        }
+
+        let mut message = self.bot.send_message(chat.id, "⚠️ Что-то пошло не так");
+        if let UpdateKind::CallbackQuery(callback_query) = &update.kind
+            && let Some(data) = &callback_query.data
+        {
+            message = message.reply_markup(InlineKeyboardMarkup::default().append_row(vec![
+                InlineKeyboardButton::callback("🔁 Повторить", data.to_owned()),
+            ]));
+        }
+        message.await?;
+
+        Ok(())
+    }
+}
+
+impl teloxide::error_handlers::ErrorHandler<Error> for ErrorHandler {
+    fn handle_error(self: Arc<Self>, error: Error) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(async move {
+            let Error::Update { update, error } = error else {
+                log::error!("{error}");
+                return;
+            };
+
+            let chat_id = update.chat().map(|chat| chat.id.0);
+            let data = match &update.kind {
+                UpdateKind::CallbackQuery(callback_query) => callback_query.data.to_owned(),
+                _ => None,
+            };
+            log::error!(
+                "update {} in chat {chat_id:?} (callback {data:?}): {error}",
+                update.id.0
+            );
+
+            if let Err(reply_error) = self.reply(&update, &error).await {
+                log::error!("failed to reply to update {}: {reply_error}", update.id.0);
+            }
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use crate::handlers::exports::command_handlers::send_export;
+use crate::proto::callback::v1::ExportTransactions;
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use chrono::DateTime;
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+
+pub async fn export_transactions(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    exports_service: Arc<dyn services::exports::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    callback: ExportTransactions,
+) -> HandlerResult {
+    let (Some(from), Some(to)) = (
+        DateTime::from_timestamp(callback.from, 0),
+        DateTime::from_timestamp(callback.to, 0),
+    ) else {
+        return Ok(());
+    };
+
+    if let Some(message) = callback_query.regular_message() {
+        let settings = settings_service.get_settings(message.chat.id.0).await?;
+
+        send_export(
+            &bot,
+            message.chat.id,
+            exports_service,
+            settings.timezone,
+            from,
+            to,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
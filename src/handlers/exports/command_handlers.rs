@@ -0,0 +1,126 @@
+use crate::dates;
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::exports::message_builders::select_export_period;
+use crate::handlers::transactions::message_handlers::parse_date;
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::types::InputFile;
+
+pub async fn export(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    exports_service: Arc<dyn services::exports::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    period: String,
+) -> HandlerResult {
+    let timezone = settings_service
+        .get_settings(message.chat.id.0)
+        .await?
+        .timezone;
+
+    if period.trim().is_empty() {
+        let builder = select_export_period::MessageBuilder::new(timezone);
+
+        bot.send_message(message.chat.id, builder.text().await?)
+            .reply_markup(builder.reply_markup().await?)
+            .await?;
+
+        return Ok(());
+    }
+
+    let Some((from, to)) = parse_period(timezone, &period) else {
+        bot.send_message(
+            message.chat.id,
+            "Неверный период. Используйте формат: /export 01.12.2025 31.12.2025",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    send_export(&bot, message.chat.id, exports_service, timezone, from, to).await
+}
+
+/// Sends transactions created in `[from, to)` as a CSV document and an Excel workbook.
+pub async fn send_export(
+    bot: &Bot,
+    chat_id: ChatId,
+    exports_service: Arc<dyn services::exports::Service>,
+    timezone: Tz,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> HandlerResult {
+    let Some(csv) = exports_service.export_csv(chat_id.0, from, to).await? else {
+        bot.send_message(chat_id, "За этот период операций нет.")
+            .await?;
+        return Ok(());
+    };
+
+    let first_day = dates::local_date(timezone, from);
+    let last_day = dates::local_date(timezone, to)
+        .pred_opt()
+        .unwrap_or(first_day);
+    let file_name = format!(
+        "budgetgram_{}_{}",
+        first_day.format("%Y-%m-%d"),
+        last_day.format("%Y-%m-%d")
+    );
+
+    bot.send_document(
+        chat_id,
+        InputFile::memory(csv).file_name(format!("{file_name}.csv")),
+    )
+    .await?;
+
+    if let Some(xlsx) = exports_service.export_xlsx(chat_id.0, from, to).await? {
+        bot.send_document(
+            chat_id,
+            InputFile::memory(xlsx).file_name(format!("{file_name}.xlsx")),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Parses `dd.mm.yyyy [dd.mm.yyyy]` into a half-open range; both days are included
+/// and a missing end means today. Days are the local ones of the timezone.
+fn parse_period(timezone: Tz, text: &str) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut words = text.split_whitespace();
+    let from = parse_date(timezone, words.next()?)?;
+    let to = match words.next() {
+        Some(date) => parse_date(timezone, date)?,
+        None => dates::today(timezone),
+    };
+    if words.next().is_some() || to < from {
+        return None;
+    }
+
+    Some((
+        dates::start_of_day(timezone, from),
+        dates::start_of_day(timezone, to.succ_opt()?),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_period() {
+        let (from, to) = parse_period(Tz::UTC, "01.12.2025 31.12.2025").unwrap();
+        assert_eq!(from.to_rfc3339(), "2025-12-01T00:00:00+00:00");
+        assert_eq!(to.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+
+        let (from, _) = parse_period(chrono_tz::Europe::Moscow, "01.12.2025").unwrap();
+        assert_eq!(from.to_rfc3339(), "2025-11-30T21:00:00+00:00");
+
+        assert!(parse_period(Tz::UTC, "31.12.2025 01.12.2025").is_none());
+        assert!(parse_period(Tz::UTC, "вчера").is_none());
+    }
+}
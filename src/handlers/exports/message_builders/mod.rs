@@ -0,0 +1 @@
+pub mod select_export_period;
@@ -0,0 +1,66 @@
+use crate::dates::{self, add_months, month_bounds};
+use crate::handlers::callback;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, ExportTransactions};
+use chrono::{DateTime, Datelike, Days, Utc};
+use chrono_tz::Tz;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    timezone: Tz,
+}
+
+impl MessageBuilder {
+    pub fn new(timezone: Tz) -> Self {
+        MessageBuilder { timezone }
+    }
+}
+
+fn button(text: &str, from: DateTime<Utc>, to: DateTime<Utc>) -> InlineKeyboardButton {
+    InlineKeyboardButton::callback(
+        text,
+        String::try_from(Callback {
+            query: Option::from(Query::ExportTransactions(ExportTransactions {
+                from: from.timestamp(),
+                to: to.timestamp(),
+            })),
+        })
+        .unwrap(),
+    )
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(
+            "📤 Выберите период выгрузки или укажите его сами: /export 01.12.2025 31.12.2025"
+                .to_string(),
+        )
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let timezone = self.timezone;
+        let today = dates::today(timezone);
+        let (month_start, month_end) =
+            month_bounds(timezone, today.year(), today.month()).ok_or("invalid month")?;
+        let (previous_year, previous_month) = add_months(today.year(), today.month(), -1);
+        let (previous_month_start, _) =
+            month_bounds(timezone, previous_year, previous_month).ok_or("invalid month")?;
+        let (year_start, _) = month_bounds(timezone, today.year(), 1).ok_or("invalid month")?;
+        let tomorrow = Utc::now()
+            .checked_add_days(Days::new(1))
+            .ok_or("invalid date")?;
+
+        Ok(InlineKeyboardMarkup::default()
+            .append_row(vec![
+                button("Этот месяц", month_start, month_end),
+                button("Прошлый месяц", previous_month_start, month_start),
+            ])
+            .append_row(vec![
+                button("Этот год", year_start, tomorrow),
+                button("Всё время", DateTime::UNIX_EPOCH, tomorrow),
+            ]))
+    }
+}
@@ -0,0 +1,128 @@
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::imports::message_builders::map_import_columns;
+use crate::handlers::imports::parsers;
+use crate::proto::callback::v1::select_import_column::Field;
+use crate::proto::callback::v1::{ApplyImport, CancelImport, SelectImportColumn};
+use crate::services;
+use crate::telegram;
+use crate::telegram::{Dialog, HandlerResult, State};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::sugar::bot::BotMessagesExt;
+use teloxide::types::InlineKeyboardMarkup;
+
+pub async fn select_import_column(
+    bot: Bot,
+    dialog: Dialog,
+    callback_query: CallbackQuery,
+    callback: SelectImportColumn,
+) -> HandlerResult {
+    let Some(State::ImportTransactions { rows, mut columns }) = dialog.get().await? else {
+        return Ok(());
+    };
+
+    let column = usize::try_from(callback.column)
+        .ok()
+        .filter(|column| *column < rows[0].len());
+    match (Field::try_from(callback.field)?, column) {
+        (Field::Date, Some(column)) => columns.date = Some(column),
+        (Field::Amount, Some(column)) => columns.amount = Some(column),
+        (Field::Description, column) => columns.description = Some(column),
+        _ => return Ok(()),
+    }
+
+    if let Some(message) = callback_query.regular_message() {
+        let builder = map_import_columns::MessageBuilder::new(rows.to_owned(), columns.to_owned());
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    dialog
+        .update(State::ImportTransactions { rows, columns })
+        .await?;
+
+    Ok(())
+}
+
+pub async fn apply_import(
+    bot: Bot,
+    dialog: Dialog,
+    callback_query: CallbackQuery,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+    _callback: ApplyImport,
+) -> HandlerResult {
+    let state = dialog.get().await?;
+    if !matches!(
+        state,
+        Some(State::ImportTransactions { .. } | State::ImportStatement { .. })
+    ) {
+        return Ok(());
+    }
+    dialog.exit().await?;
+
+    if let Some(message) = callback_query.regular_message() {
+        bot.edit_text(message, "⏳ Импортирую операции…")
+            .reply_markup(InlineKeyboardMarkup::default())
+            .await?;
+
+        let chat_id = message.chat.id.0;
+        let user_id = Some(telegram::user_id(&callback_query.from));
+        let timezone = settings_service.get_settings(chat_id).await?.timezone;
+        let (requests, failed) = match state {
+            Some(State::ImportStatement { bank, text }) => {
+                parsers::parse_statement(bank, chat_id, user_id, timezone, &text)?
+            }
+            Some(State::ImportTransactions { rows, columns }) => {
+                parsers::to_requests(chat_id, user_id, timezone, &rows, &columns)
+            }
+            _ => return Ok(()),
+        };
+        let report = transactions_service
+            .import_transactions(timezone, requests)
+            .await?;
+
+        let mut text = format!(
+            "📥 Импорт завершён\nДобавлено: {}\nПропущено как дубликаты: {}\nС ошибками: {}",
+            report.imported,
+            report.skipped,
+            failed.len()
+        );
+        if !failed.is_empty() {
+            let lines: Vec<String> = failed.iter().take(10).map(usize::to_string).collect();
+            text.push_str(&format!(" (строки {}", lines.join(", ")));
+            if failed.len() > lines.len() {
+                text.push_str(", …");
+            }
+            text.push(')');
+        }
+
+        bot.edit_text(message, text).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn cancel_import(
+    bot: Bot,
+    dialog: Dialog,
+    callback_query: CallbackQuery,
+    _callback: CancelImport,
+) -> HandlerResult {
+    if let Some(State::ImportTransactions { .. } | State::ImportStatement { .. }) =
+        dialog.get().await?
+    {
+        dialog.exit().await?;
+    }
+
+    if let Some(message) = callback_query.regular_message() {
+        bot.edit_text(message, "❌ Импорт отменён.")
+            .reply_markup(InlineKeyboardMarkup::default())
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::handlers::callback;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{ApplyImport, Callback, CancelImport, CategoryDirection};
+use crate::services::transactions::ImportTransactionRequest;
+use crate::telegram::Bank;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder<'a> {
+    bank: Bank,
+    requests: &'a [ImportTransactionRequest],
+    failed: &'a [usize],
+}
+
+impl<'a> MessageBuilder<'a> {
+    pub fn new(bank: Bank, requests: &'a [ImportTransactionRequest], failed: &'a [usize]) -> Self {
+        Self {
+            bank,
+            requests,
+            failed,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder<'_> {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let bank = match self.bank {
+            Bank::Tinkoff => "Тинькофф",
+            Bank::Sberbank => "Сбербанк",
+        };
+        let incomes = self
+            .requests
+            .iter()
+            .filter(|request| request.direction == Some(CategoryDirection::Income))
+            .count();
+
+        let mut text = format!(
+            "🏦 Выписка: {bank}\nОпераций: {} (расходов {}, доходов {incomes})",
+            self.requests.len(),
+            self.requests.len() - incomes
+        );
+        if !self.failed.is_empty() {
+            text.push_str(&format!("\nНе распознано строк: {}", self.failed.len()));
+        }
+        text.push_str(
+            "\n\nУже добавленные операции с той же датой, суммой и описанием будут пропущены.",
+        );
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(InlineKeyboardMarkup::default()
+            .append_row(vec![InlineKeyboardButton::callback(
+                "✅ Импортировать",
+                String::try_from(Callback {
+                    query: Option::from(Query::ApplyImport(ApplyImport {})),
+                })
+                .unwrap(),
+            )])
+            .append_row(vec![InlineKeyboardButton::callback(
+                "❌ Отмена",
+                String::try_from(Callback {
+                    query: Option::from(Query::CancelImport(CancelImport {})),
+                })
+                .unwrap(),
+            )]))
+    }
+}
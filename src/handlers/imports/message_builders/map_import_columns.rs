@@ -0,0 +1,134 @@
+use crate::handlers::callback;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::select_import_column::Field;
+use crate::proto::callback::v1::{ApplyImport, Callback, CancelImport, SelectImportColumn};
+use crate::telegram::ImportColumns;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+const MAX_CELL_LENGTH: usize = 24;
+
+pub struct MessageBuilder {
+    rows: Vec<Vec<String>>,
+    columns: ImportColumns,
+}
+
+impl MessageBuilder {
+    pub fn new(rows: Vec<Vec<String>>, columns: ImportColumns) -> Self {
+        Self { rows, columns }
+    }
+
+    /// The field the user has to pick a column for next, if any.
+    fn next_field(&self) -> Option<Field> {
+        if self.columns.date.is_none() {
+            Some(Field::Date)
+        } else if self.columns.amount.is_none() {
+            Some(Field::Amount)
+        } else if self.columns.description.is_none() {
+            Some(Field::Description)
+        } else {
+            None
+        }
+    }
+
+    fn header(&self, column: usize) -> String {
+        let header = self.rows[0]
+            .get(column)
+            .filter(|header| !header.is_empty())
+            .cloned()
+            .unwrap_or_else(|| format!("Столбец {}", column + 1));
+
+        truncate(&header)
+    }
+
+    fn sample(&self, column: Option<usize>) -> String {
+        column
+            .and_then(|column| self.rows.get(1).and_then(|row| row.get(column)))
+            .map(|cell| truncate(cell))
+            .unwrap_or_else(|| "—".to_string())
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() > MAX_CELL_LENGTH {
+        format!(
+            "{}…",
+            text.chars().take(MAX_CELL_LENGTH - 1).collect::<String>()
+        )
+    } else {
+        text.to_string()
+    }
+}
+
+fn button(text: String, field: Field, column: i32) -> InlineKeyboardButton {
+    InlineKeyboardButton::callback(
+        text,
+        String::try_from(Callback {
+            query: Option::from(Query::SelectImportColumn(SelectImportColumn {
+                field: i32::from(field),
+                column,
+            })),
+        })
+        .unwrap(),
+    )
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let mut text = format!("📥 Строк с операциями: {}\n\n", self.rows.len() - 1);
+
+        match self.next_field() {
+            Some(Field::Date) => text.push_str("Шаг 1/3. В каком столбце дата операции?"),
+            Some(Field::Amount) => text.push_str("Шаг 2/3. В каком столбце сумма?"),
+            Some(_) => text.push_str("Шаг 3/3. В каком столбце описание?"),
+            None => text.push_str(&format!(
+                "Пример первой строки:\nДата: {}\nСумма: {}\nОписание: {}\n\n\
+                 Уже добавленные операции с той же датой, суммой и описанием будут пропущены.",
+                self.sample(self.columns.date),
+                self.sample(self.columns.amount),
+                self.sample(self.columns.description.flatten()),
+            )),
+        }
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let mut keyboard = InlineKeyboardMarkup::default();
+
+        match self.next_field() {
+            Some(field) => {
+                for column in 0..self.rows[0].len() {
+                    keyboard = keyboard.append_row(vec![button(
+                        format!("{}: {}", self.header(column), self.sample(Some(column))),
+                        field,
+                        column as i32,
+                    )]);
+                }
+                if field == Field::Description {
+                    keyboard =
+                        keyboard.append_row(vec![button("Без описания".to_string(), field, -1)]);
+                }
+            }
+            None => {
+                keyboard = keyboard.append_row(vec![InlineKeyboardButton::callback(
+                    "✅ Импортировать",
+                    String::try_from(Callback {
+                        query: Option::from(Query::ApplyImport(ApplyImport {})),
+                    })
+                    .unwrap(),
+                )]);
+            }
+        }
+
+        Ok(keyboard.append_row(vec![InlineKeyboardButton::callback(
+            "❌ Отмена",
+            String::try_from(Callback {
+                query: Option::from(Query::CancelImport(CancelImport {})),
+            })
+            .unwrap(),
+        )]))
+    }
+}
@@ -0,0 +1,2 @@
+pub mod confirm_statement_import;
+pub mod map_import_columns;
@@ -0,0 +1,81 @@
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::imports::message_builders::{confirm_statement_import, map_import_columns};
+use crate::handlers::imports::parsers;
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult, ImportColumns, State};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::net::Download;
+use teloxide::prelude::*;
+
+const MAX_FILE_SIZE: u32 = 1024 * 1024;
+
+pub async fn import_transactions(
+    bot: Bot,
+    dialog: Dialog,
+    message: Message,
+    settings_service: Arc<dyn services::settings::Service>,
+) -> HandlerResult {
+    let Some(document) = message.document() else {
+        return Ok(());
+    };
+
+    if document.file.size > MAX_FILE_SIZE {
+        bot.send_message(message.chat.id, "Файл слишком большой: не больше 1 МБ.")
+            .await?;
+        return Ok(());
+    }
+
+    let file = bot.get_file(document.file.id.to_owned()).await?;
+    let mut data = Vec::new();
+    bot.download_file(&file.path, &mut data).await?;
+
+    let text = parsers::decode(&data);
+
+    if let Some(bank) = parsers::detect_bank(&text) {
+        let timezone = settings_service
+            .get_settings(message.chat.id.0)
+            .await?
+            .timezone;
+        let (requests, failed) =
+            match parsers::parse_statement(bank, message.chat.id.0, None, timezone, &text) {
+                Ok(parsed) => parsed,
+                Err(error) => {
+                    bot.send_message(message.chat.id, error).await?;
+                    return Ok(());
+                }
+            };
+
+        let builder = confirm_statement_import::MessageBuilder::new(bank, &requests, &failed);
+        bot.send_message(message.chat.id, builder.text().await?)
+            .reply_markup(builder.reply_markup().await?)
+            .await?;
+
+        dialog.update(State::ImportStatement { bank, text }).await?;
+
+        return Ok(());
+    }
+
+    let rows = match parsers::parse_csv(&text) {
+        Ok(rows) => rows,
+        Err(error) => {
+            bot.send_message(message.chat.id, error).await?;
+            return Ok(());
+        }
+    };
+
+    let builder =
+        map_import_columns::MessageBuilder::new(rows.to_owned(), ImportColumns::default());
+    bot.send_message(message.chat.id, builder.text().await?)
+        .reply_markup(builder.reply_markup().await?)
+        .await?;
+
+    dialog
+        .update(State::ImportTransactions {
+            rows,
+            columns: ImportColumns::default(),
+        })
+        .await?;
+
+    Ok(())
+}
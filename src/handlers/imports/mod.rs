@@ -0,0 +1,6 @@
+pub mod callback_handlers;
+mod message_builders;
+pub mod message_handlers;
+mod parsers;
+mod sberbank;
+mod tinkoff;
@@ -0,0 +1,260 @@
+use crate::dates;
+use crate::handlers::imports::{sberbank, tinkoff};
+use crate::models::transaction::CurrencyCode;
+use crate::proto::callback::v1::CategoryDirection;
+use crate::services::transactions::{CreateTransactionRequest, ImportTransactionRequest};
+use crate::telegram::{Bank, ImportColumns};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+
+const MAX_ROWS: usize = 5000;
+
+/// Bank exports are often in Windows-1251 rather than UTF-8.
+pub fn decode(data: &[u8]) -> String {
+    let text = match std::str::from_utf8(data) {
+        Ok(text) => text.to_string(),
+        Err(_) => encoding_rs::WINDOWS_1251.decode(data).0.into_owned(),
+    };
+
+    text.trim_start_matches('\u{feff}').to_string()
+}
+
+pub fn detect_bank(text: &str) -> Option<Bank> {
+    if tinkoff::detect(text) {
+        Some(Bank::Tinkoff)
+    } else if sberbank::detect(text) || sberbank::detect_csv(text) {
+        Some(Bank::Sberbank)
+    } else {
+        None
+    }
+}
+
+pub fn parse_statement(
+    bank: Bank,
+    chat_id: i64,
+    user_id: Option<i64>,
+    timezone: Tz,
+    text: &str,
+) -> Result<(Vec<ImportTransactionRequest>, Vec<usize>), &'static str> {
+    match bank {
+        Bank::Tinkoff => tinkoff::parse(chat_id, user_id, timezone, text),
+        Bank::Sberbank if sberbank::detect_csv(text) => {
+            sberbank::parse_csv(chat_id, user_id, timezone, text)
+        }
+        Bank::Sberbank => sberbank::parse(chat_id, user_id, timezone, text),
+    }
+}
+
+/// Reads a CSV file into rows of cells, guessing the delimiter from the header line.
+pub fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, &'static str> {
+    let header = text.lines().next().unwrap_or_default();
+    let delimiter = [b';', b',', b'\t']
+        .into_iter()
+        .max_by_key(|delimiter| header.matches(*delimiter as char).count())
+        .unwrap_or(b',');
+
+    let mut rows = Vec::new();
+    for record in csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes())
+        .records()
+    {
+        let record = record.map_err(|_| "Не удалось прочитать CSV-файл.")?;
+        if record.iter().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+        rows.push(record.iter().map(|cell| cell.trim().to_string()).collect());
+    }
+
+    if rows.len() < 2 {
+        return Err("В файле нет строк с операциями.");
+    }
+    if rows.len() > MAX_ROWS + 1 {
+        return Err("Слишком большой файл: не больше 5000 строк за раз.");
+    }
+
+    Ok(rows)
+}
+
+/// Parses amounts like `-1 234,50` or `350.00 ₽` into minor units, ignoring the sign.
+pub fn parse_amount(text: &str) -> Option<i64> {
+    let normalized: String = text
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, ',' | '.' | '-' | '+'))
+        .map(|c| if c == ',' { '.' } else { c })
+        .collect();
+
+    let amount = normalized.parse::<f64>().ok()?.abs();
+    if !amount.is_finite() || amount == 0.0 {
+        return None;
+    }
+
+    Some((amount * 100.0).round() as i64)
+}
+
+/// Direction of a signed amount: expenses are negative. Unsigned amounts are incomes only if
+/// the file has negative ones, otherwise they are as likely to be unsigned expenses.
+fn direction_of(amount: &str, has_negative: bool) -> Option<CategoryDirection> {
+    match amount.trim_start().chars().next() {
+        Some('-' | '−') => Some(CategoryDirection::Expense),
+        Some('+') => Some(CategoryDirection::Income),
+        _ if has_negative => Some(CategoryDirection::Income),
+        _ => None,
+    }
+}
+
+/// Parses a date and time as shown on the wall clock of the timezone.
+pub fn parse_date_time(timezone: Tz, text: &str) -> Option<DateTime<Utc>> {
+    const DATE_TIME_FORMATS: [&str; 5] = [
+        "%d.%m.%Y %H:%M:%S",
+        "%d.%m.%Y %H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        "%d/%m/%Y %H:%M",
+    ];
+    const DATE_FORMATS: [&str; 4] = ["%d.%m.%Y", "%Y-%m-%d", "%d/%m/%Y", "%d.%m.%y"];
+
+    DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .map(|date_time| dates::to_utc(timezone, date_time))
+}
+
+/// Turns the data rows into transactions, returning the 1-based line numbers
+/// of the rows that could not be parsed.
+pub fn to_requests(
+    chat_id: i64,
+    user_id: Option<i64>,
+    timezone: Tz,
+    rows: &[Vec<String>],
+    columns: &ImportColumns,
+) -> (Vec<ImportTransactionRequest>, Vec<usize>) {
+    let mut requests = Vec::new();
+    let mut failed = Vec::new();
+    let has_negative = rows.iter().skip(1).any(|row| {
+        columns
+            .amount
+            .and_then(|column| row.get(column))
+            .is_some_and(|amount| direction_of(amount, false) == Some(CategoryDirection::Expense))
+    });
+
+    for (index, row) in rows.iter().enumerate().skip(1) {
+        let cell = |column: Option<usize>| column.and_then(|column| row.get(column));
+
+        let created_at = cell(columns.date).and_then(|date| parse_date_time(timezone, date));
+        let amount = cell(columns.amount).and_then(|amount| parse_amount(amount));
+        let (Some(created_at), Some(amount)) = (created_at, amount) else {
+            failed.push(index + 1);
+            continue;
+        };
+
+        requests.push(ImportTransactionRequest {
+            transaction: CreateTransactionRequest {
+                chat_id,
+                user_id,
+                category_id: None,
+                amount,
+                description: cell(columns.description.flatten())
+                    .cloned()
+                    .unwrap_or_default(),
+                currency_code: CurrencyCode::RUB,
+                message_id: None,
+                created_at: Some(created_at),
+            },
+            direction: cell(columns.amount).and_then(|amount| direction_of(amount, has_negative)),
+        });
+    }
+
+    (requests, failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let text = decode("\u{feff}Дата;Сумма;Описание\n05.12.2025;-350,00;Такси\n\n".as_bytes());
+        let rows = parse_csv(&text).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["Дата", "Сумма", "Описание"],
+                vec!["05.12.2025", "-350,00", "Такси"]
+            ]
+        );
+
+        assert!(parse_csv("date,amount\n").is_err());
+    }
+
+    #[test]
+    fn test_decode() {
+        let (data, _, _) = encoding_rs::WINDOWS_1251.encode("Дата;Сумма");
+        assert_eq!(decode(&data), "Дата;Сумма");
+    }
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("-1 234,50"), Some(123450));
+        assert_eq!(parse_amount("350.00 ₽"), Some(35000));
+        assert_eq!(parse_amount("0"), None);
+        assert_eq!(parse_amount("—"), None);
+    }
+
+    #[test]
+    fn test_to_requests_direction() {
+        let columns = ImportColumns {
+            date: Some(0),
+            amount: Some(1),
+            description: Some(None),
+        };
+        let directions = |rows: &[[&str; 2]]| -> Vec<Option<CategoryDirection>> {
+            let rows: Vec<Vec<String>> = std::iter::once(["Дата", "Сумма"])
+                .chain(rows.iter().copied())
+                .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+                .collect();
+            to_requests(1, None, Tz::UTC, &rows, &columns)
+                .0
+                .iter()
+                .map(|request| request.direction)
+                .collect()
+        };
+
+        assert_eq!(
+            directions(&[["05.12.2025", "-350,00"], ["06.12.2025", "1000,00"]]),
+            vec![
+                Some(CategoryDirection::Expense),
+                Some(CategoryDirection::Income)
+            ]
+        );
+        assert_eq!(
+            directions(&[["05.12.2025", "350,00"], ["06.12.2025", "+1000,00"]]),
+            vec![None, Some(CategoryDirection::Income)]
+        );
+    }
+
+    #[test]
+    fn test_parse_date_time() {
+        assert_eq!(
+            parse_date_time(Tz::UTC, "05.12.2025 14:30:00").map(|date| date.to_rfc3339()),
+            Some("2025-12-05T14:30:00+00:00".to_string())
+        );
+        assert_eq!(
+            parse_date_time(Tz::UTC, "2025-12-05").map(|date| date.to_rfc3339()),
+            Some("2025-12-05T00:00:00+00:00".to_string())
+        );
+        assert_eq!(
+            parse_date_time(chrono_tz::Europe::Moscow, "2025-12-05").map(|date| date.to_rfc3339()),
+            Some("2025-12-04T21:00:00+00:00".to_string())
+        );
+        assert_eq!(parse_date_time(Tz::UTC, "вчера"), None);
+    }
+}
@@ -0,0 +1,308 @@
+use crate::handlers::imports::parsers::{parse_amount, parse_date_time};
+use crate::models::transaction::CurrencyCode;
+use crate::proto::callback::v1::CategoryDirection;
+use crate::services::transactions::{CreateTransactionRequest, ImportTransactionRequest};
+use chrono_tz::Tz;
+use regex::Regex;
+
+const AMOUNT: &str = r"\d{1,3}(?: \d{3})*,\d{2}";
+
+const CSV_OPERATION_DATE: &str = "Дата операции";
+const CSV_POSTING_DATE: &str = "Дата обработки";
+const CSV_CATEGORY: &str = "Категория";
+const CSV_DESCRIPTION: &str = "Описание";
+const CSV_AMOUNT: &str = "Сумма в валюте счёта";
+
+/// `05.12.2025 14:30 283746 Супермаркеты 350,00 12 345,67`: operation time, optional
+/// authorization code, category, amount (`+` for incomes) and the balance after it.
+static OPERATION: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(&format!(
+        r"^(?P<date>\d{{2}}\.\d{{2}}\.\d{{4}}) \d{{2}}:\d{{2}} (?:\d{{6}} )?.+? (?P<amount>\+?{AMOUNT}) -?{AMOUNT}$"
+    ))
+    .expect("invalid regex")
+});
+
+/// `06.12.2025 ПЯТЁРОЧКА 1234 Moscow RUS. Операция по карте ****1234`: posting date
+/// and description, on the line after the operation or on the next page.
+static POSTING: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(r"^(?P<date>\d{2}\.\d{2}\.\d{4}) (?P<description>.+)$").expect("invalid regex")
+});
+
+/// A PDF statement converted with `pdftotext`.
+pub fn detect(text: &str) -> bool {
+    text.to_lowercase().contains("сбербанк") && text.lines().any(is_operation)
+}
+
+/// A `;`-separated CSV statement of Sberbank Online with a fixed Russian header.
+pub fn detect_csv(text: &str) -> bool {
+    let header = text.lines().next().unwrap_or_default();
+
+    header.contains(CSV_POSTING_DATE) && header.contains(CSV_AMOUNT)
+}
+
+/// Collapses the column padding and non-breaking spaces left by the PDF conversion.
+fn normalize(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn is_operation(line: &str) -> bool {
+    OPERATION.is_match(&normalize(line))
+}
+
+/// A blank line, a page footer or the column headers repeated on every page.
+fn is_page_break(line: &str) -> bool {
+    let line = line.to_lowercase();
+
+    line.is_empty()
+        || line.starts_with("продолжение на следующей странице")
+        || line.starts_with("страница ")
+        || line.starts_with("дата операции")
+        || line.starts_with("дата обработки")
+}
+
+/// Parses the statement into ruble transactions posted on the processing date, returning
+/// the 1-based line numbers of operations without a description line.
+pub fn parse(
+    chat_id: i64,
+    user_id: Option<i64>,
+    timezone: Tz,
+    text: &str,
+) -> Result<(Vec<ImportTransactionRequest>, Vec<usize>), &'static str> {
+    let lines: Vec<String> = text.lines().map(normalize).collect();
+
+    let mut requests = Vec::new();
+    let mut failed = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let Some(operation) = OPERATION.captures(line) else {
+            continue;
+        };
+        let posting = lines[index + 1..]
+            .iter()
+            .find(|next| !is_page_break(next))
+            .filter(|next| !OPERATION.is_match(next))
+            .and_then(|next| POSTING.captures(next));
+        let signed_amount = &operation["amount"];
+
+        let (Some(posting), Some(amount)) = (posting, parse_amount(signed_amount)) else {
+            failed.push(index + 1);
+            continue;
+        };
+        let Some(created_at) = parse_date_time(timezone, &posting["date"]) else {
+            failed.push(index + 1);
+            continue;
+        };
+
+        let description = posting["description"]
+            .split(". Операция по")
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        requests.push(ImportTransactionRequest {
+            transaction: CreateTransactionRequest {
+                chat_id,
+                user_id,
+                category_id: None,
+                amount,
+                description,
+                currency_code: CurrencyCode::RUB,
+                message_id: None,
+                created_at: Some(created_at),
+            },
+            direction: Some(if signed_amount.starts_with('+') {
+                CategoryDirection::Income
+            } else {
+                CategoryDirection::Expense
+            }),
+        });
+    }
+
+    if requests.is_empty() && failed.is_empty() {
+        return Err("В выписке Сбербанка не найдено операций.");
+    }
+
+    Ok((requests, failed))
+}
+
+/// Parses the CSV statement into ruble transactions posted on the processing date, returning
+/// the 1-based line numbers of rows that could not be parsed.
+pub fn parse_csv(
+    chat_id: i64,
+    user_id: Option<i64>,
+    timezone: Tz,
+    text: &str,
+) -> Result<(Vec<ImportTransactionRequest>, Vec<usize>), &'static str> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(b';')
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|_| "Не удалось прочитать выписку Сбербанка.")?
+        .to_owned();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim() == name)
+            .ok_or("В выписке Сбербанка не хватает столбцов.")
+    };
+    let operation_date = column(CSV_OPERATION_DATE)?;
+    let posting_date = column(CSV_POSTING_DATE)?;
+    let category = column(CSV_CATEGORY)?;
+    let description = column(CSV_DESCRIPTION)?;
+    let amount = column(CSV_AMOUNT)?;
+
+    let mut requests = Vec::new();
+    let mut failed = Vec::new();
+
+    for (index, record) in reader.records().enumerate() {
+        let line = index + 2;
+        let Ok(record) = record else {
+            failed.push(line);
+            continue;
+        };
+        let cell = |column: usize| record.get(column).unwrap_or_default().trim();
+
+        // Operations that are not processed yet have no processing date.
+        let created_at = parse_date_time(timezone, cell(posting_date))
+            .or_else(|| parse_date_time(timezone, cell(operation_date)));
+        let signed_amount = cell(amount);
+        let (Some(created_at), Some(amount)) = (created_at, parse_amount(signed_amount)) else {
+            failed.push(line);
+            continue;
+        };
+
+        let description = match cell(description) {
+            "" => cell(category),
+            description => description,
+        };
+
+        requests.push(ImportTransactionRequest {
+            transaction: CreateTransactionRequest {
+                chat_id,
+                user_id,
+                category_id: None,
+                amount,
+                description: description.to_string(),
+                currency_code: CurrencyCode::RUB,
+                message_id: None,
+                created_at: Some(created_at),
+            },
+            direction: Some(if signed_amount.starts_with('+') {
+                CategoryDirection::Income
+            } else {
+                CategoryDirection::Expense
+            }),
+        });
+    }
+
+    if requests.is_empty() && failed.is_empty() {
+        return Err("В выписке Сбербанка не найдено операций.");
+    }
+
+    Ok((requests, failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::imports::parsers::decode;
+    use crate::handlers::imports::tinkoff;
+
+    #[test]
+    fn test_parse() {
+        let text = include_str!("fixtures/sberbank.txt");
+        assert!(detect(text));
+
+        let (requests, failed) = parse(1, Some(2), Tz::UTC, text).unwrap();
+        let parsed: Vec<_> = requests
+            .iter()
+            .map(|request| {
+                (
+                    request.transaction.created_at.unwrap().to_rfc3339(),
+                    request.transaction.amount,
+                    request.transaction.description.as_str(),
+                    request.direction,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            parsed,
+            vec![
+                (
+                    "2025-12-06T00:00:00+00:00".to_string(),
+                    35000,
+                    "ПЯТЁРОЧКА 1234 Moscow RUS",
+                    Some(CategoryDirection::Expense)
+                ),
+                (
+                    "2025-12-04T00:00:00+00:00".to_string(),
+                    8500000,
+                    "Зарплата",
+                    Some(CategoryDirection::Income)
+                ),
+                (
+                    "2025-12-04T00:00:00+00:00".to_string(),
+                    120000,
+                    "КОФЕЙНЯ Moscow RUS",
+                    Some(CategoryDirection::Expense)
+                ),
+            ]
+        );
+        assert_eq!(failed, vec![20]);
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let text = decode(include_bytes!("fixtures/sberbank.csv"));
+        assert!(detect_csv(&text));
+        assert!(!tinkoff::detect(&text));
+
+        let (requests, failed) = parse_csv(1, Some(2), Tz::UTC, &text).unwrap();
+        let parsed: Vec<_> = requests
+            .iter()
+            .map(|request| {
+                (
+                    request.transaction.created_at.unwrap().to_rfc3339(),
+                    request.transaction.amount,
+                    request.transaction.description.as_str(),
+                    request.direction,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            parsed,
+            vec![
+                (
+                    "2025-12-06T00:00:00+00:00".to_string(),
+                    35000,
+                    "ПЯТЁРОЧКА 1234 Moscow RUS",
+                    Some(CategoryDirection::Expense)
+                ),
+                (
+                    "2025-12-04T00:00:00+00:00".to_string(),
+                    8500000,
+                    "Зарплата",
+                    Some(CategoryDirection::Income)
+                ),
+                (
+                    "2025-12-03T18:20:00+00:00".to_string(),
+                    113025,
+                    "SPOTIFY",
+                    Some(CategoryDirection::Expense)
+                ),
+            ]
+        );
+        assert_eq!(failed, vec![5]);
+    }
+
+    #[test]
+    fn test_detect() {
+        assert!(!detect("Дата;Сумма;Описание\n05.12.2025;350;такси"));
+        assert!(!detect_csv("Дата;Сумма;Описание\n05.12.2025;350;такси"));
+    }
+}
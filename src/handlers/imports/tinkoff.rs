@@ -0,0 +1,164 @@
+use crate::handlers::imports::parsers::{parse_amount, parse_date_time};
+use crate::models::transaction::CurrencyCode;
+use crate::proto::callback::v1::CategoryDirection;
+use crate::services::transactions::{CreateTransactionRequest, ImportTransactionRequest};
+use chrono_tz::Tz;
+
+const OPERATION_DATE: &str = "Дата операции";
+const POSTING_DATE: &str = "Дата платежа";
+const STATUS: &str = "Статус";
+const AMOUNT: &str = "Сумма операции";
+const CURRENCY: &str = "Валюта операции";
+const DESCRIPTION: &str = "Описание";
+
+/// Tinkoff exports operations as a `;`-separated CSV with a fixed Russian header.
+pub fn detect(text: &str) -> bool {
+    let header = text.lines().next().unwrap_or_default();
+
+    header.contains(POSTING_DATE) && header.contains(AMOUNT)
+}
+
+/// Parses the statement into transactions posted on the payment date. Declined operations
+/// are left out; the 1-based line numbers of rows that could not be parsed are returned.
+pub fn parse(
+    chat_id: i64,
+    user_id: Option<i64>,
+    timezone: Tz,
+    text: &str,
+) -> Result<(Vec<ImportTransactionRequest>, Vec<usize>), &'static str> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(b';')
+        .flexible(true)
+        .from_reader(text.as_bytes());
+
+    let headers = reader
+        .headers()
+        .map_err(|_| "Не удалось прочитать выписку Тинькофф.")?
+        .to_owned();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim() == name)
+            .ok_or("В выписке Тинькофф не хватает столбцов.")
+    };
+    let operation_date = column(OPERATION_DATE)?;
+    let posting_date = column(POSTING_DATE)?;
+    let status = column(STATUS)?;
+    let amount = column(AMOUNT)?;
+    let currency = column(CURRENCY)?;
+    let description = column(DESCRIPTION)?;
+
+    let mut requests = Vec::new();
+    let mut failed = Vec::new();
+
+    for (index, record) in reader.records().enumerate() {
+        let line = index + 2;
+        let Ok(record) = record else {
+            failed.push(line);
+            continue;
+        };
+        let cell = |column: usize| record.get(column).unwrap_or_default().trim();
+
+        if cell(status) == "FAILED" {
+            continue;
+        }
+
+        // Operations that are not posted yet have no payment date.
+        let created_at = parse_date_time(timezone, cell(posting_date))
+            .or_else(|| parse_date_time(timezone, cell(operation_date)));
+        let signed_amount = cell(amount);
+        let currency_code = CurrencyCode::from_code_str(cell(currency));
+        let (Some(created_at), Some(amount), Some(currency_code)) =
+            (created_at, parse_amount(signed_amount), currency_code)
+        else {
+            failed.push(line);
+            continue;
+        };
+
+        requests.push(ImportTransactionRequest {
+            transaction: CreateTransactionRequest {
+                chat_id,
+                user_id,
+                category_id: None,
+                amount,
+                description: cell(description).to_string(),
+                currency_code,
+                message_id: None,
+                created_at: Some(created_at),
+            },
+            direction: Some(if signed_amount.starts_with('-') {
+                CategoryDirection::Expense
+            } else {
+                CategoryDirection::Income
+            }),
+        });
+    }
+
+    Ok((requests, failed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::imports::parsers::decode;
+
+    #[test]
+    fn test_parse() {
+        let text = decode(include_bytes!("fixtures/tinkoff.csv"));
+        assert!(detect(&text));
+
+        let (requests, failed) = parse(1, Some(2), Tz::UTC, &text).unwrap();
+        let parsed: Vec<_> = requests
+            .iter()
+            .map(|request| {
+                (
+                    request.transaction.created_at.unwrap().to_rfc3339(),
+                    request.transaction.amount,
+                    request.transaction.currency_code,
+                    request.transaction.description.as_str(),
+                    request.direction,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            parsed,
+            vec![
+                (
+                    "2025-12-06T00:00:00+00:00".to_string(),
+                    35000,
+                    CurrencyCode::RUB,
+                    "Яндекс Такси",
+                    Some(CategoryDirection::Expense)
+                ),
+                (
+                    "2025-12-05T00:00:00+00:00".to_string(),
+                    123450,
+                    CurrencyCode::RUB,
+                    "Пятёрочка",
+                    Some(CategoryDirection::Expense)
+                ),
+                (
+                    "2025-12-04T00:00:00+00:00".to_string(),
+                    8500000,
+                    CurrencyCode::RUB,
+                    "Зарплата",
+                    Some(CategoryDirection::Income)
+                ),
+                (
+                    "2025-12-03T00:00:00+00:00".to_string(),
+                    1299,
+                    CurrencyCode::USD,
+                    "Spotify",
+                    Some(CategoryDirection::Expense)
+                ),
+            ]
+        );
+        assert_eq!(failed, vec![7]);
+    }
+
+    #[test]
+    fn test_detect() {
+        assert!(!detect("Дата;Сумма;Описание\n05.12.2025;350;такси"));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::members::message_builders::{show_member_list, show_member_settings};
+use crate::proto::callback::v1::{
+    MemberRole, ShowMemberList, ShowMemberSettings, UpdateMemberRole,
+};
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::sugar::bot::BotMessagesExt;
+
+pub async fn show_member_list(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    members_service: Arc<dyn services::members::Service>,
+    _callback: ShowMemberList,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let builder = show_member_list::MessageBuilder::new(message.chat.id, members_service);
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn show_member_settings(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    members_service: Arc<dyn services::members::Service>,
+    callback: ShowMemberSettings,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let builder =
+            show_member_settings::MessageBuilder::new(message.chat.id, members_service, callback);
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn update_member_role(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    members_service: Arc<dyn services::members::Service>,
+    callback: UpdateMemberRole,
+) -> HandlerResult {
+    let role = MemberRole::try_from(callback.role)?;
+    if role == MemberRole::Unspecified {
+        return Ok(());
+    }
+
+    if let Some(message) = callback_query.regular_message() {
+        members_service
+            .update_member_role(message.chat.id.0, callback.user_id, role)
+            .await?;
+
+        let builder = show_member_settings::MessageBuilder::new(
+            message.chat.id,
+            members_service,
+            ShowMemberSettings {
+                user_id: callback.user_id,
+            },
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::services;
+use crate::services::members::UpsertMemberRequest;
+use crate::telegram;
+use crate::telegram::HandlerResult;
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::types::ChatMember;
+
+/// Takes the owners of the group chats that have none from Telegram, once on start.
+pub async fn sync_owners(bot: Bot, members_service: Arc<dyn services::members::Service>) {
+    let chat_ids = match members_service.select_chats_without_owner().await {
+        Ok(chat_ids) => chat_ids,
+        Err(err) => {
+            log::error!("failed to select chats without owner: {err}");
+            return;
+        }
+    };
+
+    for chat_id in chat_ids {
+        if let Err(err) = sync_chat_owners(&bot, ChatId(chat_id), &members_service).await {
+            log::error!("failed to sync owners of {chat_id}: {err}");
+        }
+    }
+}
+
+/// Makes the creator of the chat its owner, or every administrator if the creator is not
+/// listed, e.g. because they left.
+pub async fn sync_chat_owners(
+    bot: &Bot,
+    chat_id: ChatId,
+    members_service: &Arc<dyn services::members::Service>,
+) -> HandlerResult {
+    let administrators = bot.get_chat_administrators(chat_id).await?;
+
+    let owners = administrators
+        .iter()
+        .filter(|administrator| !administrator.user.is_bot)
+        .collect::<Vec<&ChatMember>>();
+    let owners = match owners.iter().find(|owner| owner.is_owner()) {
+        Some(creator) => vec![*creator],
+        None => owners,
+    };
+
+    members_service
+        .set_owners(
+            chat_id.0,
+            owners
+                .into_iter()
+                .map(|owner| UpsertMemberRequest {
+                    chat_id: chat_id.0,
+                    user_id: telegram::user_id(&owner.user),
+                    username: owner.user.username.to_owned(),
+                    full_name: owner.user.full_name(),
+                })
+                .collect(),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,2 @@
+pub mod show_member_list;
+pub mod show_member_settings;
@@ -0,0 +1,60 @@
+use crate::handlers::callback;
+use crate::handlers::members::message_builders::show_member_settings::role_title;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, ShowMainSettings, ShowMemberSettings};
+use crate::services;
+use std::sync::Arc;
+use teloxide::prelude::ChatId;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    chat_id: ChatId,
+    members_service: Arc<dyn services::members::Service>,
+}
+
+impl MessageBuilder {
+    pub fn new(chat_id: ChatId, members_service: Arc<dyn services::members::Service>) -> Self {
+        Self {
+            chat_id,
+            members_service,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(
+            "👥 Участники чата\n\nВыберите участника, чтобы изменить его роль. \
+            В списке только те, кто уже писал в чат."
+                .to_string(),
+        )
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let members = self.members_service.select_members(self.chat_id.0).await?;
+
+        let mut keyboard = InlineKeyboardMarkup::default();
+        for member in members {
+            keyboard = keyboard.append_row(vec![InlineKeyboardButton::callback(
+                format!("{} · {}", member.full_name, role_title(member.role)),
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowMemberSettings(ShowMemberSettings {
+                        user_id: member.user_id,
+                    })),
+                })
+                .unwrap(),
+            )]);
+        }
+
+        Ok(keyboard.append_row(vec![InlineKeyboardButton::callback(
+            "🔙 К настройкам",
+            String::try_from(Callback {
+                query: Option::from(Query::ShowMainSettings(ShowMainSettings {})),
+            })
+            .unwrap(),
+        )]))
+    }
+}
@@ -0,0 +1,117 @@
+use crate::handlers::callback;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{
+    Callback, MemberRole, ShowMemberList, ShowMemberSettings, UpdateMemberRole,
+};
+use crate::services;
+use std::sync::Arc;
+use teloxide::prelude::ChatId;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub fn role_title(role: MemberRole) -> &'static str {
+    match role {
+        MemberRole::Owner => "👑 Владелец",
+        MemberRole::Viewer => "👀 Наблюдатель",
+        _ => "✏️ Редактор",
+    }
+}
+
+pub struct MessageBuilder {
+    chat_id: ChatId,
+    members_service: Arc<dyn services::members::Service>,
+    callback: ShowMemberSettings,
+}
+
+impl MessageBuilder {
+    pub fn new(
+        chat_id: ChatId,
+        members_service: Arc<dyn services::members::Service>,
+        callback: ShowMemberSettings,
+    ) -> Self {
+        Self {
+            chat_id,
+            members_service,
+            callback,
+        }
+    }
+
+    /// The last owner cannot be demoted, otherwise nobody could manage the chat.
+    async fn is_last_owner(&self) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let members = self.members_service.select_members(self.chat_id.0).await?;
+        let owners: Vec<i64> = members
+            .iter()
+            .filter(|member| member.role == MemberRole::Owner)
+            .map(|member| member.user_id)
+            .collect();
+
+        Ok(owners == [self.callback.user_id])
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let member = self
+            .members_service
+            .get_member(self.chat_id.0, self.callback.user_id)
+            .await?;
+
+        let mut text = member.full_name;
+        if let Some(username) = member.username {
+            text.push_str(&format!(" (@{username})"));
+        }
+        text.push_str(&format!(
+            "\nРоль: {}
+
+👑 Владелец — всё, включая роли участников
+✏️ Редактор — операции, категории и долги
+👀 Наблюдатель — только отчёты",
+            role_title(member.role)
+        ));
+
+        if self.is_last_owner().await? {
+            text.push_str("\n\nЭто единственный владелец чата, его роль нельзя изменить.");
+        }
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let mut keyboard = InlineKeyboardMarkup::default();
+
+        if !self.is_last_owner().await? {
+            let member = self
+                .members_service
+                .get_member(self.chat_id.0, self.callback.user_id)
+                .await?;
+
+            let buttons = [MemberRole::Owner, MemberRole::Editor, MemberRole::Viewer]
+                .into_iter()
+                .filter(|role| *role != member.role)
+                .map(|role| {
+                    InlineKeyboardButton::callback(
+                        role_title(role),
+                        String::try_from(Callback {
+                            query: Option::from(Query::UpdateMemberRole(UpdateMemberRole {
+                                user_id: self.callback.user_id,
+                                role: i32::from(role),
+                            })),
+                        })
+                        .unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            keyboard = keyboard.append_row(buttons);
+        }
+
+        Ok(keyboard.append_row(vec![InlineKeyboardButton::callback(
+            "🔙 К участникам",
+            String::try_from(Callback {
+                query: Option::from(Query::ShowMemberList(ShowMemberList {})),
+            })
+            .unwrap(),
+        )]))
+    }
+}
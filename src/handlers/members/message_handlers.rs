@@ -0,0 +1,102 @@
+use crate::handlers::members::jobs;
+use crate::models::member::has_role;
+use crate::proto::callback::v1::MemberRole;
+use crate::services;
+use crate::services::members::UpsertMemberRequest;
+use crate::telegram;
+use crate::telegram::HandlerResult;
+use std::sync::Arc;
+use teloxide::prelude::*;
+
+/// Keeps the names of whoever writes to the chat, so history and reports can show them.
+/// A group seen for the first time gets its owner from Telegram.
+pub async fn remember_member(
+    bot: Bot,
+    message: Message,
+    members_service: Arc<dyn services::members::Service>,
+) {
+    let Some(user) = message.from.as_ref().filter(|user| !user.is_bot) else {
+        return;
+    };
+
+    if let Err(err) = members_service
+        .upsert_member(UpsertMemberRequest {
+            chat_id: message.chat.id.0,
+            user_id: telegram::user_id(user),
+            username: user.username.to_owned(),
+            full_name: user.full_name(),
+        })
+        .await
+    {
+        log::error!("failed to remember member: {err}");
+    }
+
+    if message.chat.is_private() {
+        return;
+    }
+    match members_service.has_owner(message.chat.id.0).await {
+        Ok(true) => {}
+        Ok(false) => {
+            if let Err(err) = jobs::sync_chat_owners(&bot, message.chat.id, &members_service).await
+            {
+                log::error!("failed to sync owners of {}: {err}", message.chat.id);
+            }
+        }
+        Err(err) => log::error!("failed to check the owner of {}: {err}", message.chat.id),
+    }
+}
+
+/// Whether the sender may change anything in the chat.
+pub async fn can_edit(
+    message: Message,
+    members_service: Arc<dyn services::members::Service>,
+) -> bool {
+    has_sender_role(message, members_service, MemberRole::Editor).await
+}
+
+/// Whether the sender owns the chat.
+pub async fn is_owner(
+    message: Message,
+    members_service: Arc<dyn services::members::Service>,
+) -> bool {
+    has_sender_role(message, members_service, MemberRole::Owner).await
+}
+
+async fn has_sender_role(
+    message: Message,
+    members_service: Arc<dyn services::members::Service>,
+    required: MemberRole,
+) -> bool {
+    let Some(user) = message.from.as_ref() else {
+        return false;
+    };
+
+    match members_service
+        .get_role(message.chat.id.0, telegram::user_id(user))
+        .await
+    {
+        Ok(role) => has_role(role, required),
+        Err(err) => {
+            log::error!("failed to get member role: {err}");
+            false
+        }
+    }
+}
+
+pub async fn is_read_only(
+    message: Message,
+    members_service: Arc<dyn services::members::Service>,
+) -> bool {
+    !can_edit(message, members_service).await
+}
+
+pub async fn deny_read_only(bot: Bot, message: Message) -> HandlerResult {
+    bot.send_message(
+        message.chat.id,
+        "👀 У вас доступ только к отчётам: /report, /chart, /trend\n\n\
+        Попросите владельца чата выдать вам права в /settings → 👥 Участники.",
+    )
+    .await?;
+
+    Ok(())
+}
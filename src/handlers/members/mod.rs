@@ -0,0 +1,4 @@
+pub mod callback_handlers;
+pub mod jobs;
+mod message_builders;
+pub mod message_handlers;
@@ -1,5 +1,15 @@
+pub mod actions;
+pub mod backups;
 pub mod settings;
 pub mod categories;
+pub mod charts;
+pub mod debts;
+pub mod digests;
+pub mod errors;
+pub mod exports;
+pub mod imports;
 pub mod callback;
+pub mod members;
 pub mod message;
+pub mod reports;
 pub mod transactions;
\ No newline at end of file
@@ -0,0 +1,37 @@
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::reports::message_builders::show_report;
+use crate::proto::callback::v1::ShowReport;
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::sugar::bot::BotMessagesExt;
+
+pub async fn show_report(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    members_service: Arc<dyn services::members::Service>,
+    reports_service: Arc<dyn services::reports::Service>,
+    callback: ShowReport,
+) -> HandlerResult {
+    if !(1..=12).contains(&callback.month) {
+        return Ok(());
+    }
+
+    if let Some(message) = callback_query.regular_message() {
+        let builder = show_report::MessageBuilder::new(
+            message.chat.id,
+            members_service,
+            reports_service,
+            callback,
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::dates;
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::reports::message_builders::show_report;
+use crate::proto::callback::v1::ShowReport;
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use chrono::Datelike;
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+
+pub async fn show_report(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    members_service: Arc<dyn services::members::Service>,
+    reports_service: Arc<dyn services::reports::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+) -> HandlerResult {
+    let settings = settings_service.get_settings(message.chat.id.0).await?;
+    let today = dates::today(settings.timezone);
+    let builder = show_report::MessageBuilder::new(
+        message.chat.id,
+        members_service,
+        reports_service,
+        ShowReport {
+            year: today.year(),
+            month: today.month(),
+        },
+    );
+
+    bot.send_message(message.chat.id, builder.text().await?)
+        .reply_markup(builder.reply_markup().await?)
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1 @@
+pub mod show_report;
@@ -0,0 +1,152 @@
+use crate::handlers::callback;
+use crate::models::report::{CategoryTotal, MONTHS};
+use crate::models::transaction::{Amount, CurrencyCode};
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, ShowChart, ShowReport, ShowTrend};
+use crate::services;
+use std::sync::Arc;
+use teloxide::prelude::ChatId;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    chat_id: ChatId,
+    members_service: Arc<dyn services::members::Service>,
+    reports_service: Arc<dyn services::reports::Service>,
+    callback: ShowReport,
+}
+
+impl MessageBuilder {
+    pub fn new(
+        chat_id: ChatId,
+        members_service: Arc<dyn services::members::Service>,
+        reports_service: Arc<dyn services::reports::Service>,
+        callback: ShowReport,
+    ) -> Self {
+        Self {
+            chat_id,
+            members_service,
+            reports_service,
+            callback,
+        }
+    }
+}
+
+fn describe_category(total: &CategoryTotal, with_limit: bool) -> String {
+    let mut line = format!("{}: {}", total.title(), total.totals);
+
+    if with_limit && let Some(target_amount) = total.target_amount {
+        line.push_str(&format!(
+            " из {} {}",
+            Amount(target_amount),
+            CurrencyCode::RUB.as_symbol_str()
+        ));
+    }
+
+    for child in &total.children {
+        line.push_str(&format!("\n    ↳ {}", describe_category(child, with_limit)));
+    }
+
+    line
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let report = self
+            .reports_service
+            .monthly_report(self.chat_id.0, self.callback.year, self.callback.month)
+            .await?;
+
+        let mut text = format!(
+            "📊 {} {}\n\nРасходы: {}\nДоходы: {}",
+            MONTHS[report.month as usize - 1],
+            report.year,
+            report.expenses,
+            report.incomes
+        );
+
+        if !report.expense_categories.is_empty() {
+            text.push_str("\n\n📉 Расходы по категориям");
+            for total in &report.expense_categories {
+                text.push_str(&format!("\n{}", describe_category(total, true)));
+            }
+        }
+
+        if !report.income_categories.is_empty() {
+            text.push_str("\n\n📈 Доходы по категориям");
+            for total in &report.income_categories {
+                text.push_str(&format!("\n{}", describe_category(total, false)));
+            }
+        }
+
+        if !self.chat_id.is_user() && !report.members.is_empty() {
+            let member_names = self.members_service.member_names(self.chat_id.0).await?;
+
+            text.push_str("\n\n👥 По участникам");
+            for member in &report.members {
+                let name = match member.user_id {
+                    Some(user_id) => member_names
+                        .get(&user_id)
+                        .cloned()
+                        .unwrap_or_else(|| format!("id{user_id}")),
+                    None => "Неизвестно".to_string(),
+                };
+
+                text.push_str(&format!("\n{name}: −{}", member.expenses));
+                if !member.incomes.is_empty() {
+                    text.push_str(&format!(", +{}", member.incomes));
+                }
+            }
+        }
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let (year, month) = (self.callback.year, self.callback.month);
+        let previous = if month == 1 {
+            (year - 1, 12)
+        } else {
+            (year, month - 1)
+        };
+        let next = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+
+        let button = |text: &str, (year, month): (i32, u32)| {
+            InlineKeyboardButton::callback(
+                text,
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowReport(ShowReport { year, month })),
+                })
+                .unwrap(),
+            )
+        };
+
+        Ok(InlineKeyboardMarkup::default()
+            .append_row(vec![
+                button(&format!("⬅️ {}", MONTHS[previous.1 as usize - 1]), previous),
+                button(&format!("{} ➡️", MONTHS[next.1 as usize - 1]), next),
+            ])
+            .append_row(vec![
+                InlineKeyboardButton::callback(
+                    "📊 График",
+                    String::try_from(Callback {
+                        query: Option::from(Query::ShowChart(ShowChart { year, month })),
+                    })
+                    .unwrap(),
+                ),
+                InlineKeyboardButton::callback(
+                    "📈 За год",
+                    String::try_from(Callback {
+                        query: Option::from(Query::ShowTrend(ShowTrend { category_id: 0 })),
+                    })
+                    .unwrap(),
+                ),
+            ]))
+    }
+}
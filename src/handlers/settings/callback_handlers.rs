@@ -1,7 +1,15 @@
 use crate::handlers::callback::MessageBuilder;
-use crate::handlers::settings::message_builders::show_settings;
-use crate::proto::callback::v1::{ShowMainSettings};
+use crate::handlers::settings::message_builders::{
+    show_digest_settings, show_settings, show_timezone_settings,
+};
+use crate::models::settings::TIMEZONE_PRESETS;
+use crate::proto::callback::v1::{
+    DigestFrequency, ShowDigestSettings, ShowMainSettings, ShowTimezoneSettings,
+    UpdateDigestSettings, UpdateTimezone,
+};
+use crate::services;
 use crate::telegram::{Dialog, HandlerResult};
+use std::sync::Arc;
 use teloxide::Bot;
 use teloxide::payloads::EditMessageTextSetters;
 use teloxide::prelude::CallbackQuery;
@@ -13,9 +21,114 @@ pub async fn show_settings(
     callback_query: CallbackQuery,
     _query: ShowMainSettings,
 ) -> HandlerResult {
-    let builder = show_settings::MessageBuilder::new();
+    if let Some(message) = callback_query.regular_message() {
+        let builder = show_settings::MessageBuilder::new(message.chat.id);
+
+        bot.edit_text(message, builder.text().await?)
+            .reply_markup(builder.reply_markup().await?)
+            .await?;
+    }
+
+    Ok(())
+}
 
+pub async fn show_digest_settings(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    settings_service: Arc<dyn services::settings::Service>,
+    _callback: ShowDigestSettings,
+) -> HandlerResult {
     if let Some(message) = callback_query.regular_message() {
+        let settings = settings_service.get_settings(message.chat.id.0).await?;
+        let builder = show_digest_settings::MessageBuilder::new(settings);
+
+        bot.edit_text(message, builder.text().await?)
+            .reply_markup(builder.reply_markup().await?)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn update_digest_settings(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    settings_service: Arc<dyn services::settings::Service>,
+    callback: UpdateDigestSettings,
+) -> HandlerResult {
+    let frequency = DigestFrequency::try_from(callback.frequency)?;
+    if callback.hour > 23 {
+        return Ok(());
+    }
+
+    if let Some(message) = callback_query.regular_message() {
+        let settings = settings_service.get_settings(message.chat.id.0).await?;
+        // Telegram refuses to edit a message without changes.
+        if settings.digest_frequency == frequency && settings.digest_hour == callback.hour {
+            return Ok(());
+        }
+
+        settings_service
+            .update_digest(message.chat.id.0, frequency, callback.hour)
+            .await?;
+
+        let settings = settings_service.get_settings(message.chat.id.0).await?;
+        let builder = show_digest_settings::MessageBuilder::new(settings);
+
+        bot.edit_text(message, builder.text().await?)
+            .reply_markup(builder.reply_markup().await?)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn show_timezone_settings(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    settings_service: Arc<dyn services::settings::Service>,
+    _callback: ShowTimezoneSettings,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let settings = settings_service.get_settings(message.chat.id.0).await?;
+        let builder = show_timezone_settings::MessageBuilder::new(settings);
+
+        bot.edit_text(message, builder.text().await?)
+            .reply_markup(builder.reply_markup().await?)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn update_timezone(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    settings_service: Arc<dyn services::settings::Service>,
+    callback: UpdateTimezone,
+) -> HandlerResult {
+    let Some((timezone, _)) = TIMEZONE_PRESETS.get(callback.preset as usize) else {
+        return Ok(());
+    };
+
+    if let Some(message) = callback_query.regular_message() {
+        let settings = settings_service.get_settings(message.chat.id.0).await?;
+        // Telegram refuses to edit a message without changes.
+        if settings.timezone == *timezone {
+            return Ok(());
+        }
+
+        settings_service
+            .update_timezone(message.chat.id.0, *timezone)
+            .await?;
+
+        let settings = settings_service.get_settings(message.chat.id.0).await?;
+        let builder = show_timezone_settings::MessageBuilder::new(settings);
+
         bot.edit_text(message, builder.text().await?)
             .reply_markup(builder.reply_markup().await?)
             .await?;
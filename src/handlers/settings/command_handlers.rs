@@ -1,11 +1,17 @@
 use crate::handlers::callback::MessageBuilder;
 use crate::handlers::settings::message_builders::show_settings;
+use crate::handlers::settings::message_builders::show_timezone_settings::{
+    self, describe_timezone,
+};
+use crate::services;
 use crate::telegram::{Dialog, HandlerResult};
+use chrono_tz::Tz;
+use std::sync::Arc;
 use teloxide::Bot;
 use teloxide::prelude::*;
 
 pub async fn show_settings(bot: Bot, _dialog: Dialog, message: Message) -> HandlerResult {
-    let builder = show_settings::MessageBuilder::new();
+    let builder = show_settings::MessageBuilder::new(message.chat.id);
 
     bot.send_message(message.chat.id, builder.text().await?)
         .reply_markup(builder.reply_markup().await?)
@@ -13,3 +19,44 @@ pub async fn show_settings(bot: Bot, _dialog: Dialog, message: Message) -> Handl
 
     Ok(())
 }
+
+/// Sets the timezone by its IANA name, or shows the suggested ones without an argument.
+pub async fn set_timezone(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    settings_service: Arc<dyn services::settings::Service>,
+    timezone: String,
+) -> HandlerResult {
+    if timezone.trim().is_empty() {
+        let settings = settings_service.get_settings(message.chat.id.0).await?;
+        let builder = show_timezone_settings::MessageBuilder::new(settings);
+
+        bot.send_message(message.chat.id, builder.text().await?)
+            .reply_markup(builder.reply_markup().await?)
+            .await?;
+
+        return Ok(());
+    }
+
+    let Ok(timezone) = timezone.trim().parse::<Tz>() else {
+        bot.send_message(
+            message.chat.id,
+            "Неизвестный часовой пояс. Укажите его как в базе IANA, например: /timezone Europe/Moscow",
+        )
+        .await?;
+        return Ok(());
+    };
+
+    settings_service
+        .update_timezone(message.chat.id.0, timezone)
+        .await?;
+
+    bot.send_message(
+        message.chat.id,
+        format!("🕰 Часовой пояс: {}", describe_timezone(timezone)),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1 +1,3 @@
-pub mod show_settings;
\ No newline at end of file
+pub mod show_digest_settings;
+pub mod show_settings;
+pub mod show_timezone_settings;
\ No newline at end of file
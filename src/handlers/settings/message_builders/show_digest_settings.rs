@@ -0,0 +1,93 @@
+use crate::handlers::callback;
+use crate::models::settings::ChatSettings;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{
+    Callback, DigestFrequency, ShowMainSettings, UpdateDigestSettings,
+};
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    settings: ChatSettings,
+}
+
+impl MessageBuilder {
+    pub fn new(settings: ChatSettings) -> Self {
+        Self { settings }
+    }
+}
+
+fn frequency_title(frequency: DigestFrequency) -> &'static str {
+    match frequency {
+        DigestFrequency::Daily => "Ежедневно",
+        DigestFrequency::Weekly => "Еженедельно",
+        DigestFrequency::Unspecified => "Выключены",
+    }
+}
+
+fn update_button(text: String, frequency: DigestFrequency, hour: u32) -> InlineKeyboardButton {
+    InlineKeyboardButton::callback(
+        text,
+        String::try_from(Callback {
+            query: Option::from(Query::UpdateDigestSettings(UpdateDigestSettings {
+                frequency: i32::from(frequency),
+                hour,
+            })),
+        })
+        .unwrap(),
+    )
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(format!(
+            "🔔 Сводки\n\n\
+            Частота: {}\n\
+            Время отправки: {:02}:00 ({})\n\n\
+            Ежедневная сводка — траты за вчера и с начала недели, бюджет недели, \
+            главные категории и операции без категории. Еженедельная приходит по понедельникам.",
+            frequency_title(self.settings.digest_frequency),
+            self.settings.digest_hour,
+            self.settings.timezone.name()
+        ))
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let (frequency, hour) = (self.settings.digest_frequency, self.settings.digest_hour);
+
+        let frequencies: Vec<InlineKeyboardButton> = [
+            (DigestFrequency::Unspecified, "Выкл"),
+            (DigestFrequency::Daily, "Каждый день"),
+            (DigestFrequency::Weekly, "Раз в неделю"),
+        ]
+        .into_iter()
+        .map(|(option, title)| {
+            let title = if option == frequency {
+                format!("✅ {title}")
+            } else {
+                title.to_string()
+            };
+            update_button(title, option, hour)
+        })
+        .collect();
+
+        let earlier = (hour + 23) % 24;
+        let later = (hour + 1) % 24;
+
+        Ok(InlineKeyboardMarkup::default()
+            .append_row(frequencies)
+            .append_row(vec![
+                update_button(format!("◀️ {earlier:02}:00"), frequency, earlier),
+                update_button(format!("{later:02}:00 ▶️"), frequency, later),
+            ])
+            .append_row(vec![InlineKeyboardButton::callback(
+                "🔙 Назад",
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowMainSettings(ShowMainSettings {})),
+                })
+                .unwrap(),
+            )]))
+    }
+}
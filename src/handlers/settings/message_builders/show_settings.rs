@@ -1,13 +1,19 @@
 use crate::handlers::callback;
 use crate::proto::callback::v1::callback::Query;
-use crate::proto::callback::v1::{Callback, CategoryDirection, ShowCategoryList};
+use crate::proto::callback::v1::{
+    Callback, CategoryDirection, ShowCategoryList, ShowDigestSettings, ShowMemberList,
+    ShowTimezoneSettings,
+};
+use teloxide::prelude::ChatId;
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 
-pub struct MessageBuilder {}
+pub struct MessageBuilder {
+    chat_id: ChatId,
+}
 
 impl MessageBuilder {
-    pub fn new() -> Self {
-        MessageBuilder {}
+    pub fn new(chat_id: ChatId) -> Self {
+        MessageBuilder { chat_id }
     }
 }
 
@@ -20,25 +26,56 @@ impl callback::MessageBuilder for MessageBuilder {
     async fn reply_markup(
         &self,
     ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
-        Ok(InlineKeyboardMarkup::default().append_row(vec![
-            InlineKeyboardButton::callback(
-                "📉 Расходы",
-                String::try_from(Callback {
-                    query: Option::from(Query::ShowCategoryList(ShowCategoryList {
-                        category_direction: i32::from(CategoryDirection::Expense),
-                    })),
-                })
-                .unwrap(),
-            ),
-            InlineKeyboardButton::callback(
-                "📈 Доходы",
-                String::try_from(Callback {
-                    query: Option::from(Query::ShowCategoryList(ShowCategoryList {
-                        category_direction: i32::from(CategoryDirection::Income),
-                    })),
-                })
-                .unwrap(),
-            ),
-        ]))
+        let keyboard = InlineKeyboardMarkup::default()
+            .append_row(vec![
+                InlineKeyboardButton::callback(
+                    "📉 Расходы",
+                    String::try_from(Callback {
+                        query: Option::from(Query::ShowCategoryList(ShowCategoryList {
+                            category_direction: i32::from(CategoryDirection::Expense),
+                            parent_id: 0,
+                        })),
+                    })
+                    .unwrap(),
+                ),
+                InlineKeyboardButton::callback(
+                    "📈 Доходы",
+                    String::try_from(Callback {
+                        query: Option::from(Query::ShowCategoryList(ShowCategoryList {
+                            category_direction: i32::from(CategoryDirection::Income),
+                            parent_id: 0,
+                        })),
+                    })
+                    .unwrap(),
+                ),
+            ])
+            .append_row(vec![
+                InlineKeyboardButton::callback(
+                    "🔔 Сводки",
+                    String::try_from(Callback {
+                        query: Option::from(Query::ShowDigestSettings(ShowDigestSettings {})),
+                    })
+                    .unwrap(),
+                ),
+                InlineKeyboardButton::callback(
+                    "🕰 Часовой пояс",
+                    String::try_from(Callback {
+                        query: Option::from(Query::ShowTimezoneSettings(ShowTimezoneSettings {})),
+                    })
+                    .unwrap(),
+                ),
+            ]);
+
+        if self.chat_id.is_user() {
+            return Ok(keyboard);
+        }
+
+        Ok(keyboard.append_row(vec![InlineKeyboardButton::callback(
+            "👥 Участники",
+            String::try_from(Callback {
+                query: Option::from(Query::ShowMemberList(ShowMemberList {})),
+            })
+            .unwrap(),
+        )]))
     }
 }
@@ -0,0 +1,83 @@
+use crate::handlers::callback;
+use crate::models::settings::{ChatSettings, TIMEZONE_PRESETS};
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, ShowMainSettings, UpdateTimezone};
+use chrono::Utc;
+use chrono_tz::Tz;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+const BUTTONS_PER_ROW: usize = 3;
+
+pub struct MessageBuilder {
+    settings: ChatSettings,
+}
+
+impl MessageBuilder {
+    pub fn new(settings: ChatSettings) -> Self {
+        Self { settings }
+    }
+}
+
+/// `Europe/Moscow (UTC+03:00)`, with the offset in effect right now.
+pub fn describe_timezone(timezone: Tz) -> String {
+    format!(
+        "{} (UTC{})",
+        timezone.name(),
+        Utc::now().with_timezone(&timezone).format("%:z")
+    )
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let timezone = self.settings.timezone;
+
+        Ok(format!(
+            "🕰 Часовой пояс\n\n\
+            Сейчас: {}, местное время {}\n\n\
+            По нему считаются дни и месяцы в отчётах, выгрузках и сводках. Выберите город \
+            или укажите любой пояс командой, например: /timezone Asia/Tbilisi",
+            describe_timezone(timezone),
+            Utc::now().with_timezone(&timezone).format("%H:%M")
+        ))
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let buttons: Vec<InlineKeyboardButton> = TIMEZONE_PRESETS
+            .iter()
+            .enumerate()
+            .map(|(preset, (timezone, title))| {
+                let title = if *timezone == self.settings.timezone {
+                    format!("✅ {title}")
+                } else {
+                    title.to_string()
+                };
+
+                InlineKeyboardButton::callback(
+                    title,
+                    String::try_from(Callback {
+                        query: Option::from(Query::UpdateTimezone(UpdateTimezone {
+                            preset: preset as u32,
+                        })),
+                    })
+                    .unwrap(),
+                )
+            })
+            .collect();
+
+        let mut keyboard = InlineKeyboardMarkup::default();
+        for row in buttons.chunks(BUTTONS_PER_ROW) {
+            keyboard = keyboard.append_row(row.to_vec());
+        }
+
+        Ok(keyboard.append_row(vec![InlineKeyboardButton::callback(
+            "🔙 Назад",
+            String::try_from(Callback {
+                query: Option::from(Query::ShowMainSettings(ShowMainSettings {})),
+            })
+            .unwrap(),
+        )]))
+    }
+}
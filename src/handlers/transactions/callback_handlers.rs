@@ -1,16 +1,28 @@
-use crate::handlers::callback::MessageBuilder;
-use crate::handlers::transactions::message_builders::create_transaction;
-use crate::proto::callback::v1::CreateTransaction;
+use crate::handlers::callback::{CancellableMessageBuilder, MessageBuilder};
+use crate::handlers::transactions::message_builders::{
+    approve_transaction_category, create_transaction, show_history, show_transaction, show_triage,
+    update_transaction,
+};
+use crate::models::transaction::CurrencyCode;
+use crate::proto::callback::v1::triage_transaction::Action;
+use crate::proto::callback::v1::update_transaction::Field;
+use crate::proto::callback::v1::{
+    ApproveTransactionCategory, ApproveTransactionCurrency, CategoryDirection, CreateTransaction,
+    Currency, ShowHistory, ShowTransaction, ShowTriage, TriageTransaction, UpdateTransaction,
+};
 use crate::services;
-use crate::telegram::{Dialog, HandlerResult};
+use crate::services::transactions::UpdateTransactionRequest;
+use crate::telegram;
+use crate::telegram::{Dialog, HandlerResult, State};
 use std::sync::Arc;
 use teloxide::Bot;
 use teloxide::prelude::*;
 use teloxide::sugar::bot::BotMessagesExt;
+use teloxide::types::InlineKeyboardMarkup;
 
 pub async fn create_transaction(
     bot: Bot,
-    dialog: Dialog,
+    _dialog: Dialog,
     callback_query: CallbackQuery,
     categories_service: Arc<dyn services::categories::Service>,
     transactions_service: Arc<dyn services::transactions::Service>,
@@ -31,3 +43,327 @@ pub async fn create_transaction(
 
     Ok(())
 }
+
+pub async fn approve_transaction_category(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    categories_service: Arc<dyn services::categories::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+    callback: ApproveTransactionCategory,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        transactions_service
+            .update_transaction(UpdateTransactionRequest {
+                user_id: Some(telegram::user_id(&callback_query.from)),
+                category_id: Some(callback.category_id),
+                ..UpdateTransactionRequest::new(message.chat.id.0, callback.transaction_id)
+            })
+            .await?;
+
+        let builder = approve_transaction_category::MessageBuilder::new(
+            message.chat.id,
+            categories_service,
+            transactions_service,
+            callback,
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn show_transaction(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    categories_service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+    callback: ShowTransaction,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let builder = show_transaction::MessageBuilder::new(
+            message.chat.id,
+            categories_service,
+            settings_service,
+            transactions_service,
+            callback,
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn update_transaction(
+    bot: Bot,
+    dialog: Dialog,
+    callback_query: CallbackQuery,
+    categories_service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+    callback: UpdateTransaction,
+) -> HandlerResult {
+    let Some(message) = callback_query.regular_message() else {
+        return Ok(());
+    };
+
+    match Field::try_from(callback.field)? {
+        Field::Category => {
+            let builder = create_transaction::MessageBuilder::new(
+                message.chat.id,
+                categories_service,
+                transactions_service,
+                CreateTransaction {
+                    transaction_id: callback.transaction_id,
+                    category_direction: i32::from(CategoryDirection::Expense),
+                    is_short_mode: false,
+                    parent_id: 0,
+                },
+            );
+            let text = builder.text().await?;
+            let keyboard = builder.reply_markup().await?;
+
+            bot.edit_text(message, text).reply_markup(keyboard).await?;
+        }
+        Field::Currency => {
+            let builder = update_transaction::MessageBuilder::new(
+                message.chat.id,
+                transactions_service,
+                settings_service,
+                callback,
+            );
+            let text = builder.text().await?;
+            let keyboard = builder.reply_markup().await?;
+
+            bot.edit_text(message, text).reply_markup(keyboard).await?;
+        }
+        Field::Amount | Field::Description | Field::Date => {
+            let builder = update_transaction::MessageBuilder::new(
+                message.chat.id,
+                transactions_service,
+                settings_service,
+                callback,
+            );
+            let text = builder.text().await?;
+            let keyboard = builder.reply_markup().await?;
+
+            let answer_message = bot
+                .send_message(message.chat.id, text)
+                .reply_markup(keyboard)
+                .await?;
+
+            dialog
+                .update(State::UpdateTransaction {
+                    answer_message_id: answer_message.id,
+                    callback,
+                })
+                .await?;
+        }
+        Field::Unspecified => {}
+    }
+
+    Ok(())
+}
+
+pub async fn cancel_update_transaction(
+    bot: Bot,
+    dialog: Dialog,
+    callback_query: CallbackQuery,
+) -> HandlerResult {
+    let Some(State::UpdateTransaction {
+        callback: update_transaction_callback,
+        answer_message_id,
+    }) = dialog.get().await?
+    else {
+        return Ok(());
+    };
+
+    let builder = update_transaction::CancellableMessageBuilder::new(update_transaction_callback);
+    let text = builder.text().await?;
+    let keyboard = builder.reply_markup().await?;
+
+    if let Some(message) = callback_query.regular_message() {
+        bot.send_message(message.chat.id, text)
+            .reply_markup(keyboard)
+            .await?;
+
+        bot.edit_message_reply_markup(message.chat.id, answer_message_id)
+            .reply_markup(InlineKeyboardMarkup::default())
+            .await?;
+
+        dialog.exit().await?;
+    }
+
+    Ok(())
+}
+
+pub async fn approve_transaction_currency(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    categories_service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+    callback: ApproveTransactionCurrency,
+) -> HandlerResult {
+    let Ok(currency_code) = CurrencyCode::try_from(Currency::try_from(callback.currency)?) else {
+        return Ok(());
+    };
+
+    if let Some(message) = callback_query.regular_message() {
+        transactions_service
+            .update_transaction(UpdateTransactionRequest {
+                user_id: Some(telegram::user_id(&callback_query.from)),
+                currency_code: Some(currency_code),
+                ..UpdateTransactionRequest::new(message.chat.id.0, callback.transaction_id)
+            })
+            .await?;
+
+        let builder = show_transaction::MessageBuilder::new(
+            message.chat.id,
+            categories_service,
+            settings_service,
+            transactions_service,
+            ShowTransaction {
+                transaction_id: callback.transaction_id,
+            },
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn show_history(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    categories_service: Arc<dyn services::categories::Service>,
+    members_service: Arc<dyn services::members::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+    callback: ShowHistory,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let builder = show_history::MessageBuilder::new(
+            message.chat.id,
+            categories_service,
+            members_service,
+            settings_service,
+            transactions_service,
+            callback,
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn show_triage(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    categories_service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+    callback: ShowTriage,
+) -> HandlerResult {
+    if let Some(message) = callback_query.regular_message() {
+        let builder = show_triage::MessageBuilder::new(
+            message.chat.id,
+            categories_service,
+            settings_service,
+            transactions_service,
+            callback,
+        );
+        let text = builder.text().await?;
+        let keyboard = builder.reply_markup().await?;
+
+        bot.edit_text(message, text).reply_markup(keyboard).await?;
+    }
+
+    Ok(())
+}
+
+/// Categorizes or deletes the transaction shown in the triage and moves on. The next
+/// transaction takes the place of the handled one, so the offset stays the same.
+pub async fn triage_transaction(
+    bot: Bot,
+    _dialog: Dialog,
+    callback_query: CallbackQuery,
+    categories_service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+    callback: TriageTransaction,
+) -> HandlerResult {
+    let Some(message) = callback_query.regular_message() else {
+        return Ok(());
+    };
+    let chat_id = message.chat.id.0;
+    let user_id = Some(telegram::user_id(&callback_query.from));
+    let navigated_from = callback.navigated_from.unwrap_or_default();
+
+    match Action::try_from(callback.action)? {
+        Action::Categorize if navigated_from.apply_to_similar => {
+            let transaction = transactions_service
+                .get_transaction(chat_id, callback.transaction_id)
+                .await?;
+            transactions_service
+                .categorize_by_description(
+                    chat_id,
+                    user_id,
+                    &transaction.description,
+                    callback.category_id,
+                )
+                .await?;
+        }
+        Action::Categorize => {
+            transactions_service
+                .update_transaction(UpdateTransactionRequest {
+                    user_id,
+                    category_id: Some(callback.category_id),
+                    ..UpdateTransactionRequest::new(chat_id, callback.transaction_id)
+                })
+                .await?;
+        }
+        Action::Delete => {
+            transactions_service
+                .delete_transaction(chat_id, callback.transaction_id, user_id)
+                .await?;
+        }
+        Action::Unspecified => {}
+    }
+
+    let builder = show_triage::MessageBuilder::new(
+        message.chat.id,
+        categories_service,
+        settings_service,
+        transactions_service,
+        ShowTriage {
+            apply_to_similar: false,
+            parent_id: 0,
+            ..navigated_from
+        },
+    );
+    let text = builder.text().await?;
+    let keyboard = builder.reply_markup().await?;
+
+    bot.edit_text(message, text).reply_markup(keyboard).await?;
+
+    Ok(())
+}
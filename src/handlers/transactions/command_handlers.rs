@@ -0,0 +1,82 @@
+use crate::handlers::callback::MessageBuilder;
+use crate::handlers::transactions::message_builders::{show_history, show_triage};
+use crate::handlers::transactions::message_handlers::send_category_picker;
+use crate::proto::callback::v1::ShowHistory;
+use crate::services;
+use crate::telegram::{Dialog, HandlerResult};
+use std::sync::Arc;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::types::InlineKeyboardMarkup;
+
+/// Pickers sent at once by `/uncategorized`.
+const UNCATEGORIZED_BATCH: i64 = 10;
+
+pub async fn show_history(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    categories_service: Arc<dyn services::categories::Service>,
+    members_service: Arc<dyn services::members::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+) -> HandlerResult {
+    let builder = show_history::MessageBuilder::new(
+        message.chat.id,
+        categories_service,
+        members_service,
+        settings_service,
+        transactions_service,
+        ShowHistory { page: 0 },
+    );
+
+    bot.send_message(message.chat.id, builder.text().await?)
+        .reply_markup(builder.reply_markup().await?)
+        .await?;
+
+    Ok(())
+}
+
+/// Sends the category picker for each of the oldest uncategorized transactions.
+pub async fn show_uncategorized(
+    bot: Bot,
+    _dialog: Dialog,
+    message: Message,
+    categories_service: Arc<dyn services::categories::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+) -> HandlerResult {
+    let count = transactions_service
+        .count_uncategorized(message.chat.id.0)
+        .await?;
+    if count == 0 {
+        bot.send_message(message.chat.id, "🎉 Все операции разложены по категориям.")
+            .await?;
+        return Ok(());
+    }
+
+    let transactions = transactions_service
+        .select_uncategorized(message.chat.id.0, UNCATEGORIZED_BATCH, 0)
+        .await?;
+
+    let mut text = format!("❔ Без категории: {count}. Выберите категорию для каждой операции.");
+    if count > UNCATEGORIZED_BATCH {
+        text.push_str(&format!(
+            " Показаны первые {UNCATEGORIZED_BATCH}, остальные — по команде /uncategorized."
+        ));
+    }
+    bot.send_message(message.chat.id, text)
+        .reply_markup(InlineKeyboardMarkup::default().append_row(vec![show_triage::start_button()]))
+        .await?;
+
+    for transaction in &transactions {
+        send_category_picker(
+            &bot,
+            transaction,
+            categories_service.to_owned(),
+            transactions_service.to_owned(),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
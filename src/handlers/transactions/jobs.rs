@@ -0,0 +1,98 @@
+use crate::handlers::transactions::message_builders::show_triage;
+use crate::handlers::transactions::message_handlers::send_category_picker;
+use crate::models::transaction::Transaction;
+use crate::services;
+use crate::telegram::HandlerResult;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use teloxide::Bot;
+use teloxide::prelude::*;
+use teloxide::types::InlineKeyboardMarkup;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+/// Chats with more due reminders get a single message pointing to `/uncategorized` instead.
+const MAX_REMINDERS_PER_CHAT: usize = 3;
+
+/// Reminds about uncategorized transactions for as long as the bot runs.
+pub async fn remind_uncategorized(
+    bot: Bot,
+    categories_service: Arc<dyn services::categories::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+) {
+    let mut interval = tokio::time::interval(CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(err) = send_reminders(&bot, &categories_service, &transactions_service).await {
+            log::error!("failed to send reminders: {err}");
+        }
+    }
+}
+
+async fn send_reminders(
+    bot: &Bot,
+    categories_service: &Arc<dyn services::categories::Service>,
+    transactions_service: &Arc<dyn services::transactions::Service>,
+) -> HandlerResult {
+    let now = Utc::now();
+    let transactions = transactions_service.due_reminders(now).await?;
+
+    for chat_transactions in transactions.chunk_by(|a, b| a.chat_id == b.chat_id) {
+        let ids: Vec<i64> = chat_transactions
+            .iter()
+            .map(|transaction| transaction.id)
+            .collect();
+        // Marked first, so a chat the bot can no longer write to is not retried.
+        transactions_service.mark_reminded(&ids, now).await?;
+
+        if let Err(err) = remind_chat(
+            bot,
+            chat_transactions,
+            categories_service,
+            transactions_service,
+        )
+        .await
+        {
+            log::error!(
+                "failed to send reminders to {}: {err}",
+                chat_transactions[0].chat_id
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn remind_chat(
+    bot: &Bot,
+    transactions: &[Transaction],
+    categories_service: &Arc<dyn services::categories::Service>,
+    transactions_service: &Arc<dyn services::transactions::Service>,
+) -> HandlerResult {
+    if transactions.len() > MAX_REMINDERS_PER_CHAT {
+        bot.send_message(
+            ChatId(transactions[0].chat_id),
+            format!(
+                "⏰ Операций без категории: {}. Разберите их: /uncategorized",
+                transactions.len()
+            ),
+        )
+        .reply_markup(InlineKeyboardMarkup::default().append_row(vec![show_triage::start_button()]))
+        .await?;
+        return Ok(());
+    }
+
+    for transaction in transactions {
+        send_category_picker(
+            bot,
+            transaction,
+            categories_service.to_owned(),
+            transactions_service.to_owned(),
+        )
+        .await?;
+    }
+
+    Ok(())
+}
@@ -1,12 +1,14 @@
 use crate::handlers::callback;
-use crate::proto::callback::v1::ApproveTransactionCategory;
+use crate::models::transaction::Amount;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{ApproveTransactionCategory, Callback, ShowTransaction};
 use crate::services;
 use std::sync::Arc;
-use teloxide::prelude::CallbackQuery;
-use teloxide::types::InlineKeyboardMarkup;
+use teloxide::prelude::ChatId;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 
 pub struct MessageBuilder {
-    callback_query: CallbackQuery,
+    chat_id: ChatId,
     categories_service: Arc<dyn services::categories::Service>,
     transactions_service: Arc<dyn services::transactions::Service>,
     callback: ApproveTransactionCategory,
@@ -14,13 +16,13 @@ pub struct MessageBuilder {
 
 impl MessageBuilder {
     pub fn new(
-        callback_query: CallbackQuery,
+        chat_id: ChatId,
         categories_service: Arc<dyn services::categories::Service>,
         transactions_service: Arc<dyn services::transactions::Service>,
         callback: ApproveTransactionCategory,
     ) -> Self {
         Self {
-            callback_query,
+            chat_id,
             categories_service,
             transactions_service,
             callback,
@@ -31,12 +33,38 @@ impl MessageBuilder {
 #[async_trait::async_trait]
 impl callback::MessageBuilder for MessageBuilder {
     async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        Ok("".to_string())
+        let transaction = self
+            .transactions_service
+            .get_transaction(self.chat_id.0, self.callback.transaction_id)
+            .await?;
+        let category = self
+            .categories_service
+            .get_category(self.chat_id.0, self.callback.category_id)
+            .await?;
+
+        Ok(format!(
+            "✅ {} {} {} добавлено в «{} {}»",
+            Amount(transaction.amount),
+            transaction.currency_code.as_symbol_str(),
+            transaction.description,
+            category.label,
+            category.name
+        ))
     }
 
     async fn reply_markup(
         &self,
     ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
-        Ok(InlineKeyboardMarkup::default())
+        Ok(
+            InlineKeyboardMarkup::default().append_row(vec![InlineKeyboardButton::callback(
+                "✏️ Изменить",
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowTransaction(ShowTransaction {
+                        transaction_id: self.callback.transaction_id,
+                    })),
+                })
+                .unwrap(),
+            )]),
+        )
     }
 }
@@ -1,11 +1,13 @@
 use crate::handlers::callback;
+use crate::models::category::{subcategories, top_level};
+use crate::models::transaction::Amount;
 use crate::proto::callback::v1::callback::Query;
 use crate::proto::callback::v1::{
     ApproveTransactionCategory, Callback, CategoryDirection, CreateTransaction,
 };
 use crate::services;
 use std::sync::Arc;
-use teloxide::prelude::{ChatId, Message};
+use teloxide::prelude::ChatId;
 use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
 
 pub struct MessageBuilder {
@@ -39,11 +41,26 @@ impl callback::MessageBuilder for MessageBuilder {
             .get_transaction(self.chat_id.0, self.callback.transaction_id)
             .await?;
 
-        Ok(format!(
+        let mut text = format!(
             "Выберите, в какую категорию добавить {} {}",
-            transaction.amount,
+            Amount(transaction.amount),
             transaction.currency_code.as_symbol_str()
-        ))
+        );
+
+        let categories = self
+            .categories_service
+            .select_categories(
+                self.chat_id.0,
+                CategoryDirection::try_from(self.callback.category_direction)?,
+            )
+            .await?;
+        if categories.is_empty() {
+            text.push_str(
+                "\n\nКатегорий пока нет: отправьте /start, чтобы добавить готовый набор.",
+            );
+        }
+
+        Ok(text)
     }
 
     async fn reply_markup(
@@ -51,30 +68,53 @@ impl callback::MessageBuilder for MessageBuilder {
     ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
         let categories = self
             .categories_service
-            .select_categories(
+            .select_picker_categories(
                 self.chat_id.0,
-                CategoryDirection::try_from(self.callback.category_direction).unwrap(),
+                CategoryDirection::try_from(self.callback.category_direction)?,
             )
             .await?;
 
+        let parent = categories
+            .iter()
+            .find(|category| category.id == self.callback.parent_id);
+        let list = match parent {
+            Some(parent) => std::iter::once(parent)
+                .chain(subcategories(&categories, parent.id))
+                .collect(),
+            None => top_level(&categories),
+        };
+
         let mut reply_markup = InlineKeyboardMarkup::default();
-        for chunk in categories.chunks(if self.callback.is_short_mode { 5 } else { 3 }) {
+        for chunk in list.chunks(if self.callback.is_short_mode { 5 } else { 3 }) {
             let row: Vec<InlineKeyboardButton> = chunk
                 .iter()
                 .map(|category| {
+                    let mut text = if self.callback.is_short_mode {
+                        category.label.to_string()
+                    } else {
+                        format!("{} {}", category.label, category.name)
+                    };
+
+                    // Choosing a parent opens its subcategories, unless they are already shown.
+                    let query = if parent.is_none()
+                        && !subcategories(&categories, category.id).is_empty()
+                    {
+                        text.push_str(" ›");
+                        Query::CreateTransaction(CreateTransaction {
+                            parent_id: category.id,
+                            ..self.callback
+                        })
+                    } else {
+                        Query::ApproveTransactionCategory(ApproveTransactionCategory {
+                            transaction_id: self.callback.transaction_id,
+                            category_id: category.id,
+                        })
+                    };
+
                     InlineKeyboardButton::callback(
-                        if self.callback.is_short_mode {
-                            format!("{}", category.label)
-                        } else {
-                            format!("{} {}", category.label, category.name)
-                        },
+                        text,
                         String::try_from(Callback {
-                            query: Option::from(Query::ApproveTransactionCategory(
-                                ApproveTransactionCategory {
-                                    transaction_id: self.callback.transaction_id,
-                                    category_id: category.id,
-                                },
-                            )),
+                            query: Option::from(query),
                         })
                         .unwrap(),
                     )
@@ -84,24 +124,37 @@ impl callback::MessageBuilder for MessageBuilder {
             reply_markup = reply_markup.append_row(row);
         }
 
+        if parent.is_some() {
+            reply_markup = reply_markup.append_row(vec![InlineKeyboardButton::callback(
+                "🔙 Назад",
+                String::try_from(Callback {
+                    query: Option::from(Query::CreateTransaction(CreateTransaction {
+                        parent_id: 0,
+                        ..self.callback
+                    })),
+                })
+                .unwrap(),
+            )]);
+        }
+
+        let (switch_text, switch_direction) =
+            match CategoryDirection::try_from(self.callback.category_direction)? {
+                CategoryDirection::Expense => ("📈 Доходы", CategoryDirection::Income),
+                CategoryDirection::Income => ("📉 Расходы", CategoryDirection::Expense),
+                CategoryDirection::Unspecified => {
+                    return Err("unspecified category direction".into());
+                }
+            };
+
         reply_markup = reply_markup.append_row(vec![
             InlineKeyboardButton::callback(
-                match CategoryDirection::try_from(self.callback.category_direction)? {
-                    CategoryDirection::Expense => "📈 Доходы",
-                    CategoryDirection::Income => "📉 Расходы",
-                    CategoryDirection::Unspecified => unreachable!(),
-                },
+                switch_text,
                 String::try_from(Callback {
                     query: Option::from(Query::CreateTransaction(CreateTransaction {
                         transaction_id: self.callback.transaction_id,
-                        category_direction: match CategoryDirection::try_from(
-                            self.callback.category_direction,
-                        )? {
-                            CategoryDirection::Expense => CategoryDirection::Income.into(),
-                            CategoryDirection::Income => CategoryDirection::Expense.into(),
-                            CategoryDirection::Unspecified => unreachable!(),
-                        },
+                        category_direction: i32::from(switch_direction),
                         is_short_mode: self.callback.is_short_mode,
+                        parent_id: 0,
                     })),
                 })
                 .unwrap(),
@@ -119,6 +172,7 @@ impl callback::MessageBuilder for MessageBuilder {
                             true => false,
                             false => true,
                         },
+                        parent_id: self.callback.parent_id,
                     })),
                 })
                 .unwrap(),
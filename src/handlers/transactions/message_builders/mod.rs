@@ -1,2 +1,6 @@
 pub mod approve_transaction_category;
-pub mod create_transaction;
\ No newline at end of file
+pub mod create_transaction;
+pub mod show_history;
+pub mod show_transaction;
+pub mod show_triage;
+pub mod update_transaction;
\ No newline at end of file
@@ -0,0 +1,169 @@
+use crate::handlers::callback;
+use crate::models::transaction::Amount;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::{Callback, ShowHistory, ShowTransaction};
+use crate::services;
+use std::collections::HashMap;
+use std::sync::Arc;
+use teloxide::prelude::ChatId;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+const PAGE_SIZE: i64 = 10;
+
+pub struct MessageBuilder {
+    chat_id: ChatId,
+    categories_service: Arc<dyn services::categories::Service>,
+    members_service: Arc<dyn services::members::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+    callback: ShowHistory,
+}
+
+impl MessageBuilder {
+    pub fn new(
+        chat_id: ChatId,
+        categories_service: Arc<dyn services::categories::Service>,
+        members_service: Arc<dyn services::members::Service>,
+        settings_service: Arc<dyn services::settings::Service>,
+        transactions_service: Arc<dyn services::transactions::Service>,
+        callback: ShowHistory,
+    ) -> Self {
+        Self {
+            chat_id,
+            categories_service,
+            members_service,
+            settings_service,
+            transactions_service,
+            callback,
+        }
+    }
+
+    async fn page_count(&self) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        let count = self
+            .transactions_service
+            .count_transactions(self.chat_id.0)
+            .await?;
+
+        Ok(((count + PAGE_SIZE - 1) / PAGE_SIZE).max(1))
+    }
+
+    async fn page(&self) -> Result<i64, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.callback.page.clamp(0, self.page_count().await? - 1))
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let page = self.page().await?;
+        let transactions = self
+            .transactions_service
+            .select_transactions(self.chat_id.0, PAGE_SIZE, page * PAGE_SIZE)
+            .await?;
+
+        if transactions.is_empty() {
+            return Ok("🗂 Операций пока нет.".to_string());
+        }
+
+        let category_labels: HashMap<i64, String> = self
+            .categories_service
+            .select_all_categories(self.chat_id.0)
+            .await?
+            .into_iter()
+            .map(|category| (category.id, category.label))
+            .collect();
+        // Authors are only worth showing when several people share the chat.
+        let member_names = if self.chat_id.is_user() {
+            HashMap::new()
+        } else {
+            self.members_service.member_names(self.chat_id.0).await?
+        };
+        let settings = self.settings_service.get_settings(self.chat_id.0).await?;
+
+        let mut text = format!(
+            "🗂 История операций (стр. {}/{})\n",
+            page + 1,
+            self.page_count().await?
+        );
+        for (index, transaction) in transactions.iter().enumerate() {
+            let label = transaction
+                .category_id
+                .and_then(|category_id| category_labels.get(&category_id))
+                .map(String::as_str)
+                .unwrap_or("❔");
+
+            text.push_str(&format!(
+                "\n{}. {} {label} {} {} {}",
+                index + 1,
+                transaction
+                    .created_at
+                    .with_timezone(&settings.timezone)
+                    .format("%d.%m"),
+                Amount(transaction.amount),
+                transaction.currency_code.as_symbol_str(),
+                transaction.description
+            ));
+
+            if let Some(name) = transaction
+                .user_id
+                .and_then(|user_id| member_names.get(&user_id))
+            {
+                text.push_str(&format!(" · {name}"));
+            }
+        }
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let page_count = self.page_count().await?;
+        let page = self.page().await?;
+        let transactions = self
+            .transactions_service
+            .select_transactions(self.chat_id.0, PAGE_SIZE, page * PAGE_SIZE)
+            .await?;
+
+        let mut keyboard = InlineKeyboardMarkup::default();
+
+        let buttons: Vec<InlineKeyboardButton> = transactions
+            .iter()
+            .enumerate()
+            .map(|(index, transaction)| {
+                InlineKeyboardButton::callback(
+                    format!("{}", index + 1),
+                    String::try_from(Callback {
+                        query: Option::from(Query::ShowTransaction(ShowTransaction {
+                            transaction_id: transaction.id,
+                        })),
+                    })
+                    .unwrap(),
+                )
+            })
+            .collect();
+        for row in buttons.chunks(5) {
+            keyboard = keyboard.append_row(row.to_vec());
+        }
+
+        let button = |text: &str, page: i64| {
+            InlineKeyboardButton::callback(
+                text,
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowHistory(ShowHistory { page })),
+                })
+                .unwrap(),
+            )
+        };
+
+        let mut row = Vec::new();
+        if page > 0 {
+            row.push(button("⬅️ Новее", page - 1));
+        }
+        if page + 1 < page_count {
+            row.push(button("Старее ➡️", page + 1));
+        }
+
+        Ok(keyboard.append_row(row))
+    }
+}
@@ -0,0 +1,115 @@
+use crate::handlers::callback;
+use crate::models::transaction::Amount;
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::update_transaction::Field;
+use crate::proto::callback::v1::{Callback, ShowTransaction, UpdateTransaction};
+use crate::services;
+use std::sync::Arc;
+use teloxide::prelude::ChatId;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    chat_id: ChatId,
+    categories_service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+    callback: ShowTransaction,
+}
+
+impl MessageBuilder {
+    pub fn new(
+        chat_id: ChatId,
+        categories_service: Arc<dyn services::categories::Service>,
+        settings_service: Arc<dyn services::settings::Service>,
+        transactions_service: Arc<dyn services::transactions::Service>,
+        callback: ShowTransaction,
+    ) -> Self {
+        Self {
+            chat_id,
+            categories_service,
+            settings_service,
+            transactions_service,
+            callback,
+        }
+    }
+
+    fn button(&self, text: &str, field: Field) -> InlineKeyboardButton {
+        InlineKeyboardButton::callback(
+            text,
+            String::try_from(Callback {
+                query: Option::from(Query::UpdateTransaction(UpdateTransaction {
+                    transaction_id: self.callback.transaction_id,
+                    field: i32::from(field),
+                })),
+            })
+            .unwrap(),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let transaction = self
+            .transactions_service
+            .get_transaction(self.chat_id.0, self.callback.transaction_id)
+            .await?;
+        let settings = self.settings_service.get_settings(self.chat_id.0).await?;
+
+        let category = match transaction.category_id {
+            Some(category_id) => self
+                .categories_service
+                .select_all_categories(self.chat_id.0)
+                .await?
+                .into_iter()
+                .find(|category| category.id == category_id)
+                .map(|category| match category.deleted_at {
+                    Some(_) => format!("{} {} (в архиве)", category.label, category.name),
+                    None => format!("{} {}", category.label, category.name),
+                })
+                .unwrap_or_else(|| "Удалённая категория".to_string()),
+            None => "Без категории".to_string(),
+        };
+
+        let description = if transaction.description.is_empty() {
+            "—".to_string()
+        } else {
+            transaction.description
+        };
+
+        let amount_modified = if transaction.amount_modified {
+            " (изменена)"
+        } else {
+            ""
+        };
+
+        Ok(format!(
+            "🧾 Операция
+Сумма: {} {}{amount_modified}
+Описание: {description}
+Категория: {category}
+Дата: {}",
+            Amount(transaction.amount),
+            transaction.currency_code.as_symbol_str(),
+            transaction
+                .created_at
+                .with_timezone(&settings.timezone)
+                .format("%d.%m.%Y"),
+        ))
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(InlineKeyboardMarkup::default()
+            .append_row(vec![
+                self.button("💰 Сумма", Field::Amount),
+                self.button("✏️ Описание", Field::Description),
+            ])
+            .append_row(vec![
+                self.button("🏷 Категория", Field::Category),
+                self.button("📅 Дата", Field::Date),
+            ])
+            .append_row(vec![self.button("💱 Валюта", Field::Currency)]))
+    }
+}
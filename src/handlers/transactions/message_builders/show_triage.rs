@@ -0,0 +1,282 @@
+use crate::handlers::callback;
+use crate::models::category::{subcategories, top_level};
+use crate::models::transaction::{Amount, Transaction};
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::triage_transaction::Action;
+use crate::proto::callback::v1::{Callback, CategoryDirection, ShowTriage, TriageTransaction};
+use crate::services;
+use std::sync::Arc;
+use teloxide::prelude::ChatId;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+/// Button that starts the triage from the oldest uncategorized transaction.
+pub fn start_button() -> InlineKeyboardButton {
+    InlineKeyboardButton::callback(
+        "🗂 Разобрать по одной",
+        String::try_from(Callback {
+            query: Option::from(Query::ShowTriage(ShowTriage::default())),
+        })
+        .unwrap(),
+    )
+}
+
+pub struct MessageBuilder {
+    chat_id: ChatId,
+    categories_service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+    callback: ShowTriage,
+}
+
+impl MessageBuilder {
+    pub fn new(
+        chat_id: ChatId,
+        categories_service: Arc<dyn services::categories::Service>,
+        settings_service: Arc<dyn services::settings::Service>,
+        transactions_service: Arc<dyn services::transactions::Service>,
+        callback: ShowTriage,
+    ) -> Self {
+        Self {
+            chat_id,
+            categories_service,
+            settings_service,
+            transactions_service,
+            callback,
+        }
+    }
+
+    async fn transaction(
+        &self,
+    ) -> Result<Option<Transaction>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self
+            .transactions_service
+            .select_uncategorized(self.chat_id.0, 1, self.callback.offset)
+            .await?
+            .into_iter()
+            .next())
+    }
+
+    fn direction(&self) -> CategoryDirection {
+        match CategoryDirection::try_from(self.callback.category_direction) {
+            Ok(CategoryDirection::Income) => CategoryDirection::Income,
+            _ => CategoryDirection::Expense,
+        }
+    }
+
+    fn triage_button(
+        &self,
+        text: String,
+        transaction_id: i64,
+        action: Action,
+        category_id: i64,
+    ) -> InlineKeyboardButton {
+        InlineKeyboardButton::callback(
+            text,
+            String::try_from(Callback {
+                query: Option::from(Query::TriageTransaction(TriageTransaction {
+                    transaction_id,
+                    action: action.into(),
+                    category_id,
+                    navigated_from: Some(self.callback),
+                })),
+            })
+            .unwrap(),
+        )
+    }
+
+    fn show_button(&self, text: String, callback: ShowTriage) -> InlineKeyboardButton {
+        InlineKeyboardButton::callback(
+            text,
+            String::try_from(Callback {
+                query: Option::from(Query::ShowTriage(callback)),
+            })
+            .unwrap(),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let count = self
+            .transactions_service
+            .count_uncategorized(self.chat_id.0)
+            .await?;
+        if count == 0 {
+            return Ok("🎉 Все операции разложены по категориям.".to_string());
+        }
+
+        let Some(transaction) = self.transaction().await? else {
+            return Ok(format!(
+                "⏭ Пропущено операций без категории: {count}. Начните сначала, чтобы разобрать их."
+            ));
+        };
+
+        let settings = self.settings_service.get_settings(self.chat_id.0).await?;
+        let mut text = format!(
+            "🗂 Разбор операций без категории (осталось {count})\n\n{} · {} {} · {}",
+            transaction
+                .created_at
+                .with_timezone(&settings.timezone)
+                .format("%d.%m.%Y %H:%M"),
+            Amount(transaction.amount),
+            transaction.currency_code.as_symbol_str(),
+            transaction.description
+        );
+
+        if let Some(category_id) = self
+            .transactions_service
+            .suggest_category(self.chat_id.0, &transaction.description, None)
+            .await?
+        {
+            let category = self
+                .categories_service
+                .get_category(self.chat_id.0, category_id)
+                .await?;
+            text.push_str(&format!(
+                "\n\n💡 Похоже на «{} {}»",
+                category.label, category.name
+            ));
+        }
+
+        Ok(text)
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        let Some(transaction) = self.transaction().await? else {
+            let mut reply_markup = InlineKeyboardMarkup::default();
+            if self.callback.offset > 0 {
+                reply_markup = reply_markup.append_row(vec![self.show_button(
+                    "🔁 Начать сначала".to_string(),
+                    ShowTriage {
+                        offset: 0,
+                        ..self.callback
+                    },
+                )]);
+            }
+            return Ok(reply_markup);
+        };
+
+        let mut reply_markup = InlineKeyboardMarkup::default();
+
+        if let Some(category_id) = self
+            .transactions_service
+            .suggest_category(self.chat_id.0, &transaction.description, None)
+            .await?
+        {
+            let category = self
+                .categories_service
+                .get_category(self.chat_id.0, category_id)
+                .await?;
+            reply_markup = reply_markup.append_row(vec![self.triage_button(
+                format!("💡 {} {}", category.label, category.name),
+                transaction.id,
+                Action::Categorize,
+                category.id,
+            )]);
+        }
+
+        let categories = self
+            .categories_service
+            .select_picker_categories(self.chat_id.0, self.direction())
+            .await?;
+        let parent = categories
+            .iter()
+            .find(|category| category.id == self.callback.parent_id);
+        let list = match parent {
+            Some(parent) => std::iter::once(parent)
+                .chain(subcategories(&categories, parent.id))
+                .collect(),
+            None => top_level(&categories),
+        };
+        for chunk in list.chunks(3) {
+            let row: Vec<InlineKeyboardButton> = chunk
+                .iter()
+                .map(|category| {
+                    let text = format!("{} {}", category.label, category.name);
+                    if parent.is_none() && !subcategories(&categories, category.id).is_empty() {
+                        self.show_button(
+                            format!("{text} ›"),
+                            ShowTriage {
+                                parent_id: category.id,
+                                ..self.callback
+                            },
+                        )
+                    } else {
+                        self.triage_button(text, transaction.id, Action::Categorize, category.id)
+                    }
+                })
+                .collect();
+
+            reply_markup = reply_markup.append_row(row);
+        }
+
+        let mut row = vec![];
+        if parent.is_some() {
+            row.push(self.show_button(
+                "🔙 Назад".to_string(),
+                ShowTriage {
+                    parent_id: 0,
+                    ..self.callback
+                },
+            ));
+        }
+        row.push(
+            self.show_button(
+                match self.direction() {
+                    CategoryDirection::Income => "📉 Расходы",
+                    _ => "📈 Доходы",
+                }
+                .to_string(),
+                ShowTriage {
+                    category_direction: match self.direction() {
+                        CategoryDirection::Income => CategoryDirection::Expense.into(),
+                        _ => CategoryDirection::Income.into(),
+                    },
+                    parent_id: 0,
+                    ..self.callback
+                },
+            ),
+        );
+
+        let similar = self
+            .transactions_service
+            .select_similar_uncategorized(self.chat_id.0, &transaction.description)
+            .await?
+            .len();
+        if similar > 1 {
+            row.push(self.show_button(
+                format!(
+                    "{} Всем «{}» ({similar})",
+                    if self.callback.apply_to_similar {
+                        "✅"
+                    } else {
+                        "☑️"
+                    },
+                    transaction.description
+                ),
+                ShowTriage {
+                    apply_to_similar: !self.callback.apply_to_similar,
+                    ..self.callback
+                },
+            ));
+        }
+        reply_markup = reply_markup.append_row(row);
+
+        reply_markup = reply_markup.append_row(vec![
+            self.show_button(
+                "⏭ Пропустить".to_string(),
+                ShowTriage {
+                    offset: self.callback.offset + 1,
+                    parent_id: 0,
+                    ..self.callback
+                },
+            ),
+            self.triage_button("🗑 Удалить".to_string(), transaction.id, Action::Delete, 0),
+        ]);
+
+        Ok(reply_markup)
+    }
+}
@@ -0,0 +1,177 @@
+use crate::handlers::callback;
+use crate::models::transaction::{Amount, CurrencyCode};
+use crate::proto::callback::v1::callback::Query;
+use crate::proto::callback::v1::update_transaction::Field;
+use crate::proto::callback::v1::{
+    ApproveTransactionCurrency, Callback, CancelUpdateTransaction, Currency, ShowTransaction,
+    UpdateTransaction,
+};
+use crate::services;
+use std::sync::Arc;
+use teloxide::prelude::ChatId;
+use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup};
+
+pub struct MessageBuilder {
+    chat_id: ChatId,
+    service: Arc<dyn services::transactions::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    callback: UpdateTransaction,
+}
+
+impl MessageBuilder {
+    pub fn new(
+        chat_id: ChatId,
+        service: Arc<dyn services::transactions::Service>,
+        settings_service: Arc<dyn services::settings::Service>,
+        callback: UpdateTransaction,
+    ) -> Self {
+        Self {
+            chat_id,
+            service,
+            settings_service,
+            callback,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::MessageBuilder for MessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let transaction = self
+            .service
+            .get_transaction(self.chat_id.0, self.callback.transaction_id)
+            .await?;
+
+        Ok(match Field::try_from(self.callback.field)? {
+            Field::Amount => format!(
+                "💰 Изменение суммы
+
+Текущая сумма: {} {}
+
+Введите новую сумму (можно выражением, например 20 * 2).",
+                Amount(transaction.amount),
+                transaction.currency_code.as_symbol_str()
+            ),
+            Field::Description => format!(
+                "✏️ Изменение описания
+
+Текущее описание: {}
+
+Введите новое описание.",
+                transaction.description
+            ),
+            Field::Date => format!(
+                "📅 Изменение даты
+
+Текущая дата: {}
+
+Введите новую дату в формате ДД.ММ.ГГГГ или ДД.ММ.",
+                transaction
+                    .created_at
+                    .with_timezone(
+                        &self
+                            .settings_service
+                            .get_settings(self.chat_id.0)
+                            .await?
+                            .timezone
+                    )
+                    .format("%d.%m.%Y")
+            ),
+            Field::Currency => format!(
+                "💱 Выберите валюту для {} {}",
+                Amount(transaction.amount),
+                transaction.currency_code.as_symbol_str()
+            ),
+            Field::Category | Field::Unspecified => {
+                return Err(
+                    format!("unsupported transaction field {}", self.callback.field).into(),
+                );
+            }
+        })
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        if Field::try_from(self.callback.field)? != Field::Currency {
+            return Ok(InlineKeyboardMarkup::default().append_row(vec![
+                InlineKeyboardButton::callback(
+                    "❌ Отменить",
+                    String::try_from(Callback {
+                        query: Option::from(Query::CancelUpdateTransaction(
+                            CancelUpdateTransaction {},
+                        )),
+                    })
+                    .unwrap(),
+                ),
+            ]));
+        }
+
+        let row: Vec<InlineKeyboardButton> = CurrencyCode::ALL
+            .iter()
+            .map(|currency_code| {
+                InlineKeyboardButton::callback(
+                    format!(
+                        "{} {}",
+                        currency_code.as_symbol_str(),
+                        currency_code.as_code_str()
+                    ),
+                    String::try_from(Callback {
+                        query: Option::from(Query::ApproveTransactionCurrency(
+                            ApproveTransactionCurrency {
+                                transaction_id: self.callback.transaction_id,
+                                currency: i32::from(Currency::from(*currency_code)),
+                            },
+                        )),
+                    })
+                    .unwrap(),
+                )
+            })
+            .collect();
+
+        Ok(InlineKeyboardMarkup::default()
+            .append_row(row)
+            .append_row(vec![InlineKeyboardButton::callback(
+                "🔙 Назад",
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowTransaction(ShowTransaction {
+                        transaction_id: self.callback.transaction_id,
+                    })),
+                })
+                .unwrap(),
+            )]))
+    }
+}
+
+pub struct CancellableMessageBuilder {
+    callback: UpdateTransaction,
+}
+
+impl CancellableMessageBuilder {
+    pub fn new(callback: UpdateTransaction) -> Self {
+        CancellableMessageBuilder { callback }
+    }
+}
+
+#[async_trait::async_trait]
+impl callback::CancellableMessageBuilder for CancellableMessageBuilder {
+    async fn text(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        Ok("⚠️ Изменение операции отменено.".to_string())
+    }
+
+    async fn reply_markup(
+        &self,
+    ) -> Result<InlineKeyboardMarkup, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(
+            InlineKeyboardMarkup::default().append_row(vec![InlineKeyboardButton::callback(
+                "🔙 К операции",
+                String::try_from(Callback {
+                    query: Option::from(Query::ShowTransaction(ShowTransaction {
+                        transaction_id: self.callback.transaction_id,
+                    })),
+                })
+                .unwrap(),
+            )]),
+        )
+    }
+}
@@ -1,17 +1,28 @@
+use crate::dates;
+use crate::errors::Error;
 use crate::handlers::callback::MessageBuilder;
 use crate::handlers::transactions::message_builders;
-use crate::models::transaction::CurrencyCode;
-use crate::proto::callback::v1::{CategoryDirection, CreateTransaction};
+use crate::models::transaction::{CurrencyCode, Transaction};
+use crate::proto::callback::v1::update_transaction::Field;
+use crate::proto::callback::v1::{
+    ApproveTransactionCategory, CategoryDirection, CreateTransaction, ShowTransaction,
+};
 use crate::services;
-use crate::services::transactions::CreateTransactionRequest;
-use crate::telegram::{Dialog, HandlerResult};
+use crate::services::transactions::{CreateTransactionRequest, UpdateTransactionRequest};
+use crate::telegram;
+use crate::telegram::{Dialog, HandlerResult, State};
+use chrono::{Datelike, NaiveDate};
+use chrono_tz::Tz;
 use meval::Expr;
 use regex::Regex;
 use std::sync::Arc;
 use teloxide::Bot;
 use teloxide::dispatching::dialogue::GetChatId;
-use teloxide::payloads::SendMessageSetters;
-use teloxide::prelude::{Message, Requester};
+use teloxide::payloads::{
+    EditMessageReplyMarkupSetters, EditMessageTextSetters, SendMessageSetters,
+};
+use teloxide::prelude::{ChatId, Message, Requester};
+use teloxide::types::{InlineKeyboardMarkup, MessageId, ReplyParameters};
 
 static RE: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
     Regex::new(r"^(?P<expression>\d+(?:\s*[+\-*/().\s^\d]+)?)\s+(?P<description>.+)$")
@@ -21,8 +32,10 @@ static RE: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
 pub async fn create_transaction(
     bot: Bot,
     message: Message,
-    dialog: Dialog,
+    _dialog: Dialog,
     categories_service: Arc<dyn services::categories::Service>,
+    debts_service: Arc<dyn services::debts::Service>,
+    members_service: Arc<dyn services::members::Service>,
     transactions_service: Arc<dyn services::transactions::Service>,
 ) -> HandlerResult {
     let text = match message.text() {
@@ -37,81 +50,492 @@ pub async fn create_transaction(
         }
     };
 
-    let captures = match RE.captures(text) {
-        Some(captures) => captures,
-        None => {
-            bot.send_message(
-                message.chat.id,
-                "Неверный формат. Используйте формат: 200 шоколад или 20 * 2 хлеб",
-            )
-            .await?;
+    let (amount, description) = match parse_transaction(text) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            bot.send_message(message.chat.id, error).await?;
             return Ok(());
         }
     };
 
-    let expression_caps = match captures.name("expression") {
-        Some(expression_caps) => expression_caps.as_str().to_string(),
-        None => {
+    // In groups "3000 ужин @anna @oleg" splits the expense between the sender and the mentioned.
+    let (description, usernames) = if message.chat.is_private() {
+        (description, Vec::new())
+    } else {
+        parse_mentions(&description)
+    };
+
+    let Some(participants) =
+        resolve_participants(&bot, message.chat.id, usernames, &members_service).await?
+    else {
+        return Ok(());
+    };
+
+    let user_id = message.from.as_ref().map(telegram::user_id);
+    let transaction = transactions_service
+        .create_transaction(CreateTransactionRequest {
+            chat_id: message.chat.id.0,
+            user_id,
+            category_id: None,
+            amount,
+            description,
+            currency_code: CurrencyCode::RUB,
+            message_id: Some(message.id.0),
+            created_at: None,
+        })
+        .await?;
+
+    if let Some(user_id) = user_id
+        && !participants.is_empty()
+    {
+        debts_service
+            .split_transaction(transaction.id, user_id, participants)
+            .await?;
+    }
+
+    let builder = message_builders::create_transaction::MessageBuilder::new(
+        message.chat_id().unwrap(),
+        categories_service,
+        transactions_service.to_owned(),
+        CreateTransaction {
+            transaction_id: transaction.id,
+            category_direction: i32::from(CategoryDirection::Expense),
+            is_short_mode: true,
+            parent_id: 0,
+        },
+    );
+
+    let answer_message = bot
+        .send_message(message.chat.id, builder.text().await?)
+        .reply_markup(builder.reply_markup().await?)
+        .await?;
+
+    transactions_service
+        .update_transaction(UpdateTransactionRequest {
+            reply_message_id: Some(answer_message.id.0),
+            ..UpdateTransactionRequest::new(message.chat.id.0, transaction.id)
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Finds the members of the chat mentioned by their usernames. If someone is unknown, tells
+/// the chat and returns `None`.
+async fn resolve_participants(
+    bot: &Bot,
+    chat_id: ChatId,
+    usernames: Vec<String>,
+    members_service: &Arc<dyn services::members::Service>,
+) -> Result<Option<Vec<i64>>, Error> {
+    if usernames.is_empty() {
+        return Ok(Some(Vec::new()));
+    }
+
+    let members = members_service.select_members(chat_id.0).await?;
+    let mut participants = Vec::new();
+    for username in usernames {
+        let Some(member) = members.iter().find(|member| {
+            member
+                .username
+                .as_ref()
+                .is_some_and(|member_username| member_username.eq_ignore_ascii_case(&username))
+        }) else {
             bot.send_message(
-                message.chat.id,
-                "Неверный формат. Не найдена сумма или выражение",
+                chat_id,
+                format!(
+                    "Не знаю участника @{username}: пусть сначала напишет что-нибудь в этот чат."
+                ),
             )
             .await?;
+            return Ok(None);
+        };
+        participants.push(member.user_id);
+    }
+
+    Ok(Some(participants))
+}
+
+pub async fn edit_transaction(
+    bot: Bot,
+    message: Message,
+    categories_service: Arc<dyn services::categories::Service>,
+    debts_service: Arc<dyn services::debts::Service>,
+    members_service: Arc<dyn services::members::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+) -> HandlerResult {
+    let Some(text) = message.text() else {
+        return Ok(());
+    };
+
+    let Some(transaction) = transactions_service
+        .find_transaction_by_message_id(message.chat.id.0, message.id.0)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let (amount, description) = match parse_transaction(text) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            bot.send_message(message.chat.id, error)
+                .reply_parameters(ReplyParameters::new(message.id))
+                .await?;
             return Ok(());
         }
     };
-    let expr = match expression_caps.parse::<Expr>().ok() {
-        Some(expr) => expr,
-        None => {
-            bot.send_message(
-                message.chat.id,
-                "Неверный формат. Не удалось распознать сумму или выражение",
-            )
+    let (description, usernames) = if message.chat.is_private() {
+        (description, Vec::new())
+    } else {
+        parse_mentions(&description)
+    };
+    let Some(participants) =
+        resolve_participants(&bot, message.chat.id, usernames, &members_service).await?
+    else {
+        return Ok(());
+    };
+
+    // The split follows the mentions, so removing one from the message removes the debt.
+    if let Some(payer_id) = transaction.user_id {
+        debts_service
+            .split_transaction(transaction.id, payer_id, participants)
             .await?;
-            return Ok(());
+    }
+
+    let transaction = transactions_service
+        .update_transaction(UpdateTransactionRequest {
+            user_id: message.from.as_ref().map(telegram::user_id),
+            amount: Some(amount),
+            description: Some(description),
+            ..UpdateTransactionRequest::new(message.chat.id.0, transaction.id)
+        })
+        .await?;
+
+    refresh_reply_message(&bot, &transaction, categories_service, transactions_service).await
+}
+
+/// Re-renders the bot reply to the message a transaction was created from, if it is known:
+/// the category picker for uncategorized transactions and the summary otherwise.
+pub async fn refresh_reply_message(
+    bot: &Bot,
+    transaction: &Transaction,
+    categories_service: Arc<dyn services::categories::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+) -> HandlerResult {
+    let Some(reply_message_id) = transaction.reply_message_id else {
+        return Ok(());
+    };
+    let chat_id = ChatId(transaction.chat_id);
+
+    let (text, keyboard) = match transaction.category_id {
+        Some(category_id) => {
+            let builder = message_builders::approve_transaction_category::MessageBuilder::new(
+                chat_id,
+                categories_service,
+                transactions_service,
+                ApproveTransactionCategory {
+                    transaction_id: transaction.id,
+                    category_id,
+                },
+            );
+            (builder.text().await?, builder.reply_markup().await?)
+        }
+        None => {
+            let builder = message_builders::create_transaction::MessageBuilder::new(
+                chat_id,
+                categories_service,
+                transactions_service,
+                CreateTransaction {
+                    transaction_id: transaction.id,
+                    category_direction: i32::from(CategoryDirection::Expense),
+                    is_short_mode: true,
+                    parent_id: 0,
+                },
+            );
+            (builder.text().await?, builder.reply_markup().await?)
         }
     };
-    let amount = match expr.eval() {
-        Ok(amount) => amount,
-        Err(_) => {
+
+    bot.edit_message_text(chat_id, MessageId(reply_message_id), text)
+        .reply_markup(keyboard)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn update_transaction(
+    bot: Bot,
+    message: Message,
+    dialog: Dialog,
+    categories_service: Arc<dyn services::categories::Service>,
+    settings_service: Arc<dyn services::settings::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+) -> HandlerResult {
+    let text = match message.text() {
+        Some(text) => text.trim(),
+        None => {
             bot.send_message(
                 message.chat.id,
-                "Неверный формат. Не удалось вычислить сумму или выражение",
+                "Пожалуйста, отправьте текстовое сообщение.",
             )
             .await?;
             return Ok(());
         }
     };
 
-    let description = captures
-        .name("description")
-        .map(|m| m.as_str().trim().to_string())
-        .unwrap_or_default();
+    let Some(State::UpdateTransaction {
+        answer_message_id,
+        callback,
+    }) = dialog.get().await?
+    else {
+        bot.send_message(
+            message.chat.id,
+            "Сессия устарела. Пожалуйста, начните изменение операции заново.",
+        )
+        .await?;
 
-    let transaction = transactions_service
-        .create_transaction(CreateTransactionRequest {
-            chat_id: message.chat.id.0,
-            category_id: None,
-            amount: (amount * 100.0) as i64,
-            description,
-            currency_code: CurrencyCode::RUB,
-        })
+        return Ok(());
+    };
+
+    let mut request = UpdateTransactionRequest {
+        user_id: message.from.as_ref().map(telegram::user_id),
+        ..UpdateTransactionRequest::new(message.chat.id.0, callback.transaction_id)
+    };
+
+    match Field::try_from(callback.field)? {
+        Field::Amount => match evaluate_amount(text) {
+            Some(amount) => request.amount = Some(amount),
+            None => {
+                bot.send_message(
+                    message.chat.id,
+                    "Неверный формат. Введите сумму или выражение, например: 200 или 20 * 2",
+                )
+                .await?;
+                return Ok(());
+            }
+        },
+        Field::Description => {
+            if text.is_empty() {
+                bot.send_message(message.chat.id, "Описание не может быть пустым.")
+                    .await?;
+                return Ok(());
+            }
+            request.description = Some(text.to_string());
+        }
+        Field::Date => {
+            let transaction = transactions_service
+                .get_transaction(message.chat.id.0, callback.transaction_id)
+                .await?;
+            let timezone = settings_service
+                .get_settings(message.chat.id.0)
+                .await?
+                .timezone;
+            match parse_date(timezone, text) {
+                // The time of day is kept, so only the local date changes.
+                Some(date) => {
+                    let time = transaction.created_at.with_timezone(&timezone).time();
+                    request.created_at = Some(dates::to_utc(timezone, date.and_time(time)))
+                }
+                None => {
+                    bot.send_message(
+                        message.chat.id,
+                        "Неверный формат даты. Используйте формат: 05.12.2025 или 05.12",
+                    )
+                    .await?;
+                    return Ok(());
+                }
+            }
+        }
+        Field::Category | Field::Currency | Field::Unspecified => {
+            dialog.reset().await?;
+            return Ok(());
+        }
+    }
+
+    transactions_service.update_transaction(request).await?;
+
+    dialog.reset().await?;
+
+    bot.edit_message_reply_markup(message.chat.id, answer_message_id)
+        .reply_markup(InlineKeyboardMarkup::default())
         .await?;
 
-    let builder = message_builders::create_transaction::MessageBuilder::new(
-        message.chat_id().unwrap(),
+    let builder = message_builders::show_transaction::MessageBuilder::new(
+        message.chat.id,
         categories_service,
+        settings_service,
         transactions_service,
+        ShowTransaction {
+            transaction_id: callback.transaction_id,
+        },
+    );
+
+    bot.send_message(message.chat.id, builder.text().await?)
+        .reply_markup(builder.reply_markup().await?)
+        .await?;
+
+    Ok(())
+}
+
+/// Sends the category picker for a transaction once more, replying to the message it was
+/// created from if there is one. Edits of that message then refresh the new picker.
+pub async fn send_category_picker(
+    bot: &Bot,
+    transaction: &Transaction,
+    categories_service: Arc<dyn services::categories::Service>,
+    transactions_service: Arc<dyn services::transactions::Service>,
+) -> HandlerResult {
+    let chat_id = ChatId(transaction.chat_id);
+    let builder = message_builders::create_transaction::MessageBuilder::new(
+        chat_id,
+        categories_service,
+        transactions_service.to_owned(),
         CreateTransaction {
             transaction_id: transaction.id,
             category_direction: i32::from(CategoryDirection::Expense),
             is_short_mode: true,
+            parent_id: 0,
         },
     );
 
-    bot.send_message(message.chat.id, builder.text().await?)
-        .reply_markup(builder.reply_markup().await?)
+    let mut text = builder.text().await?;
+    if !transaction.description.is_empty() {
+        text = format!("❔ {}\n{text}", transaction.description);
+    }
+
+    let mut request = bot
+        .send_message(chat_id, text)
+        .reply_markup(builder.reply_markup().await?);
+    if let Some(message_id) = transaction.message_id {
+        request = request.reply_parameters(
+            ReplyParameters::new(MessageId(message_id)).allow_sending_without_reply(),
+        );
+    }
+    let answer_message = request.await?;
+
+    transactions_service
+        .update_transaction(UpdateTransactionRequest {
+            reply_message_id: Some(answer_message.id.0),
+            ..UpdateTransactionRequest::new(transaction.chat_id, transaction.id)
+        })
         .await?;
 
     Ok(())
 }
+
+/// Parses `350 такси` or `20 * 2 хлеб` into an amount in minor units and a description.
+fn parse_transaction(text: &str) -> Result<(i64, String), &'static str> {
+    let captures = RE
+        .captures(text)
+        .ok_or("Неверный формат. Используйте формат: 200 шоколад или 20 * 2 хлеб")?;
+
+    let expression = captures
+        .name("expression")
+        .ok_or("Неверный формат. Не найдена сумма или выражение")?
+        .as_str();
+    let expr = expression
+        .parse::<Expr>()
+        .map_err(|_| "Неверный формат. Не удалось распознать сумму или выражение")?;
+    let amount = expr
+        .eval()
+        .map_err(|_| "Неверный формат. Не удалось вычислить сумму или выражение")?;
+
+    let description = captures
+        .name("description")
+        .map(|m| m.as_str().trim().to_string())
+        .unwrap_or_default();
+
+    Ok(((amount * 100.0).round() as i64, description))
+}
+
+/// Separates `@username` mentions from the rest of the description.
+fn parse_mentions(description: &str) -> (String, Vec<String>) {
+    let mut words = Vec::new();
+    let mut usernames = Vec::new();
+
+    for word in description.split_whitespace() {
+        match word.strip_prefix('@').filter(|username| !username.is_empty()) {
+            Some(username) => usernames.push(username.to_string()),
+            None => words.push(word),
+        }
+    }
+
+    (words.join(" "), usernames)
+}
+
+fn evaluate_amount(expression: &str) -> Option<i64> {
+    let amount = expression.parse::<Expr>().ok()?.eval().ok()?;
+    if !amount.is_finite() || amount <= 0.0 {
+        return None;
+    }
+
+    Some((amount * 100.0).round() as i64)
+}
+
+/// Parses `05.12.2025`, or `05.12` of the current year in the timezone.
+pub fn parse_date(timezone: Tz, text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%d.%m.%Y")
+        .ok()
+        .or_else(|| {
+            let year = dates::today(timezone).year();
+            NaiveDate::parse_from_str(&format!("{text}.{year}"), "%d.%m.%Y").ok()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mentions() {
+        assert_eq!(
+            parse_mentions("ужин @anna @oleg"),
+            (
+                "ужин".to_string(),
+                vec!["anna".to_string(), "oleg".to_string()]
+            )
+        );
+        assert_eq!(
+            parse_mentions("кофе @ вдвоём"),
+            ("кофе @ вдвоём".to_string(), vec![])
+        );
+    }
+
+    #[test]
+    fn test_parse_transaction() {
+        assert_eq!(
+            parse_transaction("350 такси"),
+            Ok((35000, "такси".to_string()))
+        );
+        assert_eq!(
+            parse_transaction("20 * 2 хлеб"),
+            Ok((4000, "хлеб".to_string()))
+        );
+        assert_eq!(
+            parse_transaction("19.99 кофе"),
+            Ok((1999, "кофе".to_string()))
+        );
+        assert!(parse_transaction("такси").is_err());
+    }
+
+    #[test]
+    fn test_evaluate_amount() {
+        assert_eq!(evaluate_amount("350"), Some(35000));
+        assert_eq!(evaluate_amount("20 * 2.5"), Some(5000));
+        assert_eq!(evaluate_amount("0"), None);
+        assert_eq!(evaluate_amount("такси"), None);
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            parse_date(Tz::UTC, "05.12.2025"),
+            NaiveDate::from_ymd_opt(2025, 12, 5)
+        );
+        assert_eq!(
+            parse_date(Tz::UTC, "05.12"),
+            NaiveDate::from_ymd_opt(dates::today(Tz::UTC).year(), 12, 5)
+        );
+        assert_eq!(parse_date(Tz::UTC, "32.12.2025"), None);
+    }
+}
@@ -1,3 +1,5 @@
 pub mod message_handlers;
 mod message_builders;
 pub mod callback_handlers;
+pub mod command_handlers;
+pub mod jobs;
\ No newline at end of file
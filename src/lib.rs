@@ -6,6 +6,8 @@ pub mod proto {
     }
 }
 
+pub mod dates;
+pub mod errors;
 pub mod handlers;
 pub mod models;
 pub mod repositories;
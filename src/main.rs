@@ -30,26 +30,161 @@ async fn main() {
         .await
         .unwrap();
 
+    let actions_repository = repositories::actions::Actions::new(pool.clone());
+
+    let settings_repository = repositories::settings::Settings::new(pool.clone());
+    let settings_service = services::settings::Settings::new(settings_repository.clone());
+
     let categories_repository = repositories::categories::Categories::new(pool.clone());
-    let categories_service = services::categories::Categories::new(categories_repository);
+    let categories_service = services::categories::Categories::new(
+        categories_repository.clone(),
+        actions_repository.clone(),
+        settings_repository.clone(),
+    );
 
     let transactions_repository = repositories::transactions::Transactions::new(pool.clone());
-    let transactions_service = services::transactions::Transactions::new(transactions_repository);
+    let transactions_service = services::transactions::Transactions::new(
+        transactions_repository.clone(),
+        actions_repository.clone(),
+    );
+
+    let members_repository = repositories::members::Members::new(pool.clone());
+    let members_service = services::members::Members::new(members_repository.clone());
+
+    let debts_repository = repositories::debts::Debts::new(pool.clone());
+    let debts_service = services::debts::Debts::new(debts_repository);
+
+    let exports_service = services::exports::Exports::new(
+        settings_repository.clone(),
+        transactions_repository.clone(),
+        categories_repository.clone(),
+        members_repository.clone(),
+    );
+
+    let backups_service =
+        services::backups::Backups::new(repositories::backups::Backups::new(pool.clone()));
+
+    let reports_service = services::reports::Reports::new(
+        settings_repository.clone(),
+        transactions_repository.clone(),
+        categories_repository.clone(),
+    );
+
+    let digests_service = services::digests::Digests::new(
+        settings_repository,
+        transactions_repository.clone(),
+        categories_repository.clone(),
+    );
+
+    let actions_service = services::actions::Actions::new(
+        actions_repository,
+        transactions_repository,
+        categories_repository,
+    );
 
     log::info!("Starting budgetgram bot...");
 
     let bot = Bot::from_env();
+
+    tokio::spawn(handlers::digests::jobs::send_digests(
+        bot.clone(),
+        digests_service,
+    ));
+    tokio::spawn(handlers::members::jobs::sync_owners(
+        bot.clone(),
+        members_service.clone(),
+    ));
+    tokio::spawn(handlers::transactions::jobs::remind_uncategorized(
+        bot.clone(),
+        categories_service.clone(),
+        transactions_service.clone(),
+    ));
+
     Dispatcher::builder(
-        bot,
-        dptree::entry()
+        bot.clone(),
+        handlers::errors::with_update(dptree::entry())
             .enter_dialogue::<Update, InMemStorage<State>, State>()
             .branch(
                 Update::filter_message()
+                    .inspect_async(handlers::members::message_handlers::remember_member)
+                    .branch(
+                        dptree::entry()
+                            .filter_command::<Command>()
+                            .branch(
+                                dptree::case![Command::Report]
+                                    .endpoint(handlers::reports::command_handlers::show_report),
+                            )
+                            .branch(
+                                dptree::case![Command::Chart]
+                                    .endpoint(handlers::charts::command_handlers::show_chart),
+                            )
+                            .branch(
+                                dptree::case![Command::Trend]
+                                    .endpoint(handlers::charts::command_handlers::show_trend),
+                            ),
+                    )
                     .branch(
-                        dptree::entry().filter_command::<Command>().branch(
-                            dptree::case![Command::Settings]
-                                .endpoint(settings::command_handlers::show_settings),
-                        ),
+                        dptree::filter_async(handlers::members::message_handlers::is_read_only)
+                            .endpoint(handlers::members::message_handlers::deny_read_only),
+                    )
+                    .branch(
+                        dptree::entry()
+                            .filter_command::<Command>()
+                            .branch(
+                                dptree::case![Command::Start]
+                                    .endpoint(handlers::categories::command_handlers::start),
+                            )
+                            .branch(
+                                dptree::case![Command::Settings]
+                                    .endpoint(settings::command_handlers::show_settings),
+                            )
+                            .branch(
+                                dptree::case![Command::Timezone(timezone)]
+                                    .endpoint(settings::command_handlers::set_timezone),
+                            )
+                            .branch(
+                                dptree::case![Command::Undo]
+                                    .endpoint(handlers::actions::command_handlers::undo),
+                            )
+                            .branch(
+                                dptree::case![Command::Log].endpoint(
+                                    handlers::actions::command_handlers::show_action_log,
+                                ),
+                            )
+                            .branch(
+                                dptree::case![Command::History].endpoint(
+                                    handlers::transactions::command_handlers::show_history,
+                                ),
+                            )
+                            .branch(
+                                dptree::case![Command::Uncategorized].endpoint(
+                                    handlers::transactions::command_handlers::show_uncategorized,
+                                ),
+                            )
+                            .branch(
+                                dptree::case![Command::Debts]
+                                    .endpoint(handlers::debts::command_handlers::show_debts),
+                            )
+                            .branch(
+                                dptree::case![Command::Export(period)]
+                                    .endpoint(handlers::exports::command_handlers::export),
+                            )
+                            .branch(
+                                dptree::case![Command::Backup]
+                                    .endpoint(handlers::backups::command_handlers::backup),
+                            )
+                            .branch(
+                                dptree::case![Command::Restore]
+                                    .endpoint(handlers::backups::command_handlers::restore),
+                            ),
+                    )
+                    .branch(
+                        dptree::case![State::RestoreBackup]
+                            .endpoint(handlers::backups::message_handlers::restore_backup),
+                    )
+                    .branch(
+                        dptree::filter(|message: Message| message.document().is_some())
+                            .endpoint(handlers::imports::message_handlers::import_transactions),
                     )
                     .branch(
                         dptree::case![State::CreateCategory {
@@ -58,18 +193,38 @@ async fn main() {
                         }]
                         .endpoint(handlers::categories::message_handlers::create_category),
                     )
+                    .branch(
+                        dptree::case![State::UpdateTransaction {
+                            answer_message_id,
+                            callback
+                        }]
+                        .endpoint(handlers::transactions::message_handlers::update_transaction),
+                    )
                     .branch(
                         dptree::case![State::None]
                         .endpoint(handlers::transactions::message_handlers::create_transaction),
                     ),
             )
+            .branch(
+                Update::filter_edited_message()
+                    .filter_async(handlers::members::message_handlers::can_edit)
+                    .endpoint(handlers::transactions::message_handlers::edit_transaction),
+            )
             .branch(Update::filter_callback_query().endpoint(match_callback_query)),
     )
     .dependencies(dptree::deps![
         InMemStorage::<State>::new(),
+        actions_service as Arc<dyn services::actions::Service>,
+        backups_service as Arc<dyn services::backups::Service>,
         categories_service as Arc<dyn services::categories::Service>,
+        debts_service as Arc<dyn services::debts::Service>,
+        exports_service as Arc<dyn services::exports::Service>,
+        members_service as Arc<dyn services::members::Service>,
+        reports_service as Arc<dyn services::reports::Service>,
+        settings_service as Arc<dyn services::settings::Service>,
         transactions_service as Arc<dyn services::transactions::Service>
     ])
+    .error_handler(handlers::errors::ErrorHandler::new(bot))
     .enable_ctrlc_handler()
     .build()
     .dispatch()
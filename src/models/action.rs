@@ -0,0 +1,135 @@
+use crate::models::category::Category;
+use crate::models::transaction::Transaction;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionKind {
+    TransactionCreated = 1,
+    TransactionUpdated = 2,
+    TransactionDeleted = 3,
+    CategoryCreated = 4,
+    CategoryUpdated = 5,
+    CategoryDeleted = 6,
+    CategoryRestored = 7,
+    /// Several transactions with the same description were put into a category at once.
+    TransactionsCategorized = 8,
+    /// Transactions created by a single import.
+    TransactionsImported = 9,
+    /// Categories created at once, e.g. from a preset pack.
+    CategoriesCreated = 10,
+    /// A category merged into another one together with its transactions and subcategories.
+    CategoryMerged = 11,
+}
+
+impl From<ActionKind> for i32 {
+    fn from(value: ActionKind) -> Self {
+        value as i32
+    }
+}
+
+impl TryFrom<i32> for ActionKind {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(ActionKind::TransactionCreated),
+            2 => Ok(ActionKind::TransactionUpdated),
+            3 => Ok(ActionKind::TransactionDeleted),
+            4 => Ok(ActionKind::CategoryCreated),
+            5 => Ok(ActionKind::CategoryUpdated),
+            6 => Ok(ActionKind::CategoryDeleted),
+            7 => Ok(ActionKind::CategoryRestored),
+            8 => Ok(ActionKind::TransactionsCategorized),
+            9 => Ok(ActionKind::TransactionsImported),
+            10 => Ok(ActionKind::CategoriesCreated),
+            11 => Ok(ActionKind::CategoryMerged),
+            _ => Err(format!("unknown action kind: {value}")),
+        }
+    }
+}
+
+/// State of an affected entity, stored before and after every change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Snapshot {
+    Transaction(Transaction),
+    Category(Category),
+    /// Transactions changed by a single bulk action.
+    Transactions(Vec<Transaction>),
+    /// Categories changed by a single bulk action.
+    Categories(Vec<Category>),
+    /// Both categories of a merge and the ids of what was moved from the source to the target.
+    Merge {
+        source: Category,
+        target: Category,
+        subcategory_ids: Vec<i64>,
+        transaction_ids: Vec<i64>,
+    },
+}
+
+impl Snapshot {
+    fn chat_id_and_entity_id(&self) -> (i64, i64) {
+        match self {
+            Snapshot::Transaction(transaction) => (transaction.chat_id, transaction.id),
+            Snapshot::Category(category) => (category.chat_id, category.id),
+            Snapshot::Transactions(transactions) => transactions
+                .first()
+                .map(|transaction| (transaction.chat_id, transaction.id))
+                .unwrap_or_default(),
+            Snapshot::Categories(categories) => categories
+                .first()
+                .map(|category| (category.chat_id, category.id))
+                .unwrap_or_default(),
+            Snapshot::Merge { source, .. } => (source.chat_id, source.id),
+        }
+    }
+}
+
+/// A single change made in a chat. `before` is empty for creations and `after` for deletions.
+#[derive(Debug, Clone)]
+pub struct Action {
+    pub id: i64,
+    pub chat_id: i64,
+    pub user_id: Option<i64>,
+    pub kind: ActionKind,
+    pub entity_id: i64,
+    pub before: Option<Snapshot>,
+    pub after: Option<Snapshot>,
+    pub created_at: DateTime<Utc>,
+    pub reverted_at: Option<DateTime<Utc>>,
+    pub reverted_by: Option<i64>,
+}
+
+impl Action {
+    pub fn new(
+        kind: ActionKind,
+        user_id: Option<i64>,
+        before: Option<Snapshot>,
+        after: Option<Snapshot>,
+    ) -> Self {
+        let (chat_id, entity_id) = after
+            .as_ref()
+            .or(before.as_ref())
+            .map(Snapshot::chat_id_and_entity_id)
+            .unwrap_or_default();
+
+        Action {
+            id: -1,
+            chat_id,
+            user_id,
+            kind,
+            entity_id,
+            before,
+            after,
+            created_at: Utc::now(),
+            reverted_at: None,
+            reverted_by: None,
+        }
+    }
+
+    /// The latest known state of the affected entity.
+    pub fn snapshot(&self) -> Option<&Snapshot> {
+        self.after.as_ref().or(self.before.as_ref())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::models::category::Category;
+use crate::models::debt::Settlement;
+use crate::models::member::Member;
+use crate::models::settings::ChatSettings;
+use crate::models::transaction::Transaction;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Format of the backups written by this version; bump it whenever the layout changes.
+pub const BACKUP_VERSION: u32 = 2;
+/// The oldest format that can still be restored; version 1 had no settings.
+pub const MIN_BACKUP_VERSION: u32 = 1;
+
+/// Everything a chat keeps in the bot. Ids are those of the source chat and are
+/// remapped on restore.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backup {
+    pub version: u32,
+    pub chat_id: i64,
+    pub created_at: DateTime<Utc>,
+    pub categories: Vec<Category>,
+    pub transactions: Vec<Transaction>,
+    pub participants: Vec<Participant>,
+    pub settlements: Vec<Settlement>,
+    pub members: Vec<Member>,
+    /// Missing if the chat has never changed its settings.
+    #[serde(default)]
+    pub settings: Option<ChatSettings>,
+}
+
+/// A member sharing a split transaction.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Participant {
+    pub transaction_id: i64,
+    pub user_id: i64,
+}
@@ -76,6 +76,26 @@ const INCOME_FORMS: [[&str; 6]; 2] = [
     ],
 ];
 
+/// Used when the direction is not known, e.g. for a broken callback.
+const OPERATION_FORMS: [[&str; 6]; 2] = [
+    [
+        "операция",
+        "операции",
+        "операции",
+        "операцию",
+        "операцией",
+        "операции",
+    ],
+    [
+        "операции",
+        "операций",
+        "операциям",
+        "операции",
+        "операциями",
+        "операциях",
+    ],
+];
+
 impl NameCase {
     fn as_index(&self) -> usize {
         match self {
@@ -103,7 +123,7 @@ impl Declinable for CategoryDirection {
         match self {
             CategoryDirection::Expense => EXPENSE_FORMS[number.as_index()][case.as_index()],
             CategoryDirection::Income => INCOME_FORMS[number.as_index()][case.as_index()],
-            CategoryDirection::Unspecified => unreachable!(),
+            CategoryDirection::Unspecified => OPERATION_FORMS[number.as_index()][case.as_index()],
         }
     }
 }
@@ -113,7 +133,7 @@ impl Labeled for CategoryDirection {
         match self {
             CategoryDirection::Expense => "📉",
             CategoryDirection::Income => "📈",
-            CategoryDirection::Unspecified => unreachable!(),
+            CategoryDirection::Unspecified => "💰",
         }
     }
 }
@@ -134,7 +154,7 @@ mod tests {
             })),
         };
         let str = String::try_from(callback).unwrap();
-        assert_eq!(str.as_str(), "\u{15}\u{1}\u{1}\u{2}\u{8}@\u{4}");
+        assert_eq!(str.as_str(), "\u{19}\u{1}\u{1}\u{2}\u{8}@\u{4}");
         assert_eq!(Callback::try_from(str).unwrap(), callback);
     }
 }
@@ -1,16 +1,171 @@
 use crate::proto::callback::v1::CategoryDirection;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use unicode_properties::UnicodeEmoji;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Category {
     pub id: i64,
     pub chat_id: i64,
     pub name: String,
     pub label: String,
+    #[serde(with = "direction")]
     pub direction: CategoryDirection,
     pub is_regular: bool,
     pub target_amount: Option<i64>,
+    /// Set for subcategories. Only one level of nesting is allowed.
+    #[serde(default)]
+    pub parent_id: Option<i64>,
+    /// Order among the categories of the same direction, smaller first.
+    #[serde(default)]
+    pub position: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
+
+mod direction {
+    use crate::proto::callback::v1::CategoryDirection;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        direction: &CategoryDirection,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(i32::from(*direction))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CategoryDirection, D::Error> {
+        Ok(CategoryDirection::try_from(i32::deserialize(deserializer)?)
+            .unwrap_or(CategoryDirection::Unspecified))
+    }
+}
+
+/// Categories shown at the top level: those without a parent and subcategories whose
+/// parent is not among `categories`, e.g. because it was deleted.
+pub fn top_level(categories: &[Category]) -> Vec<&Category> {
+    categories
+        .iter()
+        .filter(|category| {
+            category
+                .parent_id
+                .is_none_or(|parent_id| !categories.iter().any(|parent| parent.id == parent_id))
+        })
+        .collect()
+}
+
+/// Categories listed next to the category: subcategories of the same parent or the
+/// top-level ones.
+pub fn siblings<'a>(categories: &'a [Category], category: &Category) -> Vec<&'a Category> {
+    match category
+        .parent_id
+        .filter(|parent_id| categories.iter().any(|parent| parent.id == *parent_id))
+    {
+        Some(parent_id) => subcategories(categories, parent_id),
+        None => top_level(categories),
+    }
+}
+
+pub fn subcategories(categories: &[Category], parent_id: i64) -> Vec<&Category> {
+    categories
+        .iter()
+        .filter(|category| category.parent_id == Some(parent_id))
+        .collect()
+}
+
+/// Longest category name, so that two of them fit a row of buttons.
+pub const MAX_NAME_LENGTH: usize = 32;
+
+/// Whether the label is a single emoji. Emoji made of several code points, such as flags,
+/// keycaps, skin tones and ZWJ sequences, count as one.
+pub fn is_valid_label(label: &str) -> bool {
+    let mut chars = label.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    let rest = chars.as_str();
+
+    if matches!(first, '0'..='9' | '#' | '*') {
+        return matches!(rest, "\u{FE0F}\u{20E3}" | "\u{20E3}");
+    }
+    if is_regional_indicator(first) {
+        // Flags are pairs of regional indicators.
+        let mut rest = rest.chars();
+        return rest.next().is_some_and(is_regional_indicator) && rest.next().is_none();
+    }
+    if !is_emoji(first) {
+        return false;
+    }
+
+    let mut rest = rest.chars();
+    while let Some(c) = rest.next() {
+        if c == '\u{200D}' {
+            if !rest.next().is_some_and(is_emoji) {
+                return false;
+            }
+        } else if !is_modifier(c) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Whether the character is an emoji on its own, unlike punctuation, currency signs and the
+/// parts of emoji sequences.
+fn is_emoji(c: char) -> bool {
+    c.is_emoji_char() && !c.is_emoji_component()
+}
+
+fn is_modifier(c: char) -> bool {
+    matches!(
+        c,
+        '\u{FE0E}' | '\u{FE0F}' | '\u{20E3}' | '\u{1F3FB}'..='\u{1F3FF}' | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_single_emoji() {
+        let labels = ["🛒", "🗄️", "👍🏽", "👨‍👩‍👧", "🇷🇺", "1️⃣", "🏴󠁧󠁢󠁳󠁣󠁴󠁿", "©️"];
+        for label in labels {
+            assert!(is_valid_label(label), "{label}");
+        }
+    }
+
+    #[test]
+    fn rejects_punctuation_and_symbols() {
+        let labels = [".", "$", "€", "-", "→", "№", "%", "🛒\u{200D}."];
+        for label in labels {
+            assert!(!is_valid_label(label), "{label:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_text_and_several_emoji() {
+        let labels = [
+            "",
+            " ",
+            "А",
+            "ab",
+            "1",
+            "🛒🛒",
+            "🛒 ",
+            "🇷",
+            "\u{FE0F}",
+            "👍\u{200D}",
+        ];
+        for label in labels {
+            assert!(!is_valid_label(label), "{label:?}");
+        }
+    }
+}
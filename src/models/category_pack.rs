@@ -0,0 +1,115 @@
+use crate::proto::callback::v1::{CategoryDirection, CategoryPack};
+
+/// A category created from a pack.
+#[derive(Debug, Clone, Copy)]
+pub struct CategoryTemplate {
+    pub label: &'static str,
+    pub name: &'static str,
+    pub direction: CategoryDirection,
+    pub is_regular: bool,
+}
+
+const fn expense(label: &'static str, name: &'static str, is_regular: bool) -> CategoryTemplate {
+    CategoryTemplate {
+        label,
+        name,
+        direction: CategoryDirection::Expense,
+        is_regular,
+    }
+}
+
+const fn income(label: &'static str, name: &'static str, is_regular: bool) -> CategoryTemplate {
+    CategoryTemplate {
+        label,
+        name,
+        direction: CategoryDirection::Income,
+        is_regular,
+    }
+}
+
+pub const PACKS: [CategoryPack; 3] = [
+    CategoryPack::Household,
+    CategoryPack::Student,
+    CategoryPack::Freelancer,
+];
+
+const HOUSEHOLD: &[CategoryTemplate] = &[
+    expense("🛒", "Продукты", true),
+    expense("🏠", "Жильё", true),
+    expense("💡", "Коммунальные услуги", true),
+    expense("🚌", "Транспорт", true),
+    expense("💊", "Здоровье", false),
+    expense("👕", "Одежда", false),
+    expense("🍽", "Кафе и рестораны", false),
+    expense("🎉", "Развлечения", false),
+    income("💼", "Зарплата", true),
+    income("🎁", "Подарки", false),
+];
+
+const STUDENT: &[CategoryTemplate] = &[
+    expense("🛒", "Продукты", true),
+    expense("🏠", "Аренда и общежитие", true),
+    expense("🍽", "Кафе и столовые", false),
+    expense("🚌", "Транспорт", true),
+    expense("📚", "Учёба", false),
+    expense("📱", "Связь и подписки", true),
+    expense("🎉", "Развлечения", false),
+    income("🎓", "Стипендия", true),
+    income("👪", "Помощь родителей", false),
+    income("💼", "Подработка", false),
+];
+
+const FREELANCER: &[CategoryTemplate] = &[
+    expense("🛒", "Продукты", true),
+    expense("🏠", "Жильё", true),
+    expense("💻", "Техника и софт", false),
+    expense("🧾", "Налоги", true),
+    expense("🏦", "Банковские комиссии", false),
+    expense("📣", "Реклама", false),
+    expense("🚌", "Транспорт", true),
+    expense("🎉", "Развлечения", false),
+    income("💰", "Проекты", false),
+    income("🔁", "Постоянные клиенты", true),
+];
+
+pub fn title(pack: CategoryPack) -> &'static str {
+    match pack {
+        CategoryPack::Household => "🏠 Для дома",
+        CategoryPack::Student => "🎓 Для студента",
+        CategoryPack::Freelancer => "💼 Для фрилансера",
+        CategoryPack::Unspecified => "📦 Набор",
+    }
+}
+
+pub fn templates(pack: CategoryPack) -> &'static [CategoryTemplate] {
+    match pack {
+        CategoryPack::Household => HOUSEHOLD,
+        CategoryPack::Student => STUDENT,
+        CategoryPack::Freelancer => FREELANCER,
+        CategoryPack::Unspecified => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn packs_have_both_directions_and_unique_names() {
+        for pack in PACKS {
+            let templates = templates(pack);
+            let names: HashSet<&str> = templates.iter().map(|template| template.name).collect();
+
+            assert_eq!(names.len(), templates.len(), "{pack:?}");
+            for direction in [CategoryDirection::Expense, CategoryDirection::Income] {
+                assert!(
+                    templates
+                        .iter()
+                        .any(|template| template.direction == direction),
+                    "{pack:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::models::transaction::CurrencyCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// An expense paid by one member and split equally between the participants, the payer included.
+#[derive(Debug, Clone)]
+pub struct Split {
+    pub transaction_id: i64,
+    pub payer_id: i64,
+    pub amount: i64,
+    pub currency_code: CurrencyCode,
+    pub participants: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settlement {
+    pub id: i64,
+    pub chat_id: i64,
+    pub from_user_id: i64,
+    pub to_user_id: i64,
+    pub amount: i64,
+    pub currency_code: CurrencyCode,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A payment that settles a debt between two members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transfer {
+    pub from_user_id: i64,
+    pub to_user_id: i64,
+    pub amount: i64,
+    pub currency_code: CurrencyCode,
+}
@@ -0,0 +1,18 @@
+use crate::models::report::{CategoryTotal, Totals};
+use crate::proto::callback::v1::DigestFrequency;
+
+/// A short summary of recent spending sent to the chat on schedule.
+#[derive(Debug, Clone)]
+pub struct Digest {
+    pub frequency: DigestFrequency,
+    /// Expenses of yesterday for daily digests and of the past week for weekly ones.
+    pub expenses: Totals,
+    /// Expenses since the start of the week the digest covers.
+    pub week_expenses: Totals,
+    /// The weekly share of the monthly expense limits in rubles, `None` without limits.
+    pub week_budget: Option<i64>,
+    /// Largest expense categories of the period.
+    pub top_categories: Vec<CategoryTotal>,
+    /// Transactions still waiting for a category.
+    pub uncategorized: i64,
+}
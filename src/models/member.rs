@@ -0,0 +1,66 @@
+use crate::proto::callback::v1::MemberRole;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Member {
+    pub chat_id: i64,
+    pub user_id: i64,
+    pub username: Option<String>,
+    pub full_name: String,
+    #[serde(with = "role")]
+    pub role: MemberRole,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+mod role {
+    use crate::proto::callback::v1::MemberRole;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(role: &MemberRole, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(i32::from(*role))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<MemberRole, D::Error> {
+        Ok(MemberRole::try_from(i32::deserialize(deserializer)?).unwrap_or(MemberRole::Viewer))
+    }
+}
+
+/// Whether `role` grants at least the rights of `required`; more privileged roles have lower values.
+pub fn has_role(role: MemberRole, required: MemberRole) -> bool {
+    role != MemberRole::Unspecified && role <= required
+}
+
+/// Role of a user given their membership: private chats belong to their only user, and users
+/// the bot has not seen post yet may only look.
+pub fn role_of(chat_id: i64, user_id: i64, member: Option<&Member>) -> MemberRole {
+    if chat_id == user_id {
+        return MemberRole::Owner;
+    }
+
+    member.map_or(MemberRole::Viewer, |member| member.role)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn owner_has_every_role() {
+        assert!(has_role(MemberRole::Owner, MemberRole::Owner));
+        assert!(has_role(MemberRole::Owner, MemberRole::Viewer));
+    }
+
+    #[test]
+    fn viewer_cannot_edit() {
+        assert!(!has_role(MemberRole::Viewer, MemberRole::Editor));
+        assert!(!has_role(MemberRole::Unspecified, MemberRole::Viewer));
+    }
+
+    #[test]
+    fn unknown_user_is_viewer() {
+        assert_eq!(role_of(-100, 1, None), MemberRole::Viewer);
+        assert_eq!(role_of(1, 1, None), MemberRole::Owner);
+    }
+}
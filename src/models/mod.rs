@@ -1,3 +1,11 @@
+pub mod action;
+pub mod backup;
 pub mod callback;
 pub mod category;
+pub mod category_pack;
+pub mod debt;
+pub mod digest;
+pub mod member;
+pub mod report;
+pub mod settings;
 pub mod transaction;
\ No newline at end of file
@@ -0,0 +1,288 @@
+use crate::models::category::{Category, subcategories};
+use crate::models::transaction::{Amount, CurrencyCode};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+pub const MONTHS: [&str; 12] = [
+    "Январь",
+    "Февраль",
+    "Март",
+    "Апрель",
+    "Май",
+    "Июнь",
+    "Июль",
+    "Август",
+    "Сентябрь",
+    "Октябрь",
+    "Ноябрь",
+    "Декабрь",
+];
+
+/// Sums per currency, since amounts in different currencies cannot be added up.
+#[derive(Debug, Clone, Default)]
+pub struct Totals(HashMap<CurrencyCode, i64>);
+
+impl Totals {
+    pub fn add(&mut self, currency_code: CurrencyCode, amount: i64) {
+        *self.0.entry(currency_code).or_default() += amount;
+    }
+
+    pub fn merge(&mut self, other: &Totals) {
+        for (currency_code, amount) in &other.0 {
+            self.add(*currency_code, *amount);
+        }
+    }
+
+    pub fn get(&self, currency_code: CurrencyCode) -> i64 {
+        self.0.get(&currency_code).copied().unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(|amount| *amount == 0)
+    }
+
+    /// Sum of all currencies, only meaningful for ordering.
+    pub fn weight(&self) -> i64 {
+        self.0.values().sum()
+    }
+}
+
+impl Display for Totals {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = CurrencyCode::ALL
+            .iter()
+            .filter(|currency_code| self.get(**currency_code) != 0)
+            .map(|currency_code| {
+                format!(
+                    "{} {}",
+                    Amount(self.get(*currency_code)),
+                    currency_code.as_symbol_str()
+                )
+            })
+            .collect();
+
+        if parts.is_empty() {
+            write!(f, "0")
+        } else {
+            write!(f, "{}", parts.join(" + "))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CategoryTotal {
+    /// `None` for uncategorized transactions.
+    pub category_id: Option<i64>,
+    /// `None` for uncategorized transactions and categories that no longer exist.
+    pub category: Option<Category>,
+    pub totals: Totals,
+    /// Limit or plan of the category together with those of its subcategories.
+    pub target_amount: Option<i64>,
+    /// Subcategories with transactions, most spent first.
+    pub children: Vec<CategoryTotal>,
+}
+
+impl CategoryTotal {
+    /// Label and name of the category as shown in reports.
+    pub fn title(&self) -> String {
+        match (&self.category, self.category_id) {
+            (Some(category), _) if category.deleted_at.is_some() => {
+                format!("{} {} (в архиве)", category.label, category.name)
+            }
+            (Some(category), _) => format!("{} {}", category.label, category.name),
+            (None, Some(_)) => "🗑 Удалённая категория".to_string(),
+            (None, None) => "❔ Без категории".to_string(),
+        }
+    }
+}
+
+/// Totals per top-level category, most spent first. Subcategories add their totals and
+/// limits up to the parent and are listed under it unless the parent is archived. Archived
+/// categories keep their totals but not their limits.
+pub fn roll_up(
+    totals: HashMap<Option<i64>, Totals>,
+    categories: &[Category],
+) -> Vec<CategoryTotal> {
+    let find = |category_id: i64| {
+        categories
+            .iter()
+            .find(|category| category.id == category_id)
+    };
+    let target_amount = |category: &Category| {
+        if category.deleted_at.is_some() {
+            return None;
+        }
+        let amounts: Vec<i64> = std::iter::once(category)
+            .chain(subcategories(categories, category.id))
+            .filter(|category| category.deleted_at.is_none())
+            .filter_map(|category| category.target_amount)
+            .filter(|amount| *amount != 0)
+            .collect();
+
+        (!amounts.is_empty()).then(|| amounts.iter().sum())
+    };
+
+    let mut list: Vec<CategoryTotal> = Vec::new();
+    for (category_id, totals) in totals {
+        let category = category_id.and_then(find);
+        let parent = category
+            .and_then(|category| category.parent_id)
+            .and_then(find)
+            .filter(|parent| parent.deleted_at.is_none());
+        let top_id = parent.map(|parent| parent.id).or(category_id);
+
+        let index = match list.iter().position(|total| total.category_id == top_id) {
+            Some(index) => index,
+            None => {
+                let top = top_id.and_then(find);
+                list.push(CategoryTotal {
+                    category_id: top_id,
+                    category: top.cloned(),
+                    totals: Totals::default(),
+                    target_amount: top.and_then(target_amount),
+                    children: Vec::new(),
+                });
+                list.len() - 1
+            }
+        };
+
+        let top = &mut list[index];
+        top.totals.merge(&totals);
+        if parent.is_some() {
+            top.children.push(CategoryTotal {
+                category_id,
+                category: category.cloned(),
+                target_amount: category
+                    .filter(|category| category.deleted_at.is_none())
+                    .and_then(|category| category.target_amount)
+                    .filter(|amount| *amount != 0),
+                totals,
+                children: Vec::new(),
+            });
+        }
+    }
+
+    for total in &mut list {
+        total.children.sort_by_key(|child| -child.totals.weight());
+    }
+    list.sort_by_key(|total| -total.totals.weight());
+
+    list
+}
+
+#[derive(Debug, Clone)]
+pub struct MemberTotal {
+    pub user_id: Option<i64>,
+    pub expenses: Totals,
+    pub incomes: Totals,
+}
+
+#[derive(Debug, Clone)]
+pub struct MonthlyReport {
+    pub year: i32,
+    pub month: u32,
+    pub expenses: Totals,
+    pub incomes: Totals,
+    pub expense_categories: Vec<CategoryTotal>,
+    pub income_categories: Vec<CategoryTotal>,
+    pub members: Vec<MemberTotal>,
+}
+
+/// Expenses and incomes of a single month.
+#[derive(Debug, Clone)]
+pub struct MonthTotals {
+    pub year: i32,
+    pub month: u32,
+    pub expenses: Totals,
+    pub incomes: Totals,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::callback::v1::CategoryDirection;
+    use chrono::Utc;
+
+    fn category(id: i64, parent_id: Option<i64>, target_amount: Option<i64>) -> Category {
+        Category {
+            id,
+            chat_id: 1,
+            name: format!("category {id}"),
+            label: "🏷".to_string(),
+            direction: CategoryDirection::Expense,
+            is_regular: true,
+            target_amount,
+            parent_id,
+            position: 0,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn totals(amount: i64) -> Totals {
+        let mut totals = Totals::default();
+        totals.add(CurrencyCode::RUB, amount);
+        totals
+    }
+
+    #[test]
+    fn adds_subcategories_to_parent() {
+        let categories = vec![
+            category(1, None, Some(1000)),
+            category(2, Some(1), Some(500)),
+            category(3, Some(1), None),
+            category(4, None, None),
+        ];
+        let by_category = HashMap::from([
+            (Some(1), totals(100)),
+            (Some(2), totals(200)),
+            (Some(3), totals(300)),
+            (Some(4), totals(50)),
+        ]);
+
+        let list = roll_up(by_category, &categories);
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].category_id, Some(1));
+        assert_eq!(list[0].totals.get(CurrencyCode::RUB), 600);
+        assert_eq!(list[0].target_amount, Some(1500));
+        let children: Vec<Option<i64>> = list[0]
+            .children
+            .iter()
+            .map(|child| child.category_id)
+            .collect();
+        assert_eq!(children, vec![Some(3), Some(2)]);
+        assert_eq!(list[1].category_id, Some(4));
+    }
+
+    #[test]
+    fn keeps_subcategories_of_deleted_parents() {
+        let categories = vec![category(2, Some(1), None)];
+        let by_category = HashMap::from([(Some(2), totals(200)), (None, totals(10))]);
+
+        let list = roll_up(by_category, &categories);
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].category_id, Some(2));
+        assert!(list[0].children.is_empty());
+        assert_eq!(list[1].category_id, None);
+    }
+
+    #[test]
+    fn keeps_archived_categories_without_limits() {
+        let mut parent = category(1, None, Some(1000));
+        parent.deleted_at = Some(Utc::now());
+        let categories = vec![parent, category(2, Some(1), Some(500))];
+        let by_category = HashMap::from([(Some(1), totals(300)), (Some(2), totals(200))]);
+
+        let list = roll_up(by_category, &categories);
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].category_id, Some(1));
+        assert_eq!(list[0].target_amount, None);
+        assert_eq!(list[0].title(), "🏷 category 1 (в архиве)");
+        assert_eq!(list[1].category_id, Some(2));
+        assert_eq!(list[1].target_amount, Some(500));
+    }
+}
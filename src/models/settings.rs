@@ -0,0 +1,105 @@
+use crate::proto::callback::v1::{CategorySort, DigestFrequency};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_DIGEST_HOUR: u32 = 9;
+
+/// Timezones offered as buttons; any other IANA name can be set with `/timezone`.
+pub const TIMEZONE_PRESETS: [(Tz, &str); 13] = [
+    (Tz::Europe__Kaliningrad, "Калининград"),
+    (Tz::Europe__Moscow, "Москва"),
+    (Tz::Europe__Samara, "Самара"),
+    (Tz::Asia__Yekaterinburg, "Екатеринбург"),
+    (Tz::Asia__Omsk, "Омск"),
+    (Tz::Asia__Novosibirsk, "Новосибирск"),
+    (Tz::Asia__Krasnoyarsk, "Красноярск"),
+    (Tz::Asia__Irkutsk, "Иркутск"),
+    (Tz::Asia__Yakutsk, "Якутск"),
+    (Tz::Asia__Vladivostok, "Владивосток"),
+    (Tz::Asia__Magadan, "Магадан"),
+    (Tz::Asia__Kamchatka, "Камчатка"),
+    (Tz::UTC, "UTC"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSettings {
+    pub chat_id: i64,
+    #[serde(with = "digest_frequency")]
+    pub digest_frequency: DigestFrequency,
+    /// Hour of the day the digest is sent at.
+    pub digest_hour: u32,
+    /// Not part of backups: a restored chat gets its next digest as usual.
+    #[serde(skip)]
+    pub digest_sent_at: Option<DateTime<Utc>>,
+    /// Days, months and the digest hour are on this timezone's wall clock.
+    #[serde(with = "timezone")]
+    pub timezone: Tz,
+    /// Order of categories in the transaction category picker.
+    #[serde(with = "category_sort")]
+    pub category_sort: CategorySort,
+}
+
+impl ChatSettings {
+    /// Settings of a chat that has never changed them.
+    pub fn new(chat_id: i64) -> Self {
+        Self {
+            chat_id,
+            digest_frequency: DigestFrequency::Unspecified,
+            digest_hour: DEFAULT_DIGEST_HOUR,
+            digest_sent_at: None,
+            timezone: Tz::UTC,
+            category_sort: CategorySort::Unspecified,
+        }
+    }
+}
+
+mod digest_frequency {
+    use crate::proto::callback::v1::DigestFrequency;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        frequency: &DigestFrequency,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(i32::from(*frequency))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DigestFrequency, D::Error> {
+        Ok(DigestFrequency::try_from(i32::deserialize(deserializer)?)
+            .unwrap_or(DigestFrequency::Unspecified))
+    }
+}
+
+mod timezone {
+    use chrono_tz::Tz;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(timezone: &Tz, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(timezone.name())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Tz, D::Error> {
+        Ok(String::deserialize(deserializer)?
+            .parse()
+            .unwrap_or(Tz::UTC))
+    }
+}
+
+mod category_sort {
+    use crate::proto::callback::v1::CategorySort;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(sort: &CategorySort, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(i32::from(*sort))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CategorySort, D::Error> {
+        Ok(CategorySort::try_from(i32::deserialize(deserializer)?)
+            .unwrap_or(CategorySort::Unspecified))
+    }
+}
@@ -1,7 +1,9 @@
+use crate::proto::callback::v1::Currency;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CurrencyCode {
     USD,
     EUR,
@@ -10,6 +12,13 @@ pub enum CurrencyCode {
 }
 
 impl CurrencyCode {
+    pub const ALL: [CurrencyCode; 4] = [
+        CurrencyCode::RUB,
+        CurrencyCode::USD,
+        CurrencyCode::EUR,
+        CurrencyCode::JPY,
+    ];
+
     pub fn as_code_str(&self) -> &'static str {
         match self {
             CurrencyCode::USD => "USD",
@@ -39,16 +48,83 @@ impl CurrencyCode {
     }
 }
 
-#[derive(Debug, Clone)]
+impl From<CurrencyCode> for Currency {
+    fn from(value: CurrencyCode) -> Self {
+        match value {
+            CurrencyCode::USD => Currency::Usd,
+            CurrencyCode::EUR => Currency::Eur,
+            CurrencyCode::JPY => Currency::Jpy,
+            CurrencyCode::RUB => Currency::Rub,
+        }
+    }
+}
+
+impl TryFrom<Currency> for CurrencyCode {
+    type Error = ();
+
+    fn try_from(value: Currency) -> Result<Self, Self::Error> {
+        match value {
+            Currency::Usd => Ok(CurrencyCode::USD),
+            Currency::Eur => Ok(CurrencyCode::EUR),
+            Currency::Jpy => Ok(CurrencyCode::JPY),
+            Currency::Rub => Ok(CurrencyCode::RUB),
+            Currency::Unspecified => Err(()),
+        }
+    }
+}
+
+/// Amount in minor units (kopecks, cents) formatted for display: `1 234.50`.
+pub struct Amount(pub i64);
+
+impl Display for Amount {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let units = (self.0 / 100).unsigned_abs().to_string();
+        let cents = (self.0 % 100).unsigned_abs();
+
+        let mut grouped = String::with_capacity(units.len() + units.len() / 3);
+        for (i, digit) in units.chars().enumerate() {
+            if i > 0 && (units.len() - i).is_multiple_of(3) {
+                grouped.push(' ');
+            }
+            grouped.push(digit);
+        }
+
+        if cents == 0 {
+            write!(f, "{sign}{grouped}")
+        } else {
+            write!(f, "{sign}{grouped}.{cents:02}")
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
     pub id: i64,
     pub chat_id: i64,
+    #[serde(default)]
+    pub user_id: Option<i64>,
     pub category_id: Option<i64>,
     pub amount: i64,
     pub amount_modified: bool,
     pub description: String,
     pub currency_code: CurrencyCode,
+    pub message_id: Option<i32>,
+    pub reply_message_id: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_display() {
+        assert_eq!(Amount(0).to_string(), "0");
+        assert_eq!(Amount(35000).to_string(), "350");
+        assert_eq!(Amount(123456750).to_string(), "1 234 567.50");
+        assert_eq!(Amount(-100005).to_string(), "-1 000.05");
+    }
+}
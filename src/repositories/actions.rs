@@ -0,0 +1,163 @@
+use crate::models::action::{Action, ActionKind, Snapshot};
+use crate::repositories::utils;
+use sqlx::{FromRow, PgPool, types::time::OffsetDateTime};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RawAction {
+    pub id: i64,
+    pub chat_id: i64,
+    pub user_id: Option<i64>,
+    pub kind: i32,
+    pub entity_id: i64,
+    pub snapshot_before: Option<serde_json::Value>,
+    pub snapshot_after: Option<serde_json::Value>,
+    pub created_at: OffsetDateTime,
+    pub reverted_at: Option<OffsetDateTime>,
+    pub reverted_by: Option<i64>,
+}
+
+impl TryFrom<RawAction> for Action {
+    type Error = String;
+
+    fn try_from(raw: RawAction) -> Result<Self, Self::Error> {
+        let parse = |value: Option<serde_json::Value>| {
+            value
+                .map(serde_json::from_value::<Snapshot>)
+                .transpose()
+                .map_err(|e| e.to_string())
+        };
+
+        Ok(Action {
+            id: raw.id,
+            chat_id: raw.chat_id,
+            user_id: raw.user_id,
+            kind: ActionKind::try_from(raw.kind)?,
+            entity_id: raw.entity_id,
+            before: parse(raw.snapshot_before)?,
+            after: parse(raw.snapshot_after)?,
+            created_at: utils::convert_offset_to_chrono(raw.created_at),
+            reverted_at: raw.reverted_at.map(utils::convert_offset_to_chrono),
+            reverted_by: raw.reverted_by,
+        })
+    }
+}
+
+pub type RepositoryError = Box<dyn std::error::Error + Sync + Send>;
+
+#[async_trait::async_trait]
+pub trait Repository: Send + Sync {
+    async fn create_action(&self, action: Action) -> Result<i64, RepositoryError>;
+    async fn get_last_action(&self, chat_id: i64) -> Result<Option<Action>, RepositoryError>;
+    async fn select_actions(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Action>, RepositoryError>;
+    async fn count_actions(&self, chat_id: i64) -> Result<i64, RepositoryError>;
+    async fn revert_action(
+        &self,
+        chat_id: i64,
+        id: i64,
+        user_id: Option<i64>,
+    ) -> Result<(), RepositoryError>;
+}
+
+pub struct Actions {
+    db: PgPool,
+}
+
+impl Actions {
+    pub fn new(db: PgPool) -> Arc<Self> {
+        Arc::new(Self { db })
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository for Actions {
+    async fn create_action(&self, action: Action) -> Result<i64, RepositoryError> {
+        let raw = sqlx::query_file!(
+            "src/repositories/queries/create_action.sql",
+            action.chat_id,
+            action.user_id,
+            i32::from(action.kind),
+            action.entity_id,
+            action
+                .before
+                .as_ref()
+                .map(serde_json::to_value)
+                .transpose()?,
+            action
+                .after
+                .as_ref()
+                .map(serde_json::to_value)
+                .transpose()?,
+            OffsetDateTime::from_unix_timestamp(action.created_at.timestamp()).unwrap()
+        )
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(raw.id)
+    }
+
+    async fn get_last_action(&self, chat_id: i64) -> Result<Option<Action>, RepositoryError> {
+        let raw = sqlx::query_file_as!(
+            RawAction,
+            "src/repositories/queries/get_last_action.sql",
+            chat_id,
+        )
+        .fetch_optional(&self.db)
+        .await?;
+
+        raw.map(Action::try_from).transpose().map_err(|e| e.into())
+    }
+
+    async fn select_actions(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Action>, RepositoryError> {
+        let raws = sqlx::query_file_as!(
+            RawAction,
+            "src/repositories/queries/select_actions.sql",
+            chat_id,
+            limit,
+            offset,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let actions: Result<Vec<Action>, _> = raws.into_iter().map(Action::try_from).collect();
+
+        actions.map_err(|e| e.into())
+    }
+
+    async fn count_actions(&self, chat_id: i64) -> Result<i64, RepositoryError> {
+        let raw = sqlx::query_file!("src/repositories/queries/count_actions.sql", chat_id)
+            .fetch_one(&self.db)
+            .await?;
+
+        Ok(raw.count)
+    }
+
+    async fn revert_action(
+        &self,
+        chat_id: i64,
+        id: i64,
+        user_id: Option<i64>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query_file!(
+            "src/repositories/queries/revert_action.sql",
+            id,
+            chat_id,
+            OffsetDateTime::now_utc(),
+            user_id,
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+}
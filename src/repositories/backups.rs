@@ -0,0 +1,269 @@
+use crate::models::backup::{BACKUP_VERSION, Backup, Participant};
+use crate::models::category::Category;
+use crate::models::debt::Settlement;
+use crate::models::member::Member;
+use crate::models::settings::ChatSettings;
+use crate::models::transaction::Transaction;
+use crate::repositories::categories::RawCategory;
+use crate::repositories::debts::RawSettlement;
+use crate::repositories::members::RawMember;
+use crate::repositories::settings::RawChatSettings;
+use crate::repositories::transactions::RawTransaction;
+use chrono::Utc;
+use sqlx::{PgPool, types::time::OffsetDateTime};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub type RepositoryError = Box<dyn std::error::Error + Sync + Send>;
+
+#[async_trait::async_trait]
+pub trait Repository: Send + Sync {
+    /// Categories, live transactions with their splits, settlements, members and settings
+    /// of the chat.
+    async fn select_backup(&self, chat_id: i64) -> Result<Backup, RepositoryError>;
+    /// Whether the chat has neither categories, nor transactions, nor settlements.
+    async fn is_chat_empty(&self, chat_id: i64) -> Result<bool, RepositoryError>;
+    /// Loads the backup into the chat in a single database transaction, giving every
+    /// category and transaction a new id.
+    async fn restore_backup(&self, chat_id: i64, backup: Backup) -> Result<(), RepositoryError>;
+}
+
+pub struct Backups {
+    db: PgPool,
+}
+
+impl Backups {
+    pub fn new(db: PgPool) -> Arc<Self> {
+        Arc::new(Self { db })
+    }
+}
+
+fn to_offset(date_time: chrono::DateTime<Utc>) -> OffsetDateTime {
+    OffsetDateTime::from_unix_timestamp(date_time.timestamp()).unwrap()
+}
+
+#[async_trait::async_trait]
+impl Repository for Backups {
+    async fn select_backup(&self, chat_id: i64) -> Result<Backup, RepositoryError> {
+        let categories = sqlx::query_file_as!(
+            RawCategory,
+            "src/repositories/queries/select_backup_categories.sql",
+            chat_id
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(Category::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let transactions = sqlx::query_file_as!(
+            RawTransaction,
+            "src/repositories/queries/select_backup_transactions.sql",
+            chat_id
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(Transaction::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let participants = sqlx::query_file!(
+            "src/repositories/queries/select_backup_participants.sql",
+            chat_id
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|row| Participant {
+            transaction_id: row.transaction_id,
+            user_id: row.user_id,
+        })
+        .collect();
+
+        let settlements = sqlx::query_file_as!(
+            RawSettlement,
+            "src/repositories/queries/select_settlements.sql",
+            chat_id
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(Settlement::from)
+        .collect();
+
+        let members = sqlx::query_file_as!(
+            RawMember,
+            "src/repositories/queries/select_members.sql",
+            chat_id
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(Member::from)
+        .collect();
+
+        let settings = sqlx::query_file_as!(
+            RawChatSettings,
+            "src/repositories/queries/find_chat_settings.sql",
+            chat_id
+        )
+        .fetch_optional(&self.db)
+        .await?
+        .map(ChatSettings::from);
+
+        Ok(Backup {
+            version: BACKUP_VERSION,
+            chat_id,
+            created_at: Utc::now(),
+            categories,
+            transactions,
+            participants,
+            settlements,
+            members,
+            settings,
+        })
+    }
+
+    async fn is_chat_empty(&self, chat_id: i64) -> Result<bool, RepositoryError> {
+        let row = sqlx::query_file!("src/repositories/queries/is_chat_empty.sql", chat_id)
+            .fetch_one(&self.db)
+            .await?;
+
+        Ok(row.empty)
+    }
+
+    async fn restore_backup(&self, chat_id: i64, backup: Backup) -> Result<(), RepositoryError> {
+        let mut tx = self.db.begin().await?;
+
+        let mut category_ids: HashMap<i64, i64> = HashMap::new();
+        let categories = backup.categories;
+        for category in &categories {
+            let row = sqlx::query_file!(
+                "src/repositories/queries/restore_backup_category.sql",
+                chat_id,
+                category.name,
+                category.label,
+                i32::from(category.direction),
+                category.is_regular,
+                category.target_amount.unwrap_or_default(),
+                to_offset(category.created_at),
+                to_offset(category.updated_at),
+                category.deleted_at.map(to_offset),
+                category.position
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            category_ids.insert(category.id, row.id);
+        }
+
+        // Parents may come after their subcategories, so they are linked once all exist.
+        for category in &categories {
+            let (Some(id), Some(parent_id)) = (
+                category_ids.get(&category.id),
+                category
+                    .parent_id
+                    .and_then(|parent_id| category_ids.get(&parent_id)),
+            ) else {
+                continue;
+            };
+
+            sqlx::query_file!(
+                "src/repositories/queries/restore_backup_category_parent.sql",
+                id,
+                chat_id,
+                parent_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let mut transaction_ids: HashMap<i64, i64> = HashMap::new();
+        for transaction in backup.transactions {
+            let row = sqlx::query_file!(
+                "src/repositories/queries/restore_backup_transaction.sql",
+                chat_id,
+                transaction.user_id,
+                transaction
+                    .category_id
+                    .and_then(|category_id| category_ids.get(&category_id).copied()),
+                transaction.amount,
+                transaction.amount_modified,
+                transaction.description,
+                transaction.currency_code.as_code_str(),
+                to_offset(transaction.created_at),
+                to_offset(transaction.updated_at)
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            transaction_ids.insert(transaction.id, row.id);
+        }
+
+        let mut participants: HashMap<i64, Vec<i64>> = HashMap::new();
+        for participant in backup.participants {
+            if let Some(transaction_id) = transaction_ids.get(&participant.transaction_id) {
+                participants
+                    .entry(*transaction_id)
+                    .or_default()
+                    .push(participant.user_id);
+            }
+        }
+        for (transaction_id, user_ids) in participants {
+            sqlx::query_file!(
+                "src/repositories/queries/create_participants.sql",
+                transaction_id,
+                &user_ids
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for settlement in backup.settlements {
+            sqlx::query_file!(
+                "src/repositories/queries/create_settlement.sql",
+                chat_id,
+                settlement.from_user_id,
+                settlement.to_user_id,
+                settlement.amount,
+                settlement.currency_code.as_code_str(),
+                to_offset(settlement.created_at)
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+        }
+
+        for member in backup.members {
+            sqlx::query_file!(
+                "src/repositories/queries/restore_backup_member.sql",
+                chat_id,
+                member.user_id,
+                member.username,
+                member.full_name,
+                i32::from(member.role),
+                to_offset(member.created_at),
+                to_offset(member.updated_at)
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        if let Some(settings) = backup.settings {
+            sqlx::query_file!(
+                "src/repositories/queries/upsert_chat_settings.sql",
+                chat_id,
+                i32::from(settings.digest_frequency),
+                settings.digest_hour as i32,
+                settings.timezone.name(),
+                i32::from(settings.category_sort),
+                to_offset(Utc::now())
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use crate::models::category::Category;
 use crate::proto::callback::v1::CategoryDirection;
+use chrono::{DateTime, Utc};
 use sqlx::{FromRow, PgPool, types::time::OffsetDateTime};
+use std::collections::HashMap;
 use std::sync::Arc;
 use crate::repositories::utils;
 
@@ -13,6 +15,8 @@ pub struct RawCategory {
     pub direction: i32,
     pub is_regular: bool,
     pub target_amount: Option<i64>,
+    pub parent_id: Option<i64>,
+    pub position: i32,
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
     pub deleted_at: Option<OffsetDateTime>,
@@ -31,6 +35,8 @@ impl TryFrom<RawCategory> for Category {
                 .unwrap_or(CategoryDirection::Unspecified),
             is_regular: raw.is_regular,
             target_amount: raw.target_amount,
+            parent_id: raw.parent_id,
+            position: raw.position,
             created_at: utils::convert_offset_to_chrono(raw.created_at),
             updated_at: utils::convert_offset_to_chrono(raw.updated_at),
             deleted_at: raw.deleted_at.map(utils::convert_offset_to_chrono),
@@ -50,7 +56,37 @@ pub trait Repository: Send + Sync {
         chat_id: i64,
         direction: CategoryDirection,
     ) -> Result<Vec<Category>, RepositoryError>;
+    /// Categories of both directions, archived ones included.
+    async fn select_all_categories(&self, chat_id: i64) -> Result<Vec<Category>, RepositoryError>;
     async fn delete_category(&self, chat_id: i64, id: i64) -> Result<(), RepositoryError>;
+    async fn restore_category(&self, chat_id: i64, id: i64) -> Result<(), RepositoryError>;
+    /// Moves the transactions and subcategories of the source to the target, saves the target
+    /// and deletes the source, all at once. Returns the ids of the moved transactions.
+    async fn merge_categories(
+        &self,
+        chat_id: i64,
+        source_id: i64,
+        target: Category,
+    ) -> Result<Vec<i64>, RepositoryError>;
+    /// Reverts `merge_categories`: restores the source with the given subcategories and
+    /// transactions and saves the target as it was.
+    async fn unmerge_categories(
+        &self,
+        chat_id: i64,
+        source_id: i64,
+        target: Category,
+        subcategory_ids: &[i64],
+        transaction_ids: &[i64],
+    ) -> Result<(), RepositoryError>;
+    async fn count_transactions(&self, chat_id: i64, id: i64) -> Result<i64, RepositoryError>;
+    /// Numbers the categories in the order of `ids`.
+    async fn update_positions(&self, chat_id: i64, ids: &[i64]) -> Result<(), RepositoryError>;
+    /// Number of transactions per category created since `since`.
+    async fn select_usage(
+        &self,
+        chat_id: i64,
+        since: DateTime<Utc>,
+    ) -> Result<HashMap<i64, i64>, RepositoryError>;
 }
 
 pub struct Categories {
@@ -75,7 +111,8 @@ impl Repository for Categories {
             category.is_regular,
             category.target_amount,
             OffsetDateTime::from_unix_timestamp(category.created_at.timestamp()).unwrap(),
-            OffsetDateTime::from_unix_timestamp(category.updated_at.timestamp()).unwrap()
+            OffsetDateTime::from_unix_timestamp(category.updated_at.timestamp()).unwrap(),
+            category.parent_id
         )
         .fetch_one(&self.db)
         .await?;
@@ -93,9 +130,10 @@ impl Repository for Categories {
             i32::try_from(category.direction).unwrap(),
             category.is_regular,
             category.target_amount,
-            OffsetDateTime::from_unix_timestamp(category.updated_at.timestamp()).unwrap()
+            OffsetDateTime::from_unix_timestamp(category.updated_at.timestamp()).unwrap(),
+            category.parent_id
         )
-        .fetch_one(&self.db)
+        .execute(&self.db)
         .await?;
 
         Ok(())
@@ -136,6 +174,20 @@ impl Repository for Categories {
         categories.map_err(|e| e.into())
     }
 
+    async fn select_all_categories(&self, chat_id: i64) -> Result<Vec<Category>, RepositoryError> {
+        let raws = sqlx::query_file_as!(
+            RawCategory,
+            "src/repositories/queries/select_all_categories.sql",
+            chat_id
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let categories: Result<Vec<Category>, _> = raws.into_iter().map(Category::try_from).collect();
+
+        categories.map_err(|e| e.into())
+    }
+
     async fn delete_category(&self, chat_id: i64, id: i64) -> Result<(), RepositoryError> {
         sqlx::query_file!(
             "src/repositories/queries/delete_category.sql",
@@ -143,9 +195,185 @@ impl Repository for Categories {
             chat_id,
             OffsetDateTime::now_utc(),
         )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn restore_category(&self, chat_id: i64, id: i64) -> Result<(), RepositoryError> {
+        sqlx::query_file!(
+            "src/repositories/queries/restore_category.sql",
+            id,
+            chat_id,
+            OffsetDateTime::now_utc(),
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+    async fn merge_categories(
+        &self,
+        chat_id: i64,
+        source_id: i64,
+        target: Category,
+    ) -> Result<Vec<i64>, RepositoryError> {
+        let now = OffsetDateTime::now_utc();
+        let mut tx = self.db.begin().await?;
+
+        let transaction_ids = sqlx::query_file!(
+            "src/repositories/queries/merge_category_transactions.sql",
+            chat_id,
+            source_id,
+            target.id,
+            now
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|row| row.id)
+        .collect();
+
+        sqlx::query_file!(
+            "src/repositories/queries/merge_category_children.sql",
+            chat_id,
+            source_id,
+            target.id,
+            now
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query_file!(
+            "src/repositories/queries/update_category.sql",
+            target.id,
+            chat_id,
+            target.name,
+            target.label,
+            i32::from(target.direction),
+            target.is_regular,
+            target.target_amount,
+            now,
+            target.parent_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query_file!(
+            "src/repositories/queries/delete_category.sql",
+            source_id,
+            chat_id,
+            now,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(transaction_ids)
+    }
+
+    async fn unmerge_categories(
+        &self,
+        chat_id: i64,
+        source_id: i64,
+        target: Category,
+        subcategory_ids: &[i64],
+        transaction_ids: &[i64],
+    ) -> Result<(), RepositoryError> {
+        let now = OffsetDateTime::now_utc();
+        let mut tx = self.db.begin().await?;
+
+        sqlx::query_file!(
+            "src/repositories/queries/restore_category.sql",
+            source_id,
+            chat_id,
+            now,
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query_file!(
+            "src/repositories/queries/update_category.sql",
+            target.id,
+            chat_id,
+            target.name,
+            target.label,
+            i32::from(target.direction),
+            target.is_regular,
+            target.target_amount,
+            now,
+            target.parent_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query_file!(
+            "src/repositories/queries/unmerge_category_children.sql",
+            chat_id,
+            source_id,
+            subcategory_ids,
+            now
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query_file!(
+            "src/repositories/queries/unmerge_category_transactions.sql",
+            chat_id,
+            source_id,
+            transaction_ids,
+            now
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn count_transactions(&self, chat_id: i64, id: i64) -> Result<i64, RepositoryError> {
+        let row = sqlx::query_file!(
+            "src/repositories/queries/count_category_transactions.sql",
+            chat_id,
+            id
+        )
         .fetch_one(&self.db)
         .await?;
 
+        Ok(row.count)
+    }
+
+    async fn update_positions(&self, chat_id: i64, ids: &[i64]) -> Result<(), RepositoryError> {
+        sqlx::query_file!(
+            "src/repositories/queries/update_category_positions.sql",
+            chat_id,
+            ids
+        )
+        .execute(&self.db)
+        .await?;
+
         Ok(())
     }
+
+    async fn select_usage(
+        &self,
+        chat_id: i64,
+        since: DateTime<Utc>,
+    ) -> Result<HashMap<i64, i64>, RepositoryError> {
+        let rows = sqlx::query_file!(
+            "src/repositories/queries/select_category_usage.sql",
+            chat_id,
+            OffsetDateTime::from_unix_timestamp(since.timestamp()).unwrap()
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.category_id, row.count))
+            .collect())
+    }
 }
@@ -0,0 +1,142 @@
+use crate::models::debt::{Settlement, Split};
+use crate::models::transaction::CurrencyCode;
+use crate::repositories::utils;
+use sqlx::{FromRow, PgPool, types::time::OffsetDateTime};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RawSettlement {
+    pub id: i64,
+    pub chat_id: i64,
+    pub from_user_id: i64,
+    pub to_user_id: i64,
+    pub amount: i64,
+    pub currency_code: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<RawSettlement> for Settlement {
+    fn from(raw: RawSettlement) -> Self {
+        Settlement {
+            id: raw.id,
+            chat_id: raw.chat_id,
+            from_user_id: raw.from_user_id,
+            to_user_id: raw.to_user_id,
+            amount: raw.amount,
+            currency_code: CurrencyCode::from_code_str(&raw.currency_code)
+                .unwrap_or(CurrencyCode::RUB),
+            created_at: utils::convert_offset_to_chrono(raw.created_at),
+        }
+    }
+}
+
+pub type RepositoryError = Box<dyn std::error::Error + Sync + Send>;
+
+#[async_trait::async_trait]
+pub trait Repository: Send + Sync {
+    /// Replaces the participants of the transaction, an empty list removes the split.
+    async fn replace_participants(
+        &self,
+        transaction_id: i64,
+        user_ids: Vec<i64>,
+    ) -> Result<(), RepositoryError>;
+    /// Splits of the chat's transactions that were not deleted.
+    async fn select_splits(&self, chat_id: i64) -> Result<Vec<Split>, RepositoryError>;
+    async fn create_settlement(&self, settlement: Settlement) -> Result<i64, RepositoryError>;
+    async fn select_settlements(&self, chat_id: i64) -> Result<Vec<Settlement>, RepositoryError>;
+}
+
+pub struct Debts {
+    db: PgPool,
+}
+
+impl Debts {
+    pub fn new(db: PgPool) -> Arc<Self> {
+        Arc::new(Self { db })
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository for Debts {
+    async fn replace_participants(
+        &self,
+        transaction_id: i64,
+        user_ids: Vec<i64>,
+    ) -> Result<(), RepositoryError> {
+        let mut tx = self.db.begin().await?;
+
+        sqlx::query_file!(
+            "src/repositories/queries/delete_participants.sql",
+            transaction_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if !user_ids.is_empty() {
+            sqlx::query_file!(
+                "src/repositories/queries/create_participants.sql",
+                transaction_id,
+                &user_ids
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn select_splits(&self, chat_id: i64) -> Result<Vec<Split>, RepositoryError> {
+        let rows = sqlx::query_file!("src/repositories/queries/select_splits.sql", chat_id)
+            .fetch_all(&self.db)
+            .await?;
+
+        let mut splits: Vec<Split> = Vec::new();
+        for row in rows {
+            match splits.last_mut() {
+                Some(split) if split.transaction_id == row.transaction_id => {
+                    split.participants.push(row.participant_id);
+                }
+                _ => splits.push(Split {
+                    transaction_id: row.transaction_id,
+                    payer_id: row.payer_id,
+                    amount: row.amount,
+                    currency_code: CurrencyCode::from_code_str(&row.currency_code)
+                        .unwrap_or(CurrencyCode::RUB),
+                    participants: vec![row.participant_id],
+                }),
+            }
+        }
+
+        Ok(splits)
+    }
+
+    async fn create_settlement(&self, settlement: Settlement) -> Result<i64, RepositoryError> {
+        let row = sqlx::query_file!(
+            "src/repositories/queries/create_settlement.sql",
+            settlement.chat_id,
+            settlement.from_user_id,
+            settlement.to_user_id,
+            settlement.amount,
+            settlement.currency_code.as_code_str(),
+            OffsetDateTime::from_unix_timestamp(settlement.created_at.timestamp()).unwrap()
+        )
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(row.id)
+    }
+
+    async fn select_settlements(&self, chat_id: i64) -> Result<Vec<Settlement>, RepositoryError> {
+        let raws = sqlx::query_file_as!(
+            RawSettlement,
+            "src/repositories/queries/select_settlements.sql",
+            chat_id
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(raws.into_iter().map(Settlement::from).collect())
+    }
+}
@@ -0,0 +1,145 @@
+use crate::models::member::Member;
+use crate::proto::callback::v1::MemberRole;
+use crate::repositories::utils;
+use sqlx::{FromRow, PgPool, types::time::OffsetDateTime};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RawMember {
+    pub chat_id: i64,
+    pub user_id: i64,
+    pub username: Option<String>,
+    pub full_name: String,
+    pub role: i32,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<RawMember> for Member {
+    fn from(raw: RawMember) -> Self {
+        Member {
+            chat_id: raw.chat_id,
+            user_id: raw.user_id,
+            username: raw.username,
+            full_name: raw.full_name,
+            role: MemberRole::try_from(raw.role).unwrap_or(MemberRole::Viewer),
+            created_at: utils::convert_offset_to_chrono(raw.created_at),
+            updated_at: utils::convert_offset_to_chrono(raw.updated_at),
+        }
+    }
+}
+
+pub type RepositoryError = Box<dyn std::error::Error + Sync + Send>;
+
+#[async_trait::async_trait]
+pub trait Repository: Send + Sync {
+    async fn upsert_member(&self, member: Member) -> Result<(), RepositoryError>;
+    async fn select_members(&self, chat_id: i64) -> Result<Vec<Member>, RepositoryError>;
+    async fn find_member(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<Option<Member>, RepositoryError>;
+    async fn update_member_role(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        role: MemberRole,
+    ) -> Result<(), RepositoryError>;
+    async fn has_owner(&self, chat_id: i64) -> Result<bool, RepositoryError>;
+    /// Group chats none of whose known members owns them.
+    async fn select_chats_without_owner(&self) -> Result<Vec<i64>, RepositoryError>;
+}
+
+pub struct Members {
+    db: PgPool,
+}
+
+impl Members {
+    pub fn new(db: PgPool) -> Arc<Self> {
+        Arc::new(Self { db })
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository for Members {
+    async fn upsert_member(&self, member: Member) -> Result<(), RepositoryError> {
+        sqlx::query_file!(
+            "src/repositories/queries/upsert_member.sql",
+            member.chat_id,
+            member.user_id,
+            member.username,
+            member.full_name,
+            i32::from(member.role),
+            OffsetDateTime::from_unix_timestamp(member.updated_at.timestamp()).unwrap()
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn select_members(&self, chat_id: i64) -> Result<Vec<Member>, RepositoryError> {
+        let raws = sqlx::query_file_as!(
+            RawMember,
+            "src/repositories/queries/select_members.sql",
+            chat_id
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(raws.into_iter().map(Member::from).collect())
+    }
+
+    async fn find_member(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+    ) -> Result<Option<Member>, RepositoryError> {
+        let raw = sqlx::query_file_as!(
+            RawMember,
+            "src/repositories/queries/find_member.sql",
+            chat_id,
+            user_id
+        )
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(raw.map(Member::from))
+    }
+
+    async fn update_member_role(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        role: MemberRole,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query_file!(
+            "src/repositories/queries/update_member_role.sql",
+            chat_id,
+            user_id,
+            i32::from(role),
+            OffsetDateTime::now_utc()
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn has_owner(&self, chat_id: i64) -> Result<bool, RepositoryError> {
+        let raw = sqlx::query_file!("src/repositories/queries/has_owner.sql", chat_id)
+            .fetch_one(&self.db)
+            .await?;
+
+        Ok(raw.exists)
+    }
+
+    async fn select_chats_without_owner(&self) -> Result<Vec<i64>, RepositoryError> {
+        let raws = sqlx::query_file!("src/repositories/queries/select_chats_without_owner.sql")
+            .fetch_all(&self.db)
+            .await?;
+
+        Ok(raws.into_iter().map(|raw| raw.chat_id).collect())
+    }
+}
@@ -1,3 +1,8 @@
+pub mod actions;
+pub mod backups;
 pub mod categories;
+pub mod debts;
+pub mod members;
+pub mod settings;
 pub mod transactions;
 mod utils;
\ No newline at end of file
@@ -0,0 +1,3 @@
+SELECT COUNT(*) AS "count!"
+FROM actions
+WHERE chat_id = $1;
\ No newline at end of file
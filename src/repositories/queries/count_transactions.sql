@@ -0,0 +1,4 @@
+SELECT COUNT(*) AS "count!"
+FROM transactions
+WHERE chat_id = $1
+  AND deleted_at IS NULL;
\ No newline at end of file
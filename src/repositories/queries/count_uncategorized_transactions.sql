@@ -0,0 +1,5 @@
+SELECT COUNT(*) AS "count!"
+FROM transactions
+WHERE chat_id = $1
+  AND category_id IS NULL
+  AND deleted_at IS NULL;
\ No newline at end of file
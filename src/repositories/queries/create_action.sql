@@ -0,0 +1,3 @@
+INSERT INTO actions (chat_id, user_id, kind, entity_id, snapshot_before, snapshot_after, created_at)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
+RETURNING id;
\ No newline at end of file
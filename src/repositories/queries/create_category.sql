@@ -1,3 +1,7 @@
-INSERT INTO categories (chat_id, name, label, direction, is_regular, target_amount, created_at, updated_at)
-VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+INSERT INTO categories (chat_id, name, label, direction, is_regular, target_amount, created_at, updated_at, parent_id, position)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9,
+        (SELECT COALESCE(MAX(position), 0) + 1
+         FROM categories
+         WHERE chat_id = $1
+           AND direction = $4))
 RETURNING *;
\ No newline at end of file
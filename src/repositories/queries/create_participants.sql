@@ -0,0 +1,4 @@
+INSERT INTO transaction_participants (transaction_id, user_id)
+SELECT $1, user_id
+FROM UNNEST($2::BIGINT[]) AS user_id
+ON CONFLICT DO NOTHING;
\ No newline at end of file
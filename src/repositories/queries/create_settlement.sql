@@ -0,0 +1,3 @@
+INSERT INTO settlements (chat_id, from_user_id, to_user_id, amount, currency_code, created_at)
+VALUES ($1, $2, $3, $4, $5, $6)
+RETURNING id;
\ No newline at end of file
@@ -1,3 +1,3 @@
-INSERT INTO transactions (chat_id, category_id, amount, description, currency_code, created_at, updated_at)
-VALUES ($1, $2, $3, $4, $5, $6, $7)
+INSERT INTO transactions (chat_id, user_id, category_id, amount, description, currency_code, message_id, created_at, updated_at)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
 RETURNING *;
\ No newline at end of file
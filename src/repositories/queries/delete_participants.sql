@@ -0,0 +1,3 @@
+DELETE
+FROM transaction_participants
+WHERE transaction_id = $1;
\ No newline at end of file
@@ -0,0 +1,10 @@
+SELECT t.category_id AS "category_id!"
+FROM transactions t
+         JOIN categories c ON c.id = t.category_id
+WHERE t.chat_id = $1
+  AND LOWER(t.description) = LOWER($2)
+  AND ($3::INTEGER IS NULL OR c.direction = $3)
+  AND c.deleted_at IS NULL
+  AND t.deleted_at IS NULL
+ORDER BY t.created_at DESC, t.id DESC
+LIMIT 1;
\ No newline at end of file
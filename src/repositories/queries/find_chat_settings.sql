@@ -0,0 +1,3 @@
+SELECT *
+FROM chat_settings
+WHERE chat_id = $1;
\ No newline at end of file
@@ -0,0 +1,4 @@
+SELECT *
+FROM members
+WHERE chat_id = $1
+  AND user_id = $2;
\ No newline at end of file
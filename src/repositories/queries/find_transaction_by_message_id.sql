@@ -0,0 +1,7 @@
+SELECT *
+FROM transactions
+WHERE chat_id = $1
+  AND message_id = $2
+  AND deleted_at IS NULL
+ORDER BY id DESC
+LIMIT 1;
@@ -0,0 +1,6 @@
+SELECT *
+FROM actions
+WHERE chat_id = $1
+  AND reverted_at IS NULL
+ORDER BY id DESC
+LIMIT 1;
\ No newline at end of file
@@ -0,0 +1,4 @@
+SELECT EXISTS (SELECT 1
+               FROM members
+               WHERE chat_id = $1
+                 AND role = 1) AS "exists!";
\ No newline at end of file
@@ -0,0 +1,3 @@
+SELECT NOT EXISTS (SELECT 1 FROM categories WHERE chat_id = $1)
+           AND NOT EXISTS (SELECT 1 FROM transactions WHERE chat_id = $1)
+           AND NOT EXISTS (SELECT 1 FROM settlements WHERE chat_id = $1) AS "empty!";
\ No newline at end of file
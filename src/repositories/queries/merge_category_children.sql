@@ -0,0 +1,6 @@
+UPDATE categories
+SET parent_id  = $3,
+    updated_at = $4
+WHERE chat_id = $1
+  AND parent_id = $2
+  AND id <> $3;
\ No newline at end of file
@@ -0,0 +1,7 @@
+UPDATE transactions
+SET category_id = $3,
+    updated_at  = $4
+WHERE chat_id = $1
+  AND category_id = $2
+  AND deleted_at IS NULL
+RETURNING id;
\ No newline at end of file
@@ -0,0 +1,3 @@
+INSERT INTO categories (chat_id, name, label, direction, is_regular, target_amount, created_at, updated_at, deleted_at, position)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+RETURNING id;
\ No newline at end of file
@@ -0,0 +1,4 @@
+UPDATE categories
+SET parent_id = $3
+WHERE id = $1
+  AND chat_id = $2;
\ No newline at end of file
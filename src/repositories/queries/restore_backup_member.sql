@@ -0,0 +1,3 @@
+INSERT INTO members (chat_id, user_id, username, full_name, role, created_at, updated_at)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
+ON CONFLICT (chat_id, user_id) DO NOTHING;
\ No newline at end of file
@@ -0,0 +1,3 @@
+INSERT INTO transactions (chat_id, user_id, category_id, amount, amount_modified, description, currency_code, created_at, updated_at)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+RETURNING id;
\ No newline at end of file
@@ -0,0 +1,6 @@
+UPDATE transactions
+SET deleted_at = NULL,
+    updated_at = $3
+WHERE id = $1
+  AND chat_id = $2
+  AND deleted_at IS NOT NULL;
\ No newline at end of file
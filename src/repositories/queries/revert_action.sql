@@ -0,0 +1,6 @@
+UPDATE actions
+SET reverted_at = $3,
+    reverted_by = $4
+WHERE id = $1
+  AND chat_id = $2
+  AND reverted_at IS NULL;
\ No newline at end of file
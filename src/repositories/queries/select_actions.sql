@@ -0,0 +1,5 @@
+SELECT *
+FROM actions
+WHERE chat_id = $1
+ORDER BY id DESC
+LIMIT $2 OFFSET $3;
\ No newline at end of file
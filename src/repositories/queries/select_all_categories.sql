@@ -0,0 +1,4 @@
+SELECT *
+FROM categories
+WHERE chat_id = $1
+ORDER BY position, created_at DESC, id DESC;
\ No newline at end of file
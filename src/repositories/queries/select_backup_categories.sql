@@ -0,0 +1,4 @@
+SELECT *
+FROM categories
+WHERE chat_id = $1
+ORDER BY id;
\ No newline at end of file
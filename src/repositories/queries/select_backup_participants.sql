@@ -0,0 +1,6 @@
+SELECT tp.transaction_id, tp.user_id
+FROM transaction_participants tp
+         JOIN transactions t ON t.id = tp.transaction_id
+WHERE t.chat_id = $1
+  AND t.deleted_at IS NULL
+ORDER BY tp.transaction_id, tp.user_id;
\ No newline at end of file
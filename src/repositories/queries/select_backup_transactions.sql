@@ -0,0 +1,5 @@
+SELECT *
+FROM transactions
+WHERE chat_id = $1
+  AND deleted_at IS NULL
+ORDER BY id;
\ No newline at end of file
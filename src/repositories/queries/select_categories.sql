@@ -3,4 +3,4 @@ FROM categories
 WHERE chat_id = $1
   AND direction = $2
   AND deleted_at IS NULL
-ORDER BY created_at DESC, id DESC;
\ No newline at end of file
+ORDER BY position, created_at DESC, id DESC;
\ No newline at end of file
@@ -0,0 +1,7 @@
+SELECT category_id AS "category_id!", COUNT(*) AS "count!"
+FROM transactions
+WHERE chat_id = $1
+  AND category_id IS NOT NULL
+  AND created_at >= $2
+  AND deleted_at IS NULL
+GROUP BY category_id;
\ No newline at end of file
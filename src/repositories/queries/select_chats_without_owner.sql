@@ -0,0 +1,5 @@
+SELECT chat_id
+FROM members
+WHERE chat_id < 0
+GROUP BY chat_id
+HAVING COUNT(*) FILTER (WHERE role = 1) = 0;
\ No newline at end of file
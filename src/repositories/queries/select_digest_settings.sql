@@ -0,0 +1,3 @@
+SELECT *
+FROM chat_settings
+WHERE digest_frequency <> 0;
\ No newline at end of file
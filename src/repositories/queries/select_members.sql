@@ -0,0 +1,4 @@
+SELECT *
+FROM members
+WHERE chat_id = $1
+ORDER BY created_at, user_id;
\ No newline at end of file
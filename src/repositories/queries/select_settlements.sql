@@ -0,0 +1,4 @@
+SELECT *
+FROM settlements
+WHERE chat_id = $1
+ORDER BY created_at, id;
\ No newline at end of file
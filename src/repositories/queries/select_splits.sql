@@ -0,0 +1,11 @@
+SELECT t.id          AS transaction_id,
+       t.user_id     AS "payer_id!",
+       t.amount,
+       t.currency_code,
+       p.user_id     AS participant_id
+FROM transaction_participants p
+         JOIN transactions t ON t.id = p.transaction_id
+WHERE t.chat_id = $1
+  AND t.deleted_at IS NULL
+  AND t.user_id IS NOT NULL
+ORDER BY t.id, p.user_id;
\ No newline at end of file
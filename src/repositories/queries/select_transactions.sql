@@ -0,0 +1,6 @@
+SELECT *
+FROM transactions
+WHERE chat_id = $1
+  AND deleted_at IS NULL
+ORDER BY created_at DESC, id DESC
+LIMIT $2 OFFSET $3;
\ No newline at end of file
@@ -0,0 +1,7 @@
+SELECT *
+FROM transactions
+WHERE chat_id = $1
+  AND created_at >= $2
+  AND created_at < $3
+  AND deleted_at IS NULL
+ORDER BY created_at, id;
\ No newline at end of file
@@ -0,0 +1,7 @@
+SELECT *
+FROM transactions
+WHERE chat_id = $1
+  AND category_id IS NULL
+  AND LOWER(description) = LOWER($2)
+  AND deleted_at IS NULL
+ORDER BY created_at, id;
\ No newline at end of file
@@ -0,0 +1,9 @@
+SELECT t.*
+FROM transactions t
+         LEFT JOIN transaction_reminders r ON r.transaction_id = t.id
+WHERE t.category_id IS NULL
+  AND t.deleted_at IS NULL
+  AND t.created_at >= $1
+  AND t.created_at < $2
+  AND r.transaction_id IS NULL
+ORDER BY t.chat_id, t.created_at, t.id;
\ No newline at end of file
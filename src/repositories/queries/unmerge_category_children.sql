@@ -0,0 +1,5 @@
+UPDATE categories
+SET parent_id  = $2,
+    updated_at = $4
+WHERE chat_id = $1
+  AND id = ANY($3::BIGINT[]);
\ No newline at end of file
@@ -4,7 +4,8 @@ SET name          = $3,
     direction     = $5,
     is_regular    = $6,
     target_amount = $7,
-    updated_at    = $8
+    updated_at    = $8,
+    parent_id     = $9
 WHERE id = $1
   AND chat_id = $2
   AND deleted_at IS NULL;
\ No newline at end of file
@@ -0,0 +1,5 @@
+UPDATE categories
+SET position = ordered.position
+FROM UNNEST($2::BIGINT[]) WITH ORDINALITY AS ordered(id, position)
+WHERE categories.id = ordered.id
+  AND categories.chat_id = $1;
\ No newline at end of file
@@ -0,0 +1,3 @@
+UPDATE chat_settings
+SET digest_sent_at = $2
+WHERE chat_id = $1;
\ No newline at end of file
@@ -0,0 +1,5 @@
+UPDATE members
+SET role       = $3,
+    updated_at = $4
+WHERE chat_id = $1
+  AND user_id = $2;
\ No newline at end of file
@@ -1,10 +1,12 @@
 UPDATE transactions
-SET category_id     = $3,
-    amount          = $4,
-    amount_modified = $5,
-    description     = $6,
-    currency_code   = $7,
-    updated_at      = $8
+SET category_id      = $3,
+    amount           = $4,
+    amount_modified  = $5,
+    description      = $6,
+    currency_code    = $7,
+    updated_at       = $8,
+    created_at       = $9,
+    reply_message_id = $10
 WHERE id = $1
   AND chat_id = $2
   AND deleted_at IS NULL;
\ No newline at end of file
@@ -0,0 +1,8 @@
+INSERT INTO chat_settings (chat_id, digest_frequency, digest_hour, timezone, category_sort, created_at, updated_at)
+VALUES ($1, $2, $3, $4, $5, $6, $6)
+ON CONFLICT (chat_id) DO UPDATE
+    SET digest_frequency = EXCLUDED.digest_frequency,
+        digest_hour      = EXCLUDED.digest_hour,
+        timezone         = EXCLUDED.timezone,
+        category_sort    = EXCLUDED.category_sort,
+        updated_at       = EXCLUDED.updated_at;
\ No newline at end of file
@@ -0,0 +1,6 @@
+INSERT INTO members (chat_id, user_id, username, full_name, role, created_at, updated_at)
+VALUES ($1, $2, $3, $4, $5, $6, $6)
+ON CONFLICT (chat_id, user_id) DO UPDATE
+    SET username   = EXCLUDED.username,
+        full_name  = EXCLUDED.full_name,
+        updated_at = EXCLUDED.updated_at;
\ No newline at end of file
@@ -0,0 +1,5 @@
+INSERT INTO transaction_reminders (transaction_id, reminded_at)
+SELECT transaction_id, $2
+FROM UNNEST($1::BIGINT[]) AS transaction_id
+ON CONFLICT (transaction_id) DO UPDATE
+    SET reminded_at = EXCLUDED.reminded_at;
\ No newline at end of file
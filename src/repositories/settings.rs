@@ -0,0 +1,117 @@
+use crate::models::settings::ChatSettings;
+use crate::proto::callback::v1::{CategorySort, DigestFrequency};
+use crate::repositories::utils;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use sqlx::{FromRow, PgPool, types::time::OffsetDateTime};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RawChatSettings {
+    pub chat_id: i64,
+    pub digest_frequency: i32,
+    pub digest_hour: i32,
+    pub digest_sent_at: Option<OffsetDateTime>,
+    pub timezone: String,
+    pub category_sort: i32,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<RawChatSettings> for ChatSettings {
+    fn from(raw: RawChatSettings) -> Self {
+        ChatSettings {
+            chat_id: raw.chat_id,
+            digest_frequency: DigestFrequency::try_from(raw.digest_frequency)
+                .unwrap_or(DigestFrequency::Unspecified),
+            digest_hour: raw.digest_hour.clamp(0, 23) as u32,
+            digest_sent_at: raw.digest_sent_at.map(utils::convert_offset_to_chrono),
+            timezone: raw.timezone.parse().unwrap_or(Tz::UTC),
+            category_sort: CategorySort::try_from(raw.category_sort)
+                .unwrap_or(CategorySort::Unspecified),
+        }
+    }
+}
+
+pub type RepositoryError = Box<dyn std::error::Error + Sync + Send>;
+
+#[async_trait::async_trait]
+pub trait Repository: Send + Sync {
+    async fn find_settings(&self, chat_id: i64) -> Result<Option<ChatSettings>, RepositoryError>;
+    async fn upsert_settings(&self, settings: ChatSettings) -> Result<(), RepositoryError>;
+    /// Settings of every chat that has digests turned on.
+    async fn select_digest_settings(&self) -> Result<Vec<ChatSettings>, RepositoryError>;
+    async fn update_digest_sent_at(
+        &self,
+        chat_id: i64,
+        sent_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError>;
+}
+
+pub struct Settings {
+    db: PgPool,
+}
+
+impl Settings {
+    pub fn new(db: PgPool) -> Arc<Self> {
+        Arc::new(Self { db })
+    }
+}
+
+#[async_trait::async_trait]
+impl Repository for Settings {
+    async fn find_settings(&self, chat_id: i64) -> Result<Option<ChatSettings>, RepositoryError> {
+        let raw = sqlx::query_file_as!(
+            RawChatSettings,
+            "src/repositories/queries/find_chat_settings.sql",
+            chat_id
+        )
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(raw.map(ChatSettings::from))
+    }
+
+    async fn upsert_settings(&self, settings: ChatSettings) -> Result<(), RepositoryError> {
+        sqlx::query_file!(
+            "src/repositories/queries/upsert_chat_settings.sql",
+            settings.chat_id,
+            i32::from(settings.digest_frequency),
+            settings.digest_hour as i32,
+            settings.timezone.name(),
+            i32::from(settings.category_sort),
+            OffsetDateTime::from_unix_timestamp(Utc::now().timestamp()).unwrap()
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn select_digest_settings(&self) -> Result<Vec<ChatSettings>, RepositoryError> {
+        let raws = sqlx::query_file_as!(
+            RawChatSettings,
+            "src/repositories/queries/select_digest_settings.sql"
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(raws.into_iter().map(ChatSettings::from).collect())
+    }
+
+    async fn update_digest_sent_at(
+        &self,
+        chat_id: i64,
+        sent_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query_file!(
+            "src/repositories/queries/update_digest_sent_at.sql",
+            chat_id,
+            OffsetDateTime::from_unix_timestamp(sent_at.timestamp()).unwrap()
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+}
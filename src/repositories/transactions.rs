@@ -1,5 +1,7 @@
 use crate::models::transaction::{CurrencyCode, Transaction};
+use crate::proto::callback::v1::CategoryDirection;
 use crate::repositories::utils;
+use chrono::{DateTime, Utc};
 use sqlx::{FromRow, PgPool, types::time::OffsetDateTime};
 use std::sync::Arc;
 
@@ -7,11 +9,14 @@ use std::sync::Arc;
 pub struct RawTransaction {
     pub id: i64,
     pub chat_id: i64,
+    pub user_id: Option<i64>,
     pub category_id: Option<i64>,
     pub amount: i64,
     pub amount_modified: bool,
     pub description: String,
     pub currency_code: String,
+    pub message_id: Option<i32>,
+    pub reply_message_id: Option<i32>,
     pub created_at: OffsetDateTime,
     pub updated_at: OffsetDateTime,
     pub deleted_at: Option<OffsetDateTime>,
@@ -24,14 +29,17 @@ impl TryFrom<RawTransaction> for Transaction {
         Ok(Transaction {
             id: raw.id,
             chat_id: raw.chat_id,
+            user_id: raw.user_id,
             category_id: raw.category_id,
             amount: raw.amount,
             amount_modified: raw.amount_modified,
             description: raw.description,
             currency_code: CurrencyCode::from_code_str(&raw.currency_code)
                 .unwrap_or(CurrencyCode::RUB),
+            message_id: raw.message_id,
+            reply_message_id: raw.reply_message_id,
             created_at: utils::convert_offset_to_chrono(raw.created_at),
-            updated_at: utils::convert_offset_to_chrono(raw.created_at),
+            updated_at: utils::convert_offset_to_chrono(raw.updated_at),
             deleted_at: raw.deleted_at.map(utils::convert_offset_to_chrono),
         })
     }
@@ -42,9 +50,68 @@ pub type RepositoryError = Box<dyn std::error::Error + Sync + Send>;
 #[async_trait::async_trait]
 pub trait Repository: Send + Sync {
     async fn create_transaction(&self, transaction: Transaction) -> Result<i64, RepositoryError>;
+    /// Creates all the transactions in a single database transaction and returns their ids
+    /// in the same order.
+    async fn create_transactions(
+        &self,
+        transactions: &[Transaction],
+    ) -> Result<Vec<i64>, RepositoryError>;
     async fn get_transaction(&self, chat_id: i64, id: i64) -> Result<Transaction, RepositoryError>;
+    async fn find_transaction_by_message_id(
+        &self,
+        chat_id: i64,
+        message_id: i32,
+    ) -> Result<Option<Transaction>, RepositoryError>;
+    async fn select_transactions(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, RepositoryError>;
+    async fn count_transactions(&self, chat_id: i64) -> Result<i64, RepositoryError>;
+    async fn count_uncategorized_transactions(&self, chat_id: i64)
+    -> Result<i64, RepositoryError>;
+    /// The oldest uncategorized transactions of the chat.
+    async fn select_uncategorized_transactions(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, RepositoryError>;
+    /// Uncategorized transactions of the chat with the description, ignoring case.
+    async fn select_uncategorized_by_description(
+        &self,
+        chat_id: i64,
+        description: &str,
+    ) -> Result<Vec<Transaction>, RepositoryError>;
+    /// Uncategorized transactions of every chat created in `[from, to)` that no reminder
+    /// was sent for.
+    async fn select_unreminded_transactions(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transaction>, RepositoryError>;
+    async fn upsert_reminders(
+        &self,
+        transaction_ids: &[i64],
+        reminded_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError>;
+    async fn select_transactions_between(
+        &self,
+        chat_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transaction>, RepositoryError>;
+    /// Category of the latest categorized transaction with the same description.
+    async fn find_category_by_description(
+        &self,
+        chat_id: i64,
+        description: &str,
+        direction: Option<CategoryDirection>,
+    ) -> Result<Option<i64>, RepositoryError>;
     async fn update_transaction(&self, transaction: Transaction) -> Result<(), RepositoryError>;
     async fn delete_transaction(&self, chat_id: i64, id: i64) -> Result<(), RepositoryError>;
+    async fn restore_transaction(&self, chat_id: i64, id: i64) -> Result<(), RepositoryError>;
 }
 
 pub struct Transactions {
@@ -63,10 +130,12 @@ impl Repository for Transactions {
         let raw = sqlx::query_file!(
             "src/repositories/queries/create_transaction.sql",
             transaction.chat_id,
+            transaction.user_id,
             transaction.category_id,
             transaction.amount,
             transaction.description,
             transaction.currency_code.as_code_str(),
+            transaction.message_id,
             OffsetDateTime::from_unix_timestamp(transaction.created_at.timestamp()).unwrap(),
             OffsetDateTime::from_unix_timestamp(transaction.updated_at.timestamp()).unwrap()
         )
@@ -76,6 +145,37 @@ impl Repository for Transactions {
         Ok(raw.id)
     }
 
+    async fn create_transactions(
+        &self,
+        transactions: &[Transaction],
+    ) -> Result<Vec<i64>, RepositoryError> {
+        let mut tx = self.db.begin().await?;
+
+        let mut ids = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            let raw = sqlx::query_file!(
+                "src/repositories/queries/create_transaction.sql",
+                transaction.chat_id,
+                transaction.user_id,
+                transaction.category_id,
+                transaction.amount,
+                transaction.description,
+                transaction.currency_code.as_code_str(),
+                transaction.message_id,
+                OffsetDateTime::from_unix_timestamp(transaction.created_at.timestamp()).unwrap(),
+                OffsetDateTime::from_unix_timestamp(transaction.updated_at.timestamp()).unwrap()
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            ids.push(raw.id);
+        }
+
+        tx.commit().await?;
+
+        Ok(ids)
+    }
+
     async fn get_transaction(&self, chat_id: i64, id: i64) -> Result<Transaction, RepositoryError> {
         let raw = sqlx::query_file_as!(
             RawTransaction,
@@ -89,6 +189,187 @@ impl Repository for Transactions {
         Transaction::try_from(raw).map_err(|e| e.into())
     }
 
+    async fn find_transaction_by_message_id(
+        &self,
+        chat_id: i64,
+        message_id: i32,
+    ) -> Result<Option<Transaction>, RepositoryError> {
+        let raw = sqlx::query_file_as!(
+            RawTransaction,
+            "src/repositories/queries/find_transaction_by_message_id.sql",
+            chat_id,
+            message_id,
+        )
+        .fetch_optional(&self.db)
+        .await?;
+
+        raw.map(Transaction::try_from)
+            .transpose()
+            .map_err(|e| e.into())
+    }
+
+    async fn select_transactions(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, RepositoryError> {
+        let raws = sqlx::query_file_as!(
+            RawTransaction,
+            "src/repositories/queries/select_transactions.sql",
+            chat_id,
+            limit,
+            offset,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let transactions: Result<Vec<Transaction>, _> =
+            raws.into_iter().map(Transaction::try_from).collect();
+
+        transactions.map_err(|e| e.into())
+    }
+
+    async fn find_category_by_description(
+        &self,
+        chat_id: i64,
+        description: &str,
+        direction: Option<CategoryDirection>,
+    ) -> Result<Option<i64>, RepositoryError> {
+        let row = sqlx::query_file!(
+            "src/repositories/queries/find_category_by_description.sql",
+            chat_id,
+            description,
+            direction.map(i32::from)
+        )
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(row.map(|row| row.category_id))
+    }
+
+    async fn count_transactions(&self, chat_id: i64) -> Result<i64, RepositoryError> {
+        let raw = sqlx::query_file!("src/repositories/queries/count_transactions.sql", chat_id)
+            .fetch_one(&self.db)
+            .await?;
+
+        Ok(raw.count)
+    }
+
+    async fn count_uncategorized_transactions(
+        &self,
+        chat_id: i64,
+    ) -> Result<i64, RepositoryError> {
+        let raw = sqlx::query_file!(
+            "src/repositories/queries/count_uncategorized_transactions.sql",
+            chat_id
+        )
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(raw.count)
+    }
+
+    async fn select_uncategorized_transactions(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, RepositoryError> {
+        let raws = sqlx::query_file_as!(
+            RawTransaction,
+            "src/repositories/queries/select_uncategorized_transactions.sql",
+            chat_id,
+            limit,
+            offset,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let transactions: Result<Vec<Transaction>, _> =
+            raws.into_iter().map(Transaction::try_from).collect();
+
+        transactions.map_err(|e| e.into())
+    }
+
+    async fn select_uncategorized_by_description(
+        &self,
+        chat_id: i64,
+        description: &str,
+    ) -> Result<Vec<Transaction>, RepositoryError> {
+        let raws = sqlx::query_file_as!(
+            RawTransaction,
+            "src/repositories/queries/select_uncategorized_by_description.sql",
+            chat_id,
+            description,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let transactions: Result<Vec<Transaction>, _> =
+            raws.into_iter().map(Transaction::try_from).collect();
+
+        transactions.map_err(|e| e.into())
+    }
+
+    async fn select_unreminded_transactions(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transaction>, RepositoryError> {
+        let raws = sqlx::query_file_as!(
+            RawTransaction,
+            "src/repositories/queries/select_unreminded_transactions.sql",
+            OffsetDateTime::from_unix_timestamp(from.timestamp()).unwrap(),
+            OffsetDateTime::from_unix_timestamp(to.timestamp()).unwrap(),
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let transactions: Result<Vec<Transaction>, _> =
+            raws.into_iter().map(Transaction::try_from).collect();
+
+        transactions.map_err(|e| e.into())
+    }
+
+    async fn upsert_reminders(
+        &self,
+        transaction_ids: &[i64],
+        reminded_at: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query_file!(
+            "src/repositories/queries/upsert_transaction_reminders.sql",
+            transaction_ids,
+            OffsetDateTime::from_unix_timestamp(reminded_at.timestamp()).unwrap(),
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn select_transactions_between(
+        &self,
+        chat_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transaction>, RepositoryError> {
+        let raws = sqlx::query_file_as!(
+            RawTransaction,
+            "src/repositories/queries/select_transactions_between.sql",
+            chat_id,
+            OffsetDateTime::from_unix_timestamp(from.timestamp()).unwrap(),
+            OffsetDateTime::from_unix_timestamp(to.timestamp()).unwrap(),
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let transactions: Result<Vec<Transaction>, _> =
+            raws.into_iter().map(Transaction::try_from).collect();
+
+        transactions.map_err(|e| e.into())
+    }
+
     async fn update_transaction(&self, transaction: Transaction) -> Result<(), RepositoryError> {
         sqlx::query_file!(
             "src/repositories/queries/update_transaction.sql",
@@ -99,9 +380,11 @@ impl Repository for Transactions {
             transaction.amount_modified,
             transaction.description,
             transaction.currency_code.as_code_str(),
-            OffsetDateTime::from_unix_timestamp(transaction.updated_at.timestamp()).unwrap()
+            OffsetDateTime::from_unix_timestamp(transaction.updated_at.timestamp()).unwrap(),
+            OffsetDateTime::from_unix_timestamp(transaction.created_at.timestamp()).unwrap(),
+            transaction.reply_message_id
         )
-        .fetch_one(&self.db)
+        .execute(&self.db)
         .await?;
 
         Ok(())
@@ -114,7 +397,20 @@ impl Repository for Transactions {
             chat_id,
             OffsetDateTime::now_utc(),
         )
-        .fetch_one(&self.db)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn restore_transaction(&self, chat_id: i64, id: i64) -> Result<(), RepositoryError> {
+        sqlx::query_file!(
+            "src/repositories/queries/restore_transaction.sql",
+            id,
+            chat_id,
+            OffsetDateTime::now_utc(),
+        )
+        .execute(&self.db)
         .await?;
 
         Ok(())
@@ -0,0 +1,174 @@
+use crate::models::action::{Action, ActionKind, Snapshot};
+use crate::repositories::actions::Repository;
+use crate::repositories::{categories, transactions};
+use chrono::Utc;
+use std::sync::Arc;
+
+pub type ServiceError = Box<dyn std::error::Error + Sync + Send>;
+
+#[async_trait::async_trait]
+pub trait Service: Send + Sync {
+    /// Reverts the most recent not yet reverted action in the chat and returns it,
+    /// or `None` if there is nothing left to undo.
+    async fn undo_last_action(
+        &self,
+        chat_id: i64,
+        user_id: Option<i64>,
+    ) -> Result<Option<Action>, ServiceError>;
+    async fn select_actions(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Action>, ServiceError>;
+    async fn count_actions(&self, chat_id: i64) -> Result<i64, ServiceError>;
+}
+
+pub struct Actions {
+    repository: Arc<dyn Repository>,
+    transactions_repository: Arc<dyn transactions::Repository>,
+    categories_repository: Arc<dyn categories::Repository>,
+}
+
+impl Actions {
+    pub fn new(
+        repository: Arc<dyn Repository>,
+        transactions_repository: Arc<dyn transactions::Repository>,
+        categories_repository: Arc<dyn categories::Repository>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            repository,
+            transactions_repository,
+            categories_repository,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Service for Actions {
+    async fn undo_last_action(
+        &self,
+        chat_id: i64,
+        user_id: Option<i64>,
+    ) -> Result<Option<Action>, ServiceError> {
+        let Some(mut action) = self.repository.get_last_action(chat_id).await? else {
+            return Ok(None);
+        };
+
+        match (action.kind, &action.before) {
+            (ActionKind::TransactionCreated, _) => {
+                // The creation snapshot predates the bot reply, so keep the latest state.
+                let transaction = self
+                    .transactions_repository
+                    .get_transaction(chat_id, action.entity_id)
+                    .await?;
+                self.transactions_repository
+                    .delete_transaction(chat_id, action.entity_id)
+                    .await?;
+                action.after = Some(Snapshot::Transaction(transaction));
+            }
+            (ActionKind::TransactionUpdated, Some(Snapshot::Transaction(before))) => {
+                let mut transaction = before.to_owned();
+                transaction.updated_at = Utc::now();
+                self.transactions_repository
+                    .update_transaction(transaction)
+                    .await?;
+            }
+            (ActionKind::TransactionsCategorized, Some(Snapshot::Transactions(before))) => {
+                for transaction in before {
+                    let mut transaction = transaction.to_owned();
+                    transaction.updated_at = Utc::now();
+                    self.transactions_repository
+                        .update_transaction(transaction)
+                        .await?;
+                }
+            }
+            (ActionKind::TransactionsImported, _) => {
+                if let Some(Snapshot::Transactions(transactions)) = &action.after {
+                    for transaction in transactions {
+                        self.transactions_repository
+                            .delete_transaction(chat_id, transaction.id)
+                            .await?;
+                    }
+                }
+            }
+            (ActionKind::TransactionDeleted, _) => {
+                self.transactions_repository
+                    .restore_transaction(chat_id, action.entity_id)
+                    .await?;
+            }
+            (ActionKind::CategoryCreated, _) => {
+                self.categories_repository
+                    .delete_category(chat_id, action.entity_id)
+                    .await?;
+            }
+            (ActionKind::CategoriesCreated, _) => {
+                if let Some(Snapshot::Categories(categories)) = &action.after {
+                    for category in categories {
+                        self.categories_repository
+                            .delete_category(chat_id, category.id)
+                            .await?;
+                    }
+                }
+            }
+            (ActionKind::CategoryUpdated, Some(Snapshot::Category(before))) => {
+                let mut category = before.to_owned();
+                category.updated_at = Utc::now();
+                self.categories_repository.update_category(category).await?;
+            }
+            (ActionKind::CategoryDeleted, _) => {
+                self.categories_repository
+                    .restore_category(chat_id, action.entity_id)
+                    .await?;
+            }
+            (
+                ActionKind::CategoryMerged,
+                Some(Snapshot::Merge {
+                    source,
+                    target,
+                    subcategory_ids,
+                    transaction_ids,
+                }),
+            ) => {
+                let mut target = target.to_owned();
+                target.updated_at = Utc::now();
+                self.categories_repository
+                    .unmerge_categories(
+                        chat_id,
+                        source.id,
+                        target,
+                        subcategory_ids,
+                        transaction_ids,
+                    )
+                    .await?;
+            }
+            (ActionKind::CategoryRestored, _) => {
+                self.categories_repository
+                    .delete_category(chat_id, action.entity_id)
+                    .await?;
+            }
+            (kind, _) => {
+                return Err(format!("missing snapshot to revert action kind {kind:?}").into());
+            }
+        }
+
+        self.repository
+            .revert_action(chat_id, action.id, user_id)
+            .await?;
+
+        Ok(Some(action))
+    }
+
+    async fn select_actions(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Action>, ServiceError> {
+        self.repository.select_actions(chat_id, limit, offset).await
+    }
+
+    async fn count_actions(&self, chat_id: i64) -> Result<i64, ServiceError> {
+        self.repository.count_actions(chat_id).await
+    }
+}
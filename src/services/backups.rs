@@ -0,0 +1,193 @@
+use crate::models::backup::{BACKUP_VERSION, Backup, MIN_BACKUP_VERSION};
+use crate::proto::callback::v1::CategoryDirection;
+use crate::repositories::backups::Repository;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+pub type ServiceError = Box<dyn std::error::Error + Sync + Send>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RestoreResult {
+    Restored {
+        categories: usize,
+        transactions: usize,
+    },
+    /// The chat already has data, restoring would mix two budgets.
+    NotEmpty,
+    /// The file is not a backup this version can load.
+    Invalid(String),
+}
+
+#[async_trait::async_trait]
+pub trait Service: Send + Sync {
+    /// Everything the chat keeps in the bot as a JSON file.
+    async fn backup(&self, chat_id: i64) -> Result<Vec<u8>, ServiceError>;
+    /// Loads a file made by [`Service::backup`] into an empty chat.
+    async fn restore(&self, chat_id: i64, data: &[u8]) -> Result<RestoreResult, ServiceError>;
+}
+
+pub struct Backups {
+    repository: Arc<dyn Repository>,
+}
+
+impl Backups {
+    pub fn new(repository: Arc<dyn Repository>) -> Arc<Self> {
+        Arc::new(Self { repository })
+    }
+}
+
+/// Checks that the backup is complete: every reference points at something inside it.
+fn validate(backup: &Backup) -> Result<(), String> {
+    if !(MIN_BACKUP_VERSION..=BACKUP_VERSION).contains(&backup.version) {
+        return Err(format!(
+            "Версия резервной копии {} не поддерживается, ожидается от {MIN_BACKUP_VERSION} до {BACKUP_VERSION}.",
+            backup.version
+        ));
+    }
+
+    let mut category_ids = HashSet::new();
+    let mut category_keys = HashSet::new();
+    for category in &backup.categories {
+        if !category_ids.insert(category.id) {
+            return Err(format!("Категория {} встречается дважды.", category.id));
+        }
+        if !category_keys.insert((&category.name, &category.label)) {
+            return Err(format!(
+                "Категория «{} {}» встречается дважды.",
+                category.label, category.name
+            ));
+        }
+        if category.direction == CategoryDirection::Unspecified {
+            return Err(format!("У категории «{}» нет направления.", category.name));
+        }
+    }
+
+    let mut transaction_ids = HashSet::new();
+    for transaction in &backup.transactions {
+        if !transaction_ids.insert(transaction.id) {
+            return Err(format!("Операция {} встречается дважды.", transaction.id));
+        }
+        if let Some(category_id) = transaction.category_id
+            && !category_ids.contains(&category_id)
+        {
+            return Err(format!(
+                "Операция {} ссылается на неизвестную категорию {category_id}.",
+                transaction.id
+            ));
+        }
+    }
+
+    if let Some(participant) = backup
+        .participants
+        .iter()
+        .find(|participant| !transaction_ids.contains(&participant.transaction_id))
+    {
+        return Err(format!(
+            "Участник {} ссылается на неизвестную операцию {}.",
+            participant.user_id, participant.transaction_id
+        ));
+    }
+
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl Service for Backups {
+    async fn backup(&self, chat_id: i64) -> Result<Vec<u8>, ServiceError> {
+        let backup = self.repository.select_backup(chat_id).await?;
+
+        Ok(serde_json::to_vec_pretty(&backup)?)
+    }
+
+    async fn restore(&self, chat_id: i64, data: &[u8]) -> Result<RestoreResult, ServiceError> {
+        let backup: Backup = match serde_json::from_slice(data) {
+            Ok(backup) => backup,
+            Err(err) => {
+                return Ok(RestoreResult::Invalid(format!(
+                    "Файл не похож на резервную копию: {err}."
+                )));
+            }
+        };
+
+        if let Err(reason) = validate(&backup) {
+            return Ok(RestoreResult::Invalid(reason));
+        }
+
+        if !self.repository.is_chat_empty(chat_id).await? {
+            return Ok(RestoreResult::NotEmpty);
+        }
+
+        let result = RestoreResult::Restored {
+            categories: backup.categories.len(),
+            transactions: backup.transactions.len(),
+        };
+        self.repository.restore_backup(chat_id, backup).await?;
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::backup::Participant;
+    use chrono::Utc;
+
+    fn backup() -> Backup {
+        Backup {
+            version: BACKUP_VERSION,
+            chat_id: 1,
+            created_at: Utc::now(),
+            categories: Vec::new(),
+            transactions: Vec::new(),
+            participants: Vec::new(),
+            settlements: Vec::new(),
+            members: Vec::new(),
+            settings: None,
+        }
+    }
+
+    #[test]
+    fn accepts_empty_backup() {
+        assert!(validate(&backup()).is_ok());
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut backup = backup();
+        backup.version = BACKUP_VERSION + 1;
+
+        assert!(validate(&backup).is_err());
+    }
+
+    #[test]
+    fn accepts_backups_without_settings() {
+        let backup: Backup = serde_json::from_str(
+            r#"{
+                "version": 1,
+                "chat_id": 1,
+                "created_at": "2025-12-01T09:00:00Z",
+                "categories": [],
+                "transactions": [],
+                "participants": [],
+                "settlements": [],
+                "members": []
+            }"#,
+        )
+        .unwrap();
+
+        assert!(backup.settings.is_none());
+        assert!(validate(&backup).is_ok());
+    }
+
+    #[test]
+    fn rejects_dangling_participants() {
+        let mut backup = backup();
+        backup.participants.push(Participant {
+            transaction_id: 42,
+            user_id: 7,
+        });
+
+        assert!(validate(&backup).is_err());
+    }
+}
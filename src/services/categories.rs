@@ -1,23 +1,145 @@
-use crate::models::category::Category;
-use crate::proto::callback::v1::CategoryDirection;
-use crate::repositories::categories::Repository;
-use chrono::{DateTime, Utc};
+use crate::models::action::{Action, ActionKind, Snapshot};
+use crate::models::category::{Category, MAX_NAME_LENGTH, is_valid_label, siblings, subcategories};
+use crate::proto::callback::v1::move_category::Shift;
+use crate::proto::callback::v1::{CategoryDirection, CategorySort};
+use crate::repositories::categories::{Repository, RepositoryError};
+use crate::repositories::{actions, settings};
+use chrono::{TimeDelta, Utc};
+use std::collections::HashMap;
 use std::sync::Arc;
 
-pub type ServiceError = Box<dyn std::error::Error + Sync + Send>;
+#[derive(Debug)]
+pub enum ServiceError {
+    /// A category with the same name and label already exists, possibly in the archive.
+    Duplicate,
+    NotFound,
+    Validation(ValidationError),
+    Repository(RepositoryError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationError {
+    EmptyName,
+    LongName,
+    InvalidLabel,
+    /// A category cannot be its own parent or be merged into itself.
+    SelfReference,
+    /// Only one level of subcategories is allowed.
+    NestedSubcategory,
+    /// Subcategories and merged categories must share the direction.
+    DirectionMismatch,
+}
+
+impl std::fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServiceError::Duplicate => write!(
+                f,
+                "Категория с таким названием и ярлыком уже есть, возможно, в архиве."
+            ),
+            ServiceError::NotFound => write!(f, "Категория не найдена, возможно, её уже удалили."),
+            ServiceError::Validation(error) => write!(f, "{error}"),
+            ServiceError::Repository(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::EmptyName => write!(f, "Название категории не может быть пустым."),
+            ValidationError::LongName => write!(
+                f,
+                "Название категории должно быть не длиннее {MAX_NAME_LENGTH} символов."
+            ),
+            ValidationError::InvalidLabel => {
+                write!(f, "Ярлык должен быть одним эмодзи, например: 🛒 Продукты")
+            }
+            ValidationError::SelfReference => {
+                write!(
+                    f,
+                    "Категорию нельзя вложить в саму себя или объединить с собой."
+                )
+            }
+            ValidationError::NestedSubcategory => {
+                write!(f, "У подкатегорий не может быть своих подкатегорий.")
+            }
+            ValidationError::DirectionMismatch => {
+                write!(f, "Категории расходов и доходов нельзя смешивать.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ServiceError::Repository(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<ValidationError> for ServiceError {
+    fn from(error: ValidationError) -> Self {
+        ServiceError::Validation(error)
+    }
+}
+
+impl From<RepositoryError> for ServiceError {
+    fn from(error: RepositoryError) -> Self {
+        match error.downcast_ref::<sqlx::Error>() {
+            Some(sqlx::Error::RowNotFound) => ServiceError::NotFound,
+            Some(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+                ServiceError::Duplicate
+            }
+            _ => ServiceError::Repository(error),
+        }
+    }
+}
+
+fn validate(name: &str, label: &str) -> Result<(), ValidationError> {
+    validate_name(name)?;
+    validate_label(label)
+}
+
+fn validate_name(name: &str) -> Result<(), ValidationError> {
+    if name.trim().is_empty() {
+        return Err(ValidationError::EmptyName);
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(ValidationError::LongName);
+    }
+
+    Ok(())
+}
+
+fn validate_label(label: &str) -> Result<(), ValidationError> {
+    if !is_valid_label(label) {
+        return Err(ValidationError::InvalidLabel);
+    }
+
+    Ok(())
+}
+
+/// Transactions of this many last days decide the order of categories sorted by usage.
+const USAGE_PERIOD_DAYS: i64 = 90;
 
 pub struct CreateCategoryRequest {
     pub chat_id: i64,
+    pub user_id: Option<i64>,
     pub name: String,
     pub label: String,
     pub direction: CategoryDirection,
     pub is_regular: bool,
     pub target_amount: Option<i64>,
+    pub parent_id: Option<i64>,
 }
 
 pub struct UpdateCategoryRequest {
     pub id: i64,
     pub chat_id: i64,
+    pub user_id: Option<i64>,
     pub name: Option<String>,
     pub label: Option<String>,
     pub direction: Option<CategoryDirection>,
@@ -31,6 +153,13 @@ pub trait Service: Send + Sync {
         &self,
         request: CreateCategoryRequest,
     ) -> Result<Category, ServiceError>;
+    /// Creates categories of a single chat in bulk, skipping those whose name is already
+    /// taken by a category of the same direction, and logs them as a single action.
+    /// Returns the created ones.
+    async fn create_categories(
+        &self,
+        requests: Vec<CreateCategoryRequest>,
+    ) -> Result<Vec<Category>, ServiceError>;
     async fn update_category(
         &self,
         request: UpdateCategoryRequest,
@@ -41,34 +170,101 @@ pub trait Service: Send + Sync {
         chat_id: i64,
         direction: CategoryDirection,
     ) -> Result<Vec<Category>, ServiceError>;
-    async fn delete_category(&self, chat_id: i64, id: i64) -> Result<(), ServiceError>;
+    /// Categories in the order they are offered for a transaction, which depends on the
+    /// chat's settings.
+    async fn select_picker_categories(
+        &self,
+        chat_id: i64,
+        direction: CategoryDirection,
+    ) -> Result<Vec<Category>, ServiceError>;
+    /// Categories of both directions, archived ones included to name older transactions.
+    async fn select_all_categories(&self, chat_id: i64) -> Result<Vec<Category>, ServiceError>;
+    /// Deleted categories, most recently deleted first.
+    async fn select_archived_categories(
+        &self,
+        chat_id: i64,
+        direction: CategoryDirection,
+    ) -> Result<Vec<Category>, ServiceError>;
+    async fn delete_category(
+        &self,
+        chat_id: i64,
+        id: i64,
+        user_id: Option<i64>,
+    ) -> Result<(), ServiceError>;
+    async fn restore_category(
+        &self,
+        chat_id: i64,
+        id: i64,
+        user_id: Option<i64>,
+    ) -> Result<Category, ServiceError>;
+    /// Moves the category under another top-level category of the same direction, or back
+    /// to the top level with `None`.
+    async fn update_parent(
+        &self,
+        chat_id: i64,
+        id: i64,
+        parent_id: Option<i64>,
+        user_id: Option<i64>,
+    ) -> Result<Category, ServiceError>;
+    /// Merges the category into another one of the same direction: its transactions and
+    /// subcategories go to the target, the limits add up and the category is archived.
+    /// Undoing brings the archived category back together with what was moved.
+    async fn merge_category(
+        &self,
+        chat_id: i64,
+        id: i64,
+        target_id: i64,
+        user_id: Option<i64>,
+    ) -> Result<Category, ServiceError>;
+    async fn count_transactions(&self, chat_id: i64, id: i64) -> Result<i64, ServiceError>;
+    /// Swaps the category with its neighbour among the categories listed next to it.
+    /// Returns `false` if it is already the first or the last one.
+    async fn move_category(
+        &self,
+        chat_id: i64,
+        id: i64,
+        shift: Shift,
+    ) -> Result<bool, ServiceError>;
 }
 
 pub struct Categories {
     repository: Arc<dyn Repository>,
+    actions_repository: Arc<dyn actions::Repository>,
+    settings_repository: Arc<dyn settings::Repository>,
 }
 
 impl Categories {
-    pub fn new(repository: Arc<dyn Repository>) -> Arc<Self> {
-        Arc::new(Self { repository })
+    pub fn new(
+        repository: Arc<dyn Repository>,
+        actions_repository: Arc<dyn actions::Repository>,
+        settings_repository: Arc<dyn settings::Repository>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            repository,
+            actions_repository,
+            settings_repository,
+        })
     }
-}
 
-#[async_trait::async_trait]
-impl Service for Categories {
-    async fn create_category(
+    /// Validates and saves a new category without logging an action.
+    async fn insert_category(
         &self,
         request: CreateCategoryRequest,
     ) -> Result<Category, ServiceError> {
-        let now: DateTime<Utc> = Default::default();
+        let name = request.name.trim().to_string();
+        validate(&name, &request.label)?;
+
+        let now = Utc::now();
         let mut category = Category {
             id: -1,
             chat_id: request.chat_id,
-            name: request.name,
+            name,
             label: request.label,
             direction: request.direction,
             is_regular: request.is_regular,
             target_amount: request.target_amount,
+            parent_id: request.parent_id,
+            position: 0,
             created_at: now,
             updated_at: now,
             deleted_at: None,
@@ -77,20 +273,100 @@ impl Service for Categories {
 
         Ok(category)
     }
+}
+
+#[async_trait::async_trait]
+impl Service for Categories {
+    async fn create_category(
+        &self,
+        request: CreateCategoryRequest,
+    ) -> Result<Category, ServiceError> {
+        let user_id = request.user_id;
+        let category = self.insert_category(request).await?;
+
+        self.actions_repository
+            .create_action(Action::new(
+                ActionKind::CategoryCreated,
+                user_id,
+                None,
+                Some(Snapshot::Category(category.to_owned())),
+            ))
+            .await?;
+
+        Ok(category)
+    }
+
+    async fn create_categories(
+        &self,
+        requests: Vec<CreateCategoryRequest>,
+    ) -> Result<Vec<Category>, ServiceError> {
+        let Some((chat_id, user_id)) = requests
+            .first()
+            .map(|request| (request.chat_id, request.user_id))
+        else {
+            return Ok(Vec::new());
+        };
+        let mut categories = self.select_all_categories(chat_id).await?;
+        let mut created = Vec::new();
+
+        for request in requests {
+            // Archived categories keep their name and label reserved.
+            let is_taken = categories.iter().any(|category| {
+                let is_same_name = category.name.to_lowercase() == request.name.to_lowercase();
+                (category.deleted_at.is_none()
+                    && category.direction == request.direction
+                    && is_same_name)
+                    || (is_same_name && category.label == request.label)
+            });
+            if is_taken {
+                continue;
+            }
+
+            let category = self.insert_category(request).await?;
+            categories.push(category.to_owned());
+            created.push(category);
+        }
+
+        if !created.is_empty() {
+            self.actions_repository
+                .create_action(Action::new(
+                    ActionKind::CategoriesCreated,
+                    user_id,
+                    None,
+                    Some(Snapshot::Categories(created.to_owned())),
+                ))
+                .await?;
+        }
+
+        Ok(created)
+    }
 
     async fn update_category(
         &self,
         request: UpdateCategoryRequest,
     ) -> Result<Category, ServiceError> {
         let mut category = self.get_category(request.chat_id, request.id).await?;
+        let before = category.to_owned();
 
+        // Categories saved before the checks existed stay editable.
         if let Some(name) = request.name {
-            category.name = name;
+            validate_name(&name)?;
+            category.name = name.trim().to_string();
         }
         if let Some(label) = request.label {
+            validate_label(&label)?;
             category.label = label;
         }
-        if let Some(direction) = request.direction {
+        if let Some(direction) = request.direction
+            && direction != category.direction
+        {
+            // Subcategories share the direction of their parent.
+            let categories = self
+                .select_categories(request.chat_id, category.direction)
+                .await?;
+            if category.parent_id.is_some() || !subcategories(&categories, category.id).is_empty() {
+                return Err(ValidationError::DirectionMismatch.into());
+            }
             category.direction = direction;
         }
         if let Some(is_regular) = request.is_regular {
@@ -99,15 +375,23 @@ impl Service for Categories {
         if let Some(target_amount) = request.target_amount {
             category.target_amount = Some(target_amount);
         }
-
         category.updated_at = Utc::now();
         self.repository.update_category(category.to_owned()).await?;
 
+        self.actions_repository
+            .create_action(Action::new(
+                ActionKind::CategoryUpdated,
+                request.user_id,
+                Some(Snapshot::Category(before)),
+                Some(Snapshot::Category(category.to_owned())),
+            ))
+            .await?;
+
         Ok(category)
     }
 
     async fn get_category(&self, chat_id: i64, id: i64) -> Result<Category, ServiceError> {
-        self.repository.get_category(chat_id, id).await
+        Ok(self.repository.get_category(chat_id, id).await?)
     }
 
     async fn select_categories(
@@ -115,10 +399,248 @@ impl Service for Categories {
         chat_id: i64,
         direction: CategoryDirection,
     ) -> Result<Vec<Category>, ServiceError> {
-        self.repository.select_categories(chat_id, direction).await
+        Ok(self
+            .repository
+            .select_categories(chat_id, direction)
+            .await?)
+    }
+
+    async fn select_picker_categories(
+        &self,
+        chat_id: i64,
+        direction: CategoryDirection,
+    ) -> Result<Vec<Category>, ServiceError> {
+        let mut categories = self.select_categories(chat_id, direction).await?;
+
+        let category_sort = self
+            .settings_repository
+            .find_settings(chat_id)
+            .await?
+            .map(|settings| settings.category_sort)
+            .unwrap_or(CategorySort::Unspecified);
+        if category_sort != CategorySort::Usage {
+            return Ok(categories);
+        }
+
+        let usage = self
+            .repository
+            .select_usage(chat_id, Utc::now() - TimeDelta::days(USAGE_PERIOD_DAYS))
+            .await?;
+        // A parent is as used as it is together with its subcategories.
+        let weights: HashMap<i64, i64> = categories
+            .iter()
+            .map(|category| {
+                let weight = std::iter::once(category)
+                    .chain(subcategories(&categories, category.id))
+                    .filter_map(|category| usage.get(&category.id))
+                    .sum();
+                (category.id, weight)
+            })
+            .collect();
+        categories.sort_by_key(|category| -weights.get(&category.id).copied().unwrap_or_default());
+
+        Ok(categories)
+    }
+
+    async fn select_all_categories(&self, chat_id: i64) -> Result<Vec<Category>, ServiceError> {
+        Ok(self.repository.select_all_categories(chat_id).await?)
+    }
+
+    async fn select_archived_categories(
+        &self,
+        chat_id: i64,
+        direction: CategoryDirection,
+    ) -> Result<Vec<Category>, ServiceError> {
+        let mut categories: Vec<Category> = self
+            .select_all_categories(chat_id)
+            .await?
+            .into_iter()
+            .filter(|category| category.direction == direction && category.deleted_at.is_some())
+            .collect();
+        categories.sort_by_key(|category| std::cmp::Reverse(category.deleted_at));
+
+        Ok(categories)
+    }
+
+    async fn delete_category(
+        &self,
+        chat_id: i64,
+        id: i64,
+        user_id: Option<i64>,
+    ) -> Result<(), ServiceError> {
+        let category = self.get_category(chat_id, id).await?;
+        self.repository.delete_category(chat_id, id).await?;
+
+        self.actions_repository
+            .create_action(Action::new(
+                ActionKind::CategoryDeleted,
+                user_id,
+                Some(Snapshot::Category(category)),
+                None,
+            ))
+            .await?;
+
+        Ok(())
     }
 
-    async fn delete_category(&self, chat_id: i64, id: i64) -> Result<(), ServiceError> {
-        self.repository.delete_category(chat_id, id).await
+    async fn restore_category(
+        &self,
+        chat_id: i64,
+        id: i64,
+        user_id: Option<i64>,
+    ) -> Result<Category, ServiceError> {
+        self.repository.restore_category(chat_id, id).await?;
+        let category = self.get_category(chat_id, id).await?;
+
+        self.actions_repository
+            .create_action(Action::new(
+                ActionKind::CategoryRestored,
+                user_id,
+                None,
+                Some(Snapshot::Category(category.to_owned())),
+            ))
+            .await?;
+
+        Ok(category)
+    }
+
+    async fn update_parent(
+        &self,
+        chat_id: i64,
+        id: i64,
+        parent_id: Option<i64>,
+        user_id: Option<i64>,
+    ) -> Result<Category, ServiceError> {
+        let mut category = self.get_category(chat_id, id).await?;
+        let before = category.to_owned();
+
+        if let Some(parent_id) = parent_id {
+            let parent = self.get_category(chat_id, parent_id).await?;
+            if parent.id == category.id {
+                return Err(ValidationError::SelfReference.into());
+            }
+            if parent.parent_id.is_some() {
+                return Err(ValidationError::NestedSubcategory.into());
+            }
+            if parent.direction != category.direction {
+                return Err(ValidationError::DirectionMismatch.into());
+            }
+
+            let categories = self.select_categories(chat_id, category.direction).await?;
+            if !subcategories(&categories, category.id).is_empty() {
+                return Err(ValidationError::NestedSubcategory.into());
+            }
+        }
+
+        category.parent_id = parent_id;
+        category.updated_at = Utc::now();
+        self.repository.update_category(category.to_owned()).await?;
+
+        self.actions_repository
+            .create_action(Action::new(
+                ActionKind::CategoryUpdated,
+                user_id,
+                Some(Snapshot::Category(before)),
+                Some(Snapshot::Category(category.to_owned())),
+            ))
+            .await?;
+
+        Ok(category)
+    }
+
+    async fn merge_category(
+        &self,
+        chat_id: i64,
+        id: i64,
+        target_id: i64,
+        user_id: Option<i64>,
+    ) -> Result<Category, ServiceError> {
+        let source = self.get_category(chat_id, id).await?;
+        let mut target = self.get_category(chat_id, target_id).await?;
+        let before = target.to_owned();
+
+        if source.id == target.id {
+            return Err(ValidationError::SelfReference.into());
+        }
+        if source.direction != target.direction {
+            return Err(ValidationError::DirectionMismatch.into());
+        }
+
+        let categories = self.select_categories(chat_id, source.direction).await?;
+        let subcategory_ids: Vec<i64> = subcategories(&categories, source.id)
+            .into_iter()
+            .map(|category| category.id)
+            .filter(|id| *id != target.id)
+            .collect();
+        if target.parent_id == Some(source.id) {
+            // The target takes the place of its parent.
+            target.parent_id = source.parent_id;
+        } else if target.parent_id.is_some() && !subcategory_ids.is_empty() {
+            return Err(ValidationError::NestedSubcategory.into());
+        }
+
+        let amounts: Vec<i64> = [source.target_amount, target.target_amount]
+            .into_iter()
+            .flatten()
+            .filter(|amount| *amount != 0)
+            .collect();
+        target.target_amount = (!amounts.is_empty()).then(|| amounts.iter().sum());
+        target.updated_at = Utc::now();
+
+        let transaction_ids = self
+            .repository
+            .merge_categories(chat_id, source.id, target.to_owned())
+            .await?;
+
+        self.actions_repository
+            .create_action(Action::new(
+                ActionKind::CategoryMerged,
+                user_id,
+                Some(Snapshot::Merge {
+                    source,
+                    target: before,
+                    subcategory_ids,
+                    transaction_ids,
+                }),
+                Some(Snapshot::Category(target.to_owned())),
+            ))
+            .await?;
+
+        Ok(target)
+    }
+
+    async fn count_transactions(&self, chat_id: i64, id: i64) -> Result<i64, ServiceError> {
+        Ok(self.repository.count_transactions(chat_id, id).await?)
+    }
+
+    async fn move_category(
+        &self,
+        chat_id: i64,
+        id: i64,
+        shift: Shift,
+    ) -> Result<bool, ServiceError> {
+        let category = self.get_category(chat_id, id).await?;
+        let categories = self.select_categories(chat_id, category.direction).await?;
+        let mut ids: Vec<i64> = siblings(&categories, &category)
+            .into_iter()
+            .map(|category| category.id)
+            .collect();
+
+        let Some(index) = ids.iter().position(|id| *id == category.id) else {
+            return Ok(false);
+        };
+        let neighbour = match shift {
+            Shift::Up => index.checked_sub(1),
+            Shift::Down => Some(index + 1).filter(|neighbour| *neighbour < ids.len()),
+            Shift::Unspecified => None,
+        };
+        let Some(neighbour) = neighbour else {
+            return Ok(false);
+        };
+
+        ids.swap(index, neighbour);
+        self.repository.update_positions(chat_id, &ids).await?;
+
+        Ok(true)
     }
 }
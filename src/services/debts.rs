@@ -0,0 +1,215 @@
+use crate::models::debt::{Settlement, Transfer};
+use crate::models::transaction::CurrencyCode;
+use crate::repositories::debts::Repository;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub type ServiceError = Box<dyn std::error::Error + Sync + Send>;
+
+#[async_trait::async_trait]
+pub trait Service: Send + Sync {
+    /// Splits a transaction equally between the payer and the given members, replacing its
+    /// earlier split. Without other members the transaction is no longer split.
+    async fn split_transaction(
+        &self,
+        transaction_id: i64,
+        payer_id: i64,
+        user_ids: Vec<i64>,
+    ) -> Result<(), ServiceError>;
+    /// The smallest set of transfers that settles all debts in the chat.
+    async fn settle_up(&self, chat_id: i64) -> Result<Vec<Transfer>, ServiceError>;
+    /// Records a transfer as paid. Returns `false` if it is no longer part of the settle-up,
+    /// e.g. because someone has already marked it.
+    async fn settle_debt(&self, chat_id: i64, transfer: Transfer) -> Result<bool, ServiceError>;
+}
+
+pub struct Debts {
+    repository: Arc<dyn Repository>,
+}
+
+impl Debts {
+    pub fn new(repository: Arc<dyn Repository>) -> Arc<Self> {
+        Arc::new(Self { repository })
+    }
+
+    /// Net balance of every member per currency: positive if the member is owed money.
+    async fn balances(
+        &self,
+        chat_id: i64,
+    ) -> Result<HashMap<CurrencyCode, HashMap<i64, i64>>, ServiceError> {
+        let mut balances: HashMap<CurrencyCode, HashMap<i64, i64>> = HashMap::new();
+
+        for split in self.repository.select_splits(chat_id).await? {
+            let balances = balances.entry(split.currency_code).or_default();
+
+            for (participant, share) in shares(split.amount, &split.participants) {
+                if participant != split.payer_id {
+                    *balances.entry(participant).or_default() -= share;
+                    *balances.entry(split.payer_id).or_default() += share;
+                }
+            }
+        }
+
+        for settlement in self.repository.select_settlements(chat_id).await? {
+            let balances = balances.entry(settlement.currency_code).or_default();
+            *balances.entry(settlement.from_user_id).or_default() += settlement.amount;
+            *balances.entry(settlement.to_user_id).or_default() -= settlement.amount;
+        }
+
+        Ok(balances)
+    }
+}
+
+/// Divides the amount between the participants. The minor units left over go one each to
+/// the participants with the smallest ids, so the shares always add up to the amount.
+fn shares(amount: i64, participants: &[i64]) -> Vec<(i64, i64)> {
+    let mut participants = participants.to_vec();
+    participants.sort_unstable();
+    let count = participants.len() as i64;
+    if count == 0 {
+        return Vec::new();
+    }
+
+    participants
+        .into_iter()
+        .enumerate()
+        .map(|(index, participant)| {
+            let extra = i64::from((index as i64) < amount % count);
+            (participant, amount / count + extra)
+        })
+        .collect()
+}
+
+/// Greedily pairs the largest debtor with the largest creditor, which needs at most
+/// one transfer less than there are members with a non-zero balance.
+fn minimal_transfers(balances: &HashMap<i64, i64>) -> Vec<(i64, i64, i64)> {
+    let mut debtors: Vec<(i64, i64)> = balances
+        .iter()
+        .filter(|(_, balance)| **balance < 0)
+        .map(|(user_id, balance)| (*user_id, -balance))
+        .collect();
+    let mut creditors: Vec<(i64, i64)> = balances
+        .iter()
+        .filter(|(_, balance)| **balance > 0)
+        .map(|(user_id, balance)| (*user_id, *balance))
+        .collect();
+    debtors.sort_by_key(|(user_id, amount)| (-amount, *user_id));
+    creditors.sort_by_key(|(user_id, amount)| (-amount, *user_id));
+
+    let mut transfers = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < debtors.len() && j < creditors.len() {
+        let amount = debtors[i].1.min(creditors[j].1);
+        transfers.push((debtors[i].0, creditors[j].0, amount));
+
+        debtors[i].1 -= amount;
+        creditors[j].1 -= amount;
+        if debtors[i].1 == 0 {
+            i += 1;
+        }
+        if creditors[j].1 == 0 {
+            j += 1;
+        }
+    }
+
+    transfers
+}
+
+#[async_trait::async_trait]
+impl Service for Debts {
+    async fn split_transaction(
+        &self,
+        transaction_id: i64,
+        payer_id: i64,
+        user_ids: Vec<i64>,
+    ) -> Result<(), ServiceError> {
+        let mut participants = vec![payer_id];
+        for user_id in user_ids {
+            if !participants.contains(&user_id) {
+                participants.push(user_id);
+            }
+        }
+
+        if participants.len() < 2 {
+            participants.clear();
+        }
+
+        self.repository
+            .replace_participants(transaction_id, participants)
+            .await
+    }
+
+    async fn settle_up(&self, chat_id: i64) -> Result<Vec<Transfer>, ServiceError> {
+        let balances = self.balances(chat_id).await?;
+
+        Ok(CurrencyCode::ALL
+            .iter()
+            .filter_map(|currency_code| {
+                balances
+                    .get(currency_code)
+                    .map(|balances| (currency_code, balances))
+            })
+            .flat_map(|(currency_code, balances)| {
+                minimal_transfers(balances)
+                    .into_iter()
+                    .map(|(from_user_id, to_user_id, amount)| Transfer {
+                        from_user_id,
+                        to_user_id,
+                        amount,
+                        currency_code: *currency_code,
+                    })
+            })
+            .collect())
+    }
+
+    async fn settle_debt(&self, chat_id: i64, transfer: Transfer) -> Result<bool, ServiceError> {
+        if !self.settle_up(chat_id).await?.contains(&transfer) {
+            return Ok(false);
+        }
+
+        self.repository
+            .create_settlement(Settlement {
+                id: 0,
+                chat_id,
+                from_user_id: transfer.from_user_id,
+                to_user_id: transfer.to_user_id,
+                amount: transfer.amount,
+                currency_code: transfer.currency_code,
+                created_at: Utc::now(),
+            })
+            .await?;
+
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gives_remainder_to_first_participants() {
+        assert_eq!(
+            shares(10000, &[3, 1, 2]),
+            vec![(1, 3334), (2, 3333), (3, 3333)]
+        );
+        assert_eq!(shares(10001, &[2, 1]), vec![(1, 5001), (2, 5000)]);
+        assert_eq!(shares(10000, &[]), vec![]);
+    }
+
+    #[test]
+    fn settles_chain_with_single_transfer() {
+        // 1 owes 2, 2 owes 3 the same amount: 1 can pay 3 directly.
+        let balances = HashMap::from([(1, -500), (2, 0), (3, 500)]);
+
+        assert_eq!(minimal_transfers(&balances), vec![(1, 3, 500)]);
+    }
+
+    #[test]
+    fn splits_debt_between_creditors() {
+        let balances = HashMap::from([(1, -1000), (2, 600), (3, 400)]);
+
+        assert_eq!(minimal_transfers(&balances), vec![(1, 2, 600), (1, 3, 400)]);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::dates;
+use crate::models::category::Category;
+use crate::models::digest::Digest;
+use crate::models::report::{Totals, roll_up};
+use crate::models::settings::ChatSettings;
+use crate::proto::callback::v1::{CategoryDirection, DigestFrequency};
+use crate::repositories::{categories, settings, transactions};
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveTime, Utc, Weekday};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub type ServiceError = Box<dyn std::error::Error + Sync + Send>;
+
+/// Expense categories listed in a digest.
+const TOP_CATEGORIES: usize = 3;
+
+#[async_trait::async_trait]
+pub trait Service: Send + Sync {
+    /// Settings of the chats whose digest should be sent at `now`.
+    async fn due_digests(&self, now: DateTime<Utc>) -> Result<Vec<ChatSettings>, ServiceError>;
+    /// The digest for the local days before `now`, `None` if there is nothing to report.
+    async fn digest(
+        &self,
+        settings: &ChatSettings,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Digest>, ServiceError>;
+    async fn mark_sent(&self, chat_id: i64, sent_at: DateTime<Utc>) -> Result<(), ServiceError>;
+}
+
+pub struct Digests {
+    settings_repository: Arc<dyn settings::Repository>,
+    transactions_repository: Arc<dyn transactions::Repository>,
+    categories_repository: Arc<dyn categories::Repository>,
+}
+
+impl Digests {
+    pub fn new(
+        settings_repository: Arc<dyn settings::Repository>,
+        transactions_repository: Arc<dyn transactions::Repository>,
+        categories_repository: Arc<dyn categories::Repository>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            settings_repository,
+            transactions_repository,
+            categories_repository,
+        })
+    }
+}
+
+/// Daily digests go out every day and weekly ones on Mondays, once the send hour has come
+/// and unless one was already sent since then. Days and hours are the chat's local ones.
+fn is_due(settings: &ChatSettings, now: DateTime<Utc>) -> bool {
+    let today = dates::local_date(settings.timezone, now);
+    match settings.digest_frequency {
+        DigestFrequency::Daily => {}
+        DigestFrequency::Weekly if today.weekday() == Weekday::Mon => {}
+        _ => return false,
+    }
+
+    let Some(scheduled_at) = NaiveTime::from_hms_opt(settings.digest_hour, 0, 0)
+        .map(|time| dates::to_utc(settings.timezone, today.and_time(time)))
+    else {
+        return false;
+    };
+
+    now >= scheduled_at
+        && settings
+            .digest_sent_at
+            .is_none_or(|sent_at| sent_at < scheduled_at)
+}
+
+fn days_in_month(date: NaiveDate) -> u32 {
+    let first = date.with_day(1).unwrap_or(date);
+    let next = first
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(first);
+
+    (next - first).num_days().max(1) as u32
+}
+
+#[async_trait::async_trait]
+impl Service for Digests {
+    async fn due_digests(&self, now: DateTime<Utc>) -> Result<Vec<ChatSettings>, ServiceError> {
+        Ok(self
+            .settings_repository
+            .select_digest_settings()
+            .await?
+            .into_iter()
+            .filter(|settings| is_due(settings, now))
+            .collect())
+    }
+
+    async fn digest(
+        &self,
+        settings: &ChatSettings,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Digest>, ServiceError> {
+        let (chat_id, frequency, timezone) = (
+            settings.chat_id,
+            settings.digest_frequency,
+            settings.timezone,
+        );
+        let start_of_day = |date: NaiveDate| dates::start_of_day(timezone, date);
+        let today = dates::local_date(timezone, now);
+        let yesterday = today.checked_sub_days(Days::new(1)).unwrap_or(today);
+        let week_start = yesterday.week(Weekday::Mon).first_day();
+        let period_start = match frequency {
+            DigestFrequency::Weekly => week_start,
+            _ => yesterday,
+        };
+
+        let transactions = self
+            .transactions_repository
+            .select_transactions_between(chat_id, start_of_day(week_start), start_of_day(today))
+            .await?;
+        let uncategorized = self
+            .transactions_repository
+            .count_uncategorized_transactions(chat_id)
+            .await?;
+
+        if transactions.is_empty() && uncategorized == 0 {
+            return Ok(None);
+        }
+
+        let (income_categories, expense_categories): (Vec<Category>, Vec<Category>) = self
+            .categories_repository
+            .select_all_categories(chat_id)
+            .await?
+            .into_iter()
+            .partition(|category| category.direction == CategoryDirection::Income);
+        let income_category_ids: Vec<i64> = income_categories
+            .iter()
+            .map(|category| category.id)
+            .collect();
+
+        let mut expenses = Totals::default();
+        let mut week_expenses = Totals::default();
+        let mut by_category: HashMap<Option<i64>, Totals> = HashMap::new();
+
+        for transaction in transactions {
+            if transaction
+                .category_id
+                .is_some_and(|category_id| income_category_ids.contains(&category_id))
+            {
+                continue;
+            }
+
+            week_expenses.add(transaction.currency_code, transaction.amount);
+            if transaction.created_at >= start_of_day(period_start) {
+                expenses.add(transaction.currency_code, transaction.amount);
+                by_category
+                    .entry(transaction.category_id)
+                    .or_default()
+                    .add(transaction.currency_code, transaction.amount);
+            }
+        }
+
+        let mut top_categories = roll_up(by_category, &expense_categories);
+        top_categories.truncate(TOP_CATEGORIES);
+
+        let monthly_budget: i64 = expense_categories
+            .iter()
+            .filter(|category| category.deleted_at.is_none())
+            .filter_map(|category| category.target_amount)
+            .sum();
+        let week_budget =
+            (monthly_budget > 0).then(|| monthly_budget * 7 / days_in_month(yesterday) as i64);
+
+        Ok(Some(Digest {
+            frequency,
+            expenses,
+            week_expenses,
+            week_budget,
+            top_categories,
+            uncategorized,
+        }))
+    }
+
+    async fn mark_sent(&self, chat_id: i64, sent_at: DateTime<Utc>) -> Result<(), ServiceError> {
+        self.settings_repository
+            .update_digest_sent_at(chat_id, sent_at)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn settings(frequency: DigestFrequency) -> ChatSettings {
+        ChatSettings {
+            digest_frequency: frequency,
+            ..ChatSettings::new(1)
+        }
+    }
+
+    #[test]
+    fn waits_for_send_hour() {
+        let settings = settings(DigestFrequency::Daily);
+
+        assert!(!is_due(
+            &settings,
+            Utc.with_ymd_and_hms(2025, 12, 24, 8, 59, 0).unwrap()
+        ));
+        assert!(is_due(
+            &settings,
+            Utc.with_ymd_and_hms(2025, 12, 24, 9, 0, 0).unwrap()
+        ));
+    }
+
+    #[test]
+    fn sends_once_per_day() {
+        let mut settings = settings(DigestFrequency::Daily);
+        settings.digest_sent_at = Some(Utc.with_ymd_and_hms(2025, 12, 24, 9, 1, 0).unwrap());
+
+        assert!(!is_due(
+            &settings,
+            Utc.with_ymd_and_hms(2025, 12, 24, 18, 0, 0).unwrap()
+        ));
+        assert!(is_due(
+            &settings,
+            Utc.with_ymd_and_hms(2025, 12, 25, 9, 0, 0).unwrap()
+        ));
+    }
+
+    #[test]
+    fn uses_local_hour() {
+        let mut settings = settings(DigestFrequency::Daily);
+        settings.timezone = chrono_tz::Europe::Moscow;
+
+        // 9:00 in Moscow is 6:00 UTC.
+        assert!(!is_due(
+            &settings,
+            Utc.with_ymd_and_hms(2025, 12, 24, 5, 59, 0).unwrap()
+        ));
+        assert!(is_due(
+            &settings,
+            Utc.with_ymd_and_hms(2025, 12, 24, 6, 0, 0).unwrap()
+        ));
+    }
+
+    #[test]
+    fn sends_weekly_on_mondays() {
+        let settings = settings(DigestFrequency::Weekly);
+
+        // 2025-12-22 is a Monday.
+        assert!(is_due(
+            &settings,
+            Utc.with_ymd_and_hms(2025, 12, 22, 10, 0, 0).unwrap()
+        ));
+        assert!(!is_due(
+            &settings,
+            Utc.with_ymd_and_hms(2025, 12, 23, 10, 0, 0).unwrap()
+        ));
+    }
+}
@@ -0,0 +1,414 @@
+use crate::dates;
+use crate::models::category::Category;
+use crate::models::transaction::{CurrencyCode, Transaction};
+use crate::proto::callback::v1::CategoryDirection;
+use crate::repositories::{categories, members, settings, transactions};
+use chrono::{DateTime, Datelike, Months, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+pub type ServiceError = Box<dyn std::error::Error + Sync + Send>;
+
+#[async_trait::async_trait]
+pub trait Service: Send + Sync {
+    /// Transactions created in `[from, to)` as a CSV file, `None` if there are none.
+    async fn export_csv(
+        &self,
+        chat_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Option<Vec<u8>>, ServiceError>;
+    /// The same transactions as an Excel workbook with a per-category summary and
+    /// a budget-vs-actual sheet, `None` if there are none.
+    async fn export_xlsx(
+        &self,
+        chat_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Option<Vec<u8>>, ServiceError>;
+}
+
+pub struct Exports {
+    settings_repository: Arc<dyn settings::Repository>,
+    transactions_repository: Arc<dyn transactions::Repository>,
+    categories_repository: Arc<dyn categories::Repository>,
+    members_repository: Arc<dyn members::Repository>,
+}
+
+impl Exports {
+    pub fn new(
+        settings_repository: Arc<dyn settings::Repository>,
+        transactions_repository: Arc<dyn transactions::Repository>,
+        categories_repository: Arc<dyn categories::Repository>,
+        members_repository: Arc<dyn members::Repository>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            settings_repository,
+            transactions_repository,
+            categories_repository,
+            members_repository,
+        })
+    }
+
+    async fn timezone(&self, chat_id: i64) -> Result<Tz, ServiceError> {
+        Ok(self
+            .settings_repository
+            .find_settings(chat_id)
+            .await?
+            .map(|settings| settings.timezone)
+            .unwrap_or(Tz::UTC))
+    }
+
+    async fn rows(
+        &self,
+        chat_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Row>, ServiceError> {
+        let timezone = self.timezone(chat_id).await?;
+        let transactions = self
+            .transactions_repository
+            .select_transactions_between(chat_id, from, to)
+            .await?;
+
+        let mut categories = self
+            .categories_repository
+            .select_categories(chat_id, CategoryDirection::Expense)
+            .await?;
+        categories.extend(
+            self.categories_repository
+                .select_categories(chat_id, CategoryDirection::Income)
+                .await?,
+        );
+        let categories: HashMap<i64, Category> = categories
+            .into_iter()
+            .map(|category| (category.id, category))
+            .collect();
+
+        let member_names: HashMap<i64, String> = self
+            .members_repository
+            .select_members(chat_id)
+            .await?
+            .into_iter()
+            .map(|member| (member.user_id, member.full_name))
+            .collect();
+
+        Ok(transactions
+            .into_iter()
+            .map(|transaction| Row {
+                created_at: transaction
+                    .created_at
+                    .with_timezone(&timezone)
+                    .naive_local(),
+                category: transaction
+                    .category_id
+                    .and_then(|category_id| categories.get(&category_id))
+                    .cloned(),
+                author: transaction
+                    .user_id
+                    .and_then(|user_id| member_names.get(&user_id))
+                    .cloned()
+                    .unwrap_or_default(),
+                transaction,
+            })
+            .collect())
+    }
+}
+
+/// A transaction with everything needed to describe it outside the bot.
+struct Row {
+    transaction: Transaction,
+    /// When the transaction happened on the chat's wall clock.
+    created_at: NaiveDateTime,
+    category: Option<Category>,
+    author: String,
+}
+
+impl Row {
+    fn direction(&self) -> &'static str {
+        match self.category.as_ref().map(|category| category.direction) {
+            Some(CategoryDirection::Income) => "income",
+            _ => "expense",
+        }
+    }
+
+    fn direction_title(&self) -> &'static str {
+        match self.category.as_ref().map(|category| category.direction) {
+            Some(CategoryDirection::Income) => "Доход",
+            _ => "Расход",
+        }
+    }
+
+    fn category_name(&self) -> &str {
+        self.category
+            .as_ref()
+            .map(|category| category.name.as_str())
+            .unwrap_or("Без категории")
+    }
+
+    fn month(&self) -> String {
+        self.created_at.format("%Y-%m").to_string()
+    }
+}
+
+/// First days of every local month that overlaps `[from, to)`.
+fn months_between(timezone: Tz, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<NaiveDate> {
+    let mut months = Vec::new();
+    let mut month = dates::local_date(timezone, from).with_day(1);
+    while let Some(date) = month
+        && dates::start_of_day(timezone, date) < to
+    {
+        months.push(date);
+        month = date.checked_add_months(Months::new(1));
+    }
+
+    months
+}
+
+/// Writes a header row and keeps it visible with a filter, so the sheet can be turned
+/// into a pivot table or sorted as is.
+fn write_table(
+    worksheet: &mut Worksheet,
+    name: &str,
+    header: &[&str],
+    rows: u32,
+) -> Result<(), XlsxError> {
+    let bold = Format::new().set_bold();
+
+    worksheet.set_name(name)?;
+    for (col, title) in header.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *title, &bold)?;
+    }
+    worksheet.set_freeze_panes(1, 0)?;
+    worksheet.autofilter(0, 0, rows, header.len() as u16 - 1)?;
+
+    Ok(())
+}
+
+fn build_workbook(
+    rows: &[Row],
+    budgets: &[Category],
+    months: &[NaiveDate],
+) -> Result<Vec<u8>, XlsxError> {
+    let money = Format::new().set_num_format("#,##0.00");
+    let date = Format::new().set_num_format("dd.mm.yyyy hh:mm");
+    let mut workbook = Workbook::new();
+
+    let worksheet = workbook.add_worksheet();
+    write_table(
+        worksheet,
+        "Операции",
+        &[
+            "Дата",
+            "Сумма",
+            "Валюта",
+            "Категория",
+            "Метка",
+            "Направление",
+            "Описание",
+            "Автор",
+        ],
+        rows.len() as u32,
+    )?;
+    for (index, row) in rows.iter().enumerate() {
+        let line = index as u32 + 1;
+        worksheet.write_datetime_with_format(line, 0, row.created_at, &date)?;
+        worksheet.write_number_with_format(
+            line,
+            1,
+            row.transaction.amount as f64 / 100.0,
+            &money,
+        )?;
+        worksheet.write_string(line, 2, row.transaction.currency_code.as_code_str())?;
+        worksheet.write_string(line, 3, row.category_name())?;
+        if let Some(category) = &row.category {
+            worksheet.write_string(line, 4, &category.label)?;
+        }
+        worksheet.write_string(line, 5, row.direction_title())?;
+        worksheet.write_string(line, 6, &row.transaction.description)?;
+        worksheet.write_string(line, 7, &row.author)?;
+    }
+    worksheet.autofit();
+
+    let mut totals: BTreeMap<(String, &str, &str, &str), i64> = BTreeMap::new();
+    for row in rows {
+        *totals
+            .entry((
+                row.month(),
+                row.direction_title(),
+                row.category_name(),
+                row.transaction.currency_code.as_code_str(),
+            ))
+            .or_default() += row.transaction.amount;
+    }
+
+    let worksheet = workbook.add_worksheet();
+    write_table(
+        worksheet,
+        "По категориям",
+        &["Месяц", "Направление", "Категория", "Валюта", "Сумма"],
+        totals.len() as u32,
+    )?;
+    for (index, ((month, direction, category, currency), amount)) in totals.iter().enumerate() {
+        let line = index as u32 + 1;
+        worksheet.write_string(line, 0, month)?;
+        worksheet.write_string(line, 1, *direction)?;
+        worksheet.write_string(line, 2, *category)?;
+        worksheet.write_string(line, 3, *currency)?;
+        worksheet.write_number_with_format(line, 4, *amount as f64 / 100.0, &money)?;
+    }
+    worksheet.autofit();
+
+    // Limits are set in rubles, so only ruble transactions count against them.
+    let mut spent: HashMap<(String, i64), i64> = HashMap::new();
+    for row in rows {
+        if row.transaction.currency_code == CurrencyCode::RUB
+            && let Some(category_id) = row.transaction.category_id
+        {
+            *spent.entry((row.month(), category_id)).or_default() += row.transaction.amount;
+        }
+    }
+
+    let worksheet = workbook.add_worksheet();
+    write_table(
+        worksheet,
+        "Бюджет",
+        &["Месяц", "Категория", "Лимит", "Факт", "Остаток"],
+        (months.len() * budgets.len()) as u32,
+    )?;
+    let mut line = 1;
+    for month in months {
+        let month = month.format("%Y-%m").to_string();
+        for category in budgets {
+            let target_amount = category.target_amount.unwrap_or_default();
+            let spent = spent
+                .get(&(month.to_owned(), category.id))
+                .copied()
+                .unwrap_or_default();
+
+            worksheet.write_string(line, 0, &month)?;
+            worksheet.write_string(line, 1, &category.name)?;
+            worksheet.write_number_with_format(line, 2, target_amount as f64 / 100.0, &money)?;
+            worksheet.write_number_with_format(line, 3, spent as f64 / 100.0, &money)?;
+            worksheet.write_number_with_format(
+                line,
+                4,
+                (target_amount - spent) as f64 / 100.0,
+                &money,
+            )?;
+            line += 1;
+        }
+    }
+    worksheet.autofit();
+
+    workbook.save_to_buffer()
+}
+
+/// Minor units as a plain decimal that spreadsheets parse, e.g. `-1234.50`.
+fn format_decimal(amount: i64) -> String {
+    let sign = if amount < 0 { "-" } else { "" };
+
+    format!("{sign}{}.{:02}", amount.abs() / 100, amount.abs() % 100)
+}
+
+#[async_trait::async_trait]
+impl Service for Exports {
+    async fn export_csv(
+        &self,
+        chat_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Option<Vec<u8>>, ServiceError> {
+        let rows = self.rows(chat_id, from, to).await?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record([
+            "date",
+            "amount",
+            "currency",
+            "category",
+            "label",
+            "direction",
+            "description",
+            "author",
+        ])?;
+
+        for row in &rows {
+            writer.write_record([
+                row.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                format_decimal(row.transaction.amount),
+                row.transaction.currency_code.as_code_str().to_string(),
+                row.category
+                    .as_ref()
+                    .map(|category| category.name.to_owned())
+                    .unwrap_or_default(),
+                row.category
+                    .as_ref()
+                    .map(|category| category.label.to_owned())
+                    .unwrap_or_default(),
+                row.direction().to_string(),
+                row.transaction.description.to_owned(),
+                row.author.to_owned(),
+            ])?;
+        }
+
+        Ok(Some(writer.into_inner()?))
+    }
+
+    async fn export_xlsx(
+        &self,
+        chat_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Option<Vec<u8>>, ServiceError> {
+        let rows = self.rows(chat_id, from, to).await?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let budgets: Vec<Category> = self
+            .categories_repository
+            .select_categories(chat_id, CategoryDirection::Expense)
+            .await?
+            .into_iter()
+            .filter(|category| category.target_amount.is_some_and(|amount| amount != 0))
+            .collect();
+
+        Ok(Some(build_workbook(
+            &rows,
+            &budgets,
+            &months_between(self.timezone(chat_id).await?, from, to),
+        )?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn keeps_two_fraction_digits() {
+        assert_eq!(format_decimal(35000), "350.00");
+        assert_eq!(format_decimal(5), "0.05");
+        assert_eq!(format_decimal(-123450), "-1234.50");
+    }
+
+    #[test]
+    fn includes_partial_months() {
+        let from = Utc.with_ymd_and_hms(2025, 11, 15, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2026, 1, 2, 0, 0, 0).unwrap();
+
+        let months: Vec<String> = months_between(Tz::UTC, from, to)
+            .iter()
+            .map(|month| month.format("%Y-%m").to_string())
+            .collect();
+
+        assert_eq!(months, vec!["2025-11", "2025-12", "2026-01"]);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::models::member::{Member, role_of};
+use crate::proto::callback::v1::MemberRole;
+use crate::repositories::members::Repository;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub type ServiceError = Box<dyn std::error::Error + Sync + Send>;
+
+pub struct UpsertMemberRequest {
+    pub chat_id: i64,
+    pub user_id: i64,
+    pub username: Option<String>,
+    pub full_name: String,
+}
+
+#[async_trait::async_trait]
+pub trait Service: Send + Sync {
+    /// Remembers the user's name. New members may only look until an owner gives them
+    /// more rights; the role of a known member is kept.
+    async fn upsert_member(&self, request: UpsertMemberRequest) -> Result<(), ServiceError>;
+    async fn select_members(&self, chat_id: i64) -> Result<Vec<Member>, ServiceError>;
+    /// Display names of the chat members keyed by user id.
+    async fn member_names(&self, chat_id: i64) -> Result<HashMap<i64, String>, ServiceError>;
+    async fn get_member(&self, chat_id: i64, user_id: i64) -> Result<Member, ServiceError>;
+    /// Role of the user in the chat. Private chats belong to their only user, and members
+    /// the bot has not seen yet are viewers.
+    async fn get_role(&self, chat_id: i64, user_id: i64) -> Result<MemberRole, ServiceError>;
+    /// Returns `false` if the change would leave the chat without an owner.
+    async fn update_member_role(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        role: MemberRole,
+    ) -> Result<bool, ServiceError>;
+    async fn has_owner(&self, chat_id: i64) -> Result<bool, ServiceError>;
+    /// Group chats whose owner still has to be taken from Telegram.
+    async fn select_chats_without_owner(&self) -> Result<Vec<i64>, ServiceError>;
+    /// Remembers the users and makes them owners of the chat.
+    async fn set_owners(
+        &self,
+        chat_id: i64,
+        owners: Vec<UpsertMemberRequest>,
+    ) -> Result<(), ServiceError>;
+}
+
+pub struct Members {
+    repository: Arc<dyn Repository>,
+}
+
+impl Members {
+    pub fn new(repository: Arc<dyn Repository>) -> Arc<Self> {
+        Arc::new(Self { repository })
+    }
+}
+
+#[async_trait::async_trait]
+impl Service for Members {
+    async fn upsert_member(&self, request: UpsertMemberRequest) -> Result<(), ServiceError> {
+        let now = Utc::now();
+
+        self.repository
+            .upsert_member(Member {
+                chat_id: request.chat_id,
+                user_id: request.user_id,
+                username: request.username,
+                full_name: request.full_name,
+                role: MemberRole::Viewer,
+                created_at: now,
+                updated_at: now,
+            })
+            .await
+    }
+
+    async fn select_members(&self, chat_id: i64) -> Result<Vec<Member>, ServiceError> {
+        self.repository.select_members(chat_id).await
+    }
+
+    async fn member_names(&self, chat_id: i64) -> Result<HashMap<i64, String>, ServiceError> {
+        Ok(self
+            .select_members(chat_id)
+            .await?
+            .into_iter()
+            .map(|member| (member.user_id, member.full_name))
+            .collect())
+    }
+
+    async fn get_member(&self, chat_id: i64, user_id: i64) -> Result<Member, ServiceError> {
+        self.repository
+            .find_member(chat_id, user_id)
+            .await?
+            .ok_or_else(|| "member not found".into())
+    }
+
+    async fn get_role(&self, chat_id: i64, user_id: i64) -> Result<MemberRole, ServiceError> {
+        let member = self.repository.find_member(chat_id, user_id).await?;
+
+        Ok(role_of(chat_id, user_id, member.as_ref()))
+    }
+
+    async fn update_member_role(
+        &self,
+        chat_id: i64,
+        user_id: i64,
+        role: MemberRole,
+    ) -> Result<bool, ServiceError> {
+        let members = self.select_members(chat_id).await?;
+        let owners = members
+            .iter()
+            .filter(|member| member.role == MemberRole::Owner)
+            .count();
+        let is_owner = members
+            .iter()
+            .any(|member| member.user_id == user_id && member.role == MemberRole::Owner);
+
+        if is_owner && role != MemberRole::Owner && owners == 1 {
+            return Ok(false);
+        }
+
+        self.repository
+            .update_member_role(chat_id, user_id, role)
+            .await?;
+
+        Ok(true)
+    }
+
+    async fn has_owner(&self, chat_id: i64) -> Result<bool, ServiceError> {
+        self.repository.has_owner(chat_id).await
+    }
+
+    async fn select_chats_without_owner(&self) -> Result<Vec<i64>, ServiceError> {
+        self.repository.select_chats_without_owner().await
+    }
+
+    async fn set_owners(
+        &self,
+        chat_id: i64,
+        owners: Vec<UpsertMemberRequest>,
+    ) -> Result<(), ServiceError> {
+        for owner in owners {
+            let user_id = owner.user_id;
+            self.upsert_member(owner).await?;
+            self.repository
+                .update_member_role(chat_id, user_id, MemberRole::Owner)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
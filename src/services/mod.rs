@@ -1,2 +1,10 @@
+pub mod actions;
+pub mod backups;
 pub mod categories;
+pub mod debts;
+pub mod digests;
+pub mod exports;
+pub mod members;
+pub mod reports;
+pub mod settings;
 pub mod transactions;
\ No newline at end of file
@@ -0,0 +1,224 @@
+use crate::dates::{self, add_months, month_bounds};
+use crate::models::category::subcategories;
+use crate::models::report::{MemberTotal, MonthTotals, MonthlyReport, Totals, roll_up};
+use crate::proto::callback::v1::CategoryDirection;
+use crate::repositories::{categories, settings, transactions};
+use chrono::Datelike;
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub type ServiceError = Box<dyn std::error::Error + Sync + Send>;
+
+#[async_trait::async_trait]
+pub trait Service: Send + Sync {
+    /// Report for a month of the chat's local calendar.
+    async fn monthly_report(
+        &self,
+        chat_id: i64,
+        year: i32,
+        month: u32,
+    ) -> Result<MonthlyReport, ServiceError>;
+    /// Totals of `count` months up to and including the given one, oldest first.
+    /// With a category, only its transactions and those of its subcategories are counted.
+    async fn monthly_trend(
+        &self,
+        chat_id: i64,
+        category_id: Option<i64>,
+        year: i32,
+        month: u32,
+        count: u32,
+    ) -> Result<Vec<MonthTotals>, ServiceError>;
+}
+
+pub struct Reports {
+    settings_repository: Arc<dyn settings::Repository>,
+    transactions_repository: Arc<dyn transactions::Repository>,
+    categories_repository: Arc<dyn categories::Repository>,
+}
+
+impl Reports {
+    pub fn new(
+        settings_repository: Arc<dyn settings::Repository>,
+        transactions_repository: Arc<dyn transactions::Repository>,
+        categories_repository: Arc<dyn categories::Repository>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            settings_repository,
+            transactions_repository,
+            categories_repository,
+        })
+    }
+
+    async fn timezone(&self, chat_id: i64) -> Result<Tz, ServiceError> {
+        Ok(self
+            .settings_repository
+            .find_settings(chat_id)
+            .await?
+            .map(|settings| settings.timezone)
+            .unwrap_or(Tz::UTC))
+    }
+}
+
+#[async_trait::async_trait]
+impl Service for Reports {
+    async fn monthly_report(
+        &self,
+        chat_id: i64,
+        year: i32,
+        month: u32,
+    ) -> Result<MonthlyReport, ServiceError> {
+        let timezone = self.timezone(chat_id).await?;
+        let (from, to) = month_bounds(timezone, year, month).ok_or("invalid month")?;
+
+        let transactions = self
+            .transactions_repository
+            .select_transactions_between(chat_id, from, to)
+            .await?;
+
+        // Archived categories still name and classify the transactions made before.
+        let categories = self
+            .categories_repository
+            .select_all_categories(chat_id)
+            .await?;
+
+        let mut expenses = Totals::default();
+        let mut incomes = Totals::default();
+        let mut expense_categories: HashMap<Option<i64>, Totals> = HashMap::new();
+        let mut income_categories: HashMap<Option<i64>, Totals> = HashMap::new();
+        let mut members: Vec<MemberTotal> = Vec::new();
+
+        for transaction in transactions {
+            let direction = transaction
+                .category_id
+                .and_then(|category_id| {
+                    categories
+                        .iter()
+                        .find(|category| category.id == category_id)
+                })
+                .map(|category| category.direction)
+                .unwrap_or(CategoryDirection::Expense);
+
+            let member = match members
+                .iter_mut()
+                .position(|member| member.user_id == transaction.user_id)
+            {
+                Some(index) => &mut members[index],
+                None => {
+                    members.push(MemberTotal {
+                        user_id: transaction.user_id,
+                        expenses: Totals::default(),
+                        incomes: Totals::default(),
+                    });
+                    members.last_mut().unwrap()
+                }
+            };
+
+            let (totals, by_category, member_totals) = match direction {
+                CategoryDirection::Income => {
+                    (&mut incomes, &mut income_categories, &mut member.incomes)
+                }
+                _ => (&mut expenses, &mut expense_categories, &mut member.expenses),
+            };
+            totals.add(transaction.currency_code, transaction.amount);
+            member_totals.add(transaction.currency_code, transaction.amount);
+            by_category
+                .entry(transaction.category_id)
+                .or_default()
+                .add(transaction.currency_code, transaction.amount);
+        }
+
+        members.sort_by_key(|member| -member.expenses.weight());
+
+        Ok(MonthlyReport {
+            year,
+            month,
+            expenses,
+            incomes,
+            expense_categories: roll_up(expense_categories, &categories),
+            income_categories: roll_up(income_categories, &categories),
+            members,
+        })
+    }
+
+    async fn monthly_trend(
+        &self,
+        chat_id: i64,
+        category_id: Option<i64>,
+        year: i32,
+        month: u32,
+        count: u32,
+    ) -> Result<Vec<MonthTotals>, ServiceError> {
+        let (first_year, first_month) = add_months(year, month, 1 - count as i32);
+        let timezone = self.timezone(chat_id).await?;
+        let (from, _) = month_bounds(timezone, first_year, first_month).ok_or("invalid month")?;
+        let (_, to) = month_bounds(timezone, year, month).ok_or("invalid month")?;
+
+        let transactions = self
+            .transactions_repository
+            .select_transactions_between(chat_id, from, to)
+            .await?;
+        let categories = self
+            .categories_repository
+            .select_all_categories(chat_id)
+            .await?;
+        let income_category_ids: Vec<i64> = categories
+            .iter()
+            .filter(|category| category.direction == CategoryDirection::Income)
+            .map(|category| category.id)
+            .collect();
+        let category_ids: Option<Vec<i64>> = category_id.map(|category_id| {
+            std::iter::once(category_id)
+                .chain(
+                    subcategories(&categories, category_id)
+                        .into_iter()
+                        .map(|category| category.id),
+                )
+                .collect()
+        });
+
+        let mut months: Vec<MonthTotals> = (0..count as i32)
+            .map(|offset| {
+                let (year, month) = add_months(first_year, first_month, offset);
+                MonthTotals {
+                    year,
+                    month,
+                    expenses: Totals::default(),
+                    incomes: Totals::default(),
+                }
+            })
+            .collect();
+
+        for transaction in transactions {
+            if let Some(category_ids) = &category_ids
+                && !transaction
+                    .category_id
+                    .is_some_and(|category_id| category_ids.contains(&category_id))
+            {
+                continue;
+            }
+
+            let created_at = dates::local_date(timezone, transaction.created_at);
+            let Some(totals) = months.iter_mut().find(|totals| {
+                totals.year == created_at.year() && totals.month == created_at.month()
+            }) else {
+                continue;
+            };
+
+            let is_income = transaction
+                .category_id
+                .is_some_and(|category_id| income_category_ids.contains(&category_id));
+            if is_income {
+                totals
+                    .incomes
+                    .add(transaction.currency_code, transaction.amount);
+            } else {
+                totals
+                    .expenses
+                    .add(transaction.currency_code, transaction.amount);
+            }
+        }
+
+        Ok(months)
+    }
+}
@@ -0,0 +1,80 @@
+use crate::models::settings::ChatSettings;
+use crate::proto::callback::v1::{CategorySort, DigestFrequency};
+use crate::repositories::settings::Repository;
+use chrono_tz::Tz;
+use std::sync::Arc;
+
+pub type ServiceError = Box<dyn std::error::Error + Sync + Send>;
+
+#[async_trait::async_trait]
+pub trait Service: Send + Sync {
+    /// Settings of the chat, the defaults if it has never changed them.
+    async fn get_settings(&self, chat_id: i64) -> Result<ChatSettings, ServiceError>;
+    async fn update_digest(
+        &self,
+        chat_id: i64,
+        frequency: DigestFrequency,
+        hour: u32,
+    ) -> Result<(), ServiceError>;
+    async fn update_timezone(&self, chat_id: i64, timezone: Tz) -> Result<(), ServiceError>;
+    async fn update_category_sort(
+        &self,
+        chat_id: i64,
+        category_sort: CategorySort,
+    ) -> Result<(), ServiceError>;
+}
+
+pub struct Settings {
+    repository: Arc<dyn Repository>,
+}
+
+impl Settings {
+    pub fn new(repository: Arc<dyn Repository>) -> Arc<Self> {
+        Arc::new(Self { repository })
+    }
+}
+
+#[async_trait::async_trait]
+impl Service for Settings {
+    async fn get_settings(&self, chat_id: i64) -> Result<ChatSettings, ServiceError> {
+        Ok(self
+            .repository
+            .find_settings(chat_id)
+            .await?
+            .unwrap_or_else(|| ChatSettings::new(chat_id)))
+    }
+
+    async fn update_digest(
+        &self,
+        chat_id: i64,
+        frequency: DigestFrequency,
+        hour: u32,
+    ) -> Result<(), ServiceError> {
+        if hour > 23 {
+            return Err("invalid digest hour".into());
+        }
+
+        let mut settings = self.get_settings(chat_id).await?;
+        settings.digest_frequency = frequency;
+        settings.digest_hour = hour;
+
+        self.repository.upsert_settings(settings).await
+    }
+
+    async fn update_timezone(&self, chat_id: i64, timezone: Tz) -> Result<(), ServiceError> {
+        let mut settings = self.get_settings(chat_id).await?;
+        settings.timezone = timezone;
+
+        self.repository.upsert_settings(settings).await
+    }
+    async fn update_category_sort(
+        &self,
+        chat_id: i64,
+        category_sort: CategorySort,
+    ) -> Result<(), ServiceError> {
+        let mut settings = self.get_settings(chat_id).await?;
+        settings.category_sort = category_sort;
+
+        self.repository.upsert_settings(settings).await
+    }
+}
@@ -1,25 +1,73 @@
+use crate::dates;
+use crate::models::action::{Action, ActionKind, Snapshot};
 use crate::models::transaction::{CurrencyCode, Transaction};
+use crate::proto::callback::v1::CategoryDirection;
+use crate::repositories::actions;
 use crate::repositories::transactions::Repository;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Days, NaiveDate, TimeDelta, Utc};
+use chrono_tz::Tz;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 pub type ServiceError = Box<dyn std::error::Error + Sync + Send>;
 
+/// Hours a transaction may stay uncategorized before the bot asks about it again.
+const REMINDER_DELAY_HOURS: i64 = 12;
+/// Older transactions are left for `/uncategorized`, so the first run does not flood chats
+/// with reminders about the whole history.
+const REMINDER_MAX_AGE_DAYS: i64 = 7;
+
 pub struct CreateTransactionRequest {
     pub chat_id: i64,
+    pub user_id: Option<i64>,
     pub category_id: Option<i64>,
     pub amount: i64,
     pub description: String,
     pub currency_code: CurrencyCode,
+    pub message_id: Option<i32>,
+    /// Defaults to now.
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+pub struct ImportTransactionRequest {
+    pub transaction: CreateTransactionRequest,
+    /// Known for bank statements, where the sign tells incomes from expenses.
+    pub direction: Option<CategoryDirection>,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub imported: usize,
+    /// Transactions that already exist with the same day, amount and description.
+    pub skipped: usize,
 }
 
 pub struct UpdateTransactionRequest {
     pub id: i64,
     pub chat_id: i64,
+    pub user_id: Option<i64>,
     pub category_id: Option<i64>,
     pub amount: Option<i64>,
     pub description: Option<String>,
     pub currency_code: Option<CurrencyCode>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub reply_message_id: Option<i32>,
+}
+
+impl UpdateTransactionRequest {
+    pub fn new(chat_id: i64, id: i64) -> Self {
+        Self {
+            id,
+            chat_id,
+            user_id: None,
+            category_id: None,
+            amount: None,
+            description: None,
+            currency_code: None,
+            created_at: None,
+            reply_message_id: None,
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -33,16 +81,149 @@ pub trait Service: Send + Sync {
         request: UpdateTransactionRequest,
     ) -> Result<Transaction, ServiceError>;
     async fn get_transaction(&self, chat_id: i64, id: i64) -> Result<Transaction, ServiceError>;
-    async fn delete_transaction(&self, chat_id: i64, id: i64) -> Result<(), ServiceError>;
+    async fn select_transactions(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, ServiceError>;
+    async fn count_transactions(&self, chat_id: i64) -> Result<i64, ServiceError>;
+    async fn count_uncategorized(&self, chat_id: i64) -> Result<i64, ServiceError>;
+    /// The oldest uncategorized transactions of the chat.
+    async fn select_uncategorized(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, ServiceError>;
+    /// Uncategorized transactions with the same description, ignoring case.
+    async fn select_similar_uncategorized(
+        &self,
+        chat_id: i64,
+        description: &str,
+    ) -> Result<Vec<Transaction>, ServiceError>;
+    /// Puts every uncategorized transaction with the description into the category as a
+    /// single undoable action. Returns the number of updated transactions.
+    async fn categorize_by_description(
+        &self,
+        chat_id: i64,
+        user_id: Option<i64>,
+        description: &str,
+        category_id: i64,
+    ) -> Result<usize, ServiceError>;
+    /// Uncategorized transactions of every chat that are old enough to be reminded about
+    /// and have not been yet.
+    async fn due_reminders(&self, now: DateTime<Utc>) -> Result<Vec<Transaction>, ServiceError>;
+    async fn mark_reminded(
+        &self,
+        transaction_ids: &[i64],
+        reminded_at: DateTime<Utc>,
+    ) -> Result<(), ServiceError>;
+    async fn select_transactions_between(
+        &self,
+        chat_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transaction>, ServiceError>;
+    async fn find_transaction_by_message_id(
+        &self,
+        chat_id: i64,
+        message_id: i32,
+    ) -> Result<Option<Transaction>, ServiceError>;
+    async fn delete_transaction(
+        &self,
+        chat_id: i64,
+        id: i64,
+        user_id: Option<i64>,
+    ) -> Result<(), ServiceError>;
+    /// Category most likely meant for the description, judging by earlier transactions.
+    async fn suggest_category(
+        &self,
+        chat_id: i64,
+        description: &str,
+        direction: Option<CategoryDirection>,
+    ) -> Result<Option<i64>, ServiceError>;
+    /// Creates the transactions of a chat that do not exist yet, categorizing them by
+    /// description. A transaction exists if one with the same amount and description was made
+    /// the same local day before the import, and each of those matches one imported row only.
+    /// The whole import is logged as a single undoable action.
+    async fn import_transactions(
+        &self,
+        timezone: Tz,
+        requests: Vec<ImportTransactionRequest>,
+    ) -> Result<ImportReport, ServiceError>;
+}
+
+/// Local day, amount and lowercase description by which imported rows are matched.
+type ImportKey = (NaiveDate, i64, String);
+
+fn import_key(
+    timezone: Tz,
+    created_at: DateTime<Utc>,
+    amount: i64,
+    description: &str,
+) -> ImportKey {
+    (
+        dates::local_date(timezone, created_at),
+        amount,
+        description.trim().to_lowercase(),
+    )
+}
+
+/// Marks the incoming rows that already exist. Each existing transaction matches a single row,
+/// so identical purchases made the same day are all kept.
+fn mark_existing(existing: Vec<ImportKey>, incoming: &[ImportKey]) -> Vec<bool> {
+    let mut counts: HashMap<ImportKey, usize> = HashMap::new();
+    for key in existing {
+        *counts.entry(key).or_default() += 1;
+    }
+
+    incoming
+        .iter()
+        .map(|key| match counts.get_mut(key) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        })
+        .collect()
+}
+
+/// A transaction yet to be saved, without an id.
+fn new_transaction(request: CreateTransactionRequest) -> Transaction {
+    let now = Utc::now();
+    Transaction {
+        id: -1,
+        chat_id: request.chat_id,
+        user_id: request.user_id,
+        category_id: request.category_id,
+        amount: request.amount,
+        amount_modified: false,
+        description: request.description,
+        currency_code: request.currency_code,
+        message_id: request.message_id,
+        reply_message_id: None,
+        created_at: request.created_at.unwrap_or(now),
+        updated_at: now,
+        deleted_at: None,
+    }
 }
 
 pub struct Transactions {
     repository: Arc<dyn Repository>,
+    actions_repository: Arc<dyn actions::Repository>,
 }
 
 impl Transactions {
-    pub fn new(repository: Arc<dyn Repository>) -> Arc<Self> {
-        Arc::new(Self { repository })
+    pub fn new(
+        repository: Arc<dyn Repository>,
+        actions_repository: Arc<dyn actions::Repository>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            repository,
+            actions_repository,
+        })
     }
 }
 
@@ -52,24 +233,22 @@ impl Service for Transactions {
         &self,
         request: CreateTransactionRequest,
     ) -> Result<Transaction, ServiceError> {
-        let now: DateTime<Utc> = Default::default();
-        let mut transaction = Transaction {
-            id: -1,
-            chat_id: request.chat_id,
-            category_id: request.category_id,
-            amount: request.amount,
-            amount_modified: false,
-            description: request.description,
-            currency_code: request.currency_code,
-            created_at: now,
-            updated_at: now,
-            deleted_at: None,
-        };
+        let user_id = request.user_id;
+        let mut transaction = new_transaction(request);
         transaction.id = self
             .repository
             .create_transaction(transaction.to_owned())
             .await?;
 
+        self.actions_repository
+            .create_action(Action::new(
+                ActionKind::TransactionCreated,
+                user_id,
+                None,
+                Some(Snapshot::Transaction(transaction.to_owned())),
+            ))
+            .await?;
+
         Ok(transaction)
     }
 
@@ -78,11 +257,14 @@ impl Service for Transactions {
         request: UpdateTransactionRequest,
     ) -> Result<Transaction, ServiceError> {
         let mut transaction = self.get_transaction(request.chat_id, request.id).await?;
+        let before = transaction.to_owned();
 
         if let Some(category_id) = request.category_id {
             transaction.category_id = Some(category_id);
         }
-        if let Some(amount) = request.amount {
+        if let Some(amount) = request.amount
+            && amount != transaction.amount
+        {
             transaction.amount = amount;
             transaction.amount_modified = true;
         }
@@ -92,12 +274,35 @@ impl Service for Transactions {
         if let Some(currency_code) = request.currency_code {
             transaction.currency_code = currency_code;
         }
+        if let Some(created_at) = request.created_at {
+            transaction.created_at = created_at;
+        }
+        if let Some(reply_message_id) = request.reply_message_id {
+            transaction.reply_message_id = Some(reply_message_id);
+        }
 
         transaction.updated_at = Utc::now();
         self.repository
             .update_transaction(transaction.to_owned())
             .await?;
 
+        // Remembering the reply message is bookkeeping, not something the user can undo.
+        let is_changed = before.category_id != transaction.category_id
+            || before.amount != transaction.amount
+            || before.description != transaction.description
+            || before.currency_code != transaction.currency_code
+            || before.created_at != transaction.created_at;
+        if is_changed {
+            self.actions_repository
+                .create_action(Action::new(
+                    ActionKind::TransactionUpdated,
+                    request.user_id,
+                    Some(Snapshot::Transaction(before)),
+                    Some(Snapshot::Transaction(transaction.to_owned())),
+                ))
+                .await?;
+        }
+
         Ok(transaction)
     }
 
@@ -105,7 +310,292 @@ impl Service for Transactions {
         self.repository.get_transaction(chat_id, id).await
     }
 
-    async fn delete_transaction(&self, chat_id: i64, id: i64) -> Result<(), ServiceError> {
-        self.repository.delete_transaction(chat_id, id).await
+    async fn select_transactions(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, ServiceError> {
+        self.repository
+            .select_transactions(chat_id, limit, offset)
+            .await
+    }
+
+    async fn count_transactions(&self, chat_id: i64) -> Result<i64, ServiceError> {
+        self.repository.count_transactions(chat_id).await
+    }
+
+    async fn count_uncategorized(&self, chat_id: i64) -> Result<i64, ServiceError> {
+        self.repository
+            .count_uncategorized_transactions(chat_id)
+            .await
+    }
+
+    async fn select_uncategorized(
+        &self,
+        chat_id: i64,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Transaction>, ServiceError> {
+        self.repository
+            .select_uncategorized_transactions(chat_id, limit, offset)
+            .await
+    }
+
+    async fn select_similar_uncategorized(
+        &self,
+        chat_id: i64,
+        description: &str,
+    ) -> Result<Vec<Transaction>, ServiceError> {
+        if description.trim().is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.repository
+            .select_uncategorized_by_description(chat_id, description.trim())
+            .await
+    }
+
+    async fn categorize_by_description(
+        &self,
+        chat_id: i64,
+        user_id: Option<i64>,
+        description: &str,
+        category_id: i64,
+    ) -> Result<usize, ServiceError> {
+        let transactions = self
+            .select_similar_uncategorized(chat_id, description)
+            .await?;
+
+        if transactions.is_empty() {
+            return Ok(0);
+        }
+
+        let mut updated = Vec::with_capacity(transactions.len());
+        for transaction in &transactions {
+            let mut transaction = transaction.to_owned();
+            transaction.category_id = Some(category_id);
+            transaction.updated_at = Utc::now();
+            self.repository
+                .update_transaction(transaction.to_owned())
+                .await?;
+            updated.push(transaction);
+        }
+
+        self.actions_repository
+            .create_action(Action::new(
+                ActionKind::TransactionsCategorized,
+                user_id,
+                Some(Snapshot::Transactions(transactions)),
+                Some(Snapshot::Transactions(updated.to_owned())),
+            ))
+            .await?;
+
+        Ok(updated.len())
+    }
+
+    async fn due_reminders(&self, now: DateTime<Utc>) -> Result<Vec<Transaction>, ServiceError> {
+        self.repository
+            .select_unreminded_transactions(
+                now - TimeDelta::days(REMINDER_MAX_AGE_DAYS),
+                now - TimeDelta::hours(REMINDER_DELAY_HOURS),
+            )
+            .await
+    }
+
+    async fn mark_reminded(
+        &self,
+        transaction_ids: &[i64],
+        reminded_at: DateTime<Utc>,
+    ) -> Result<(), ServiceError> {
+        self.repository
+            .upsert_reminders(transaction_ids, reminded_at)
+            .await
+    }
+
+    async fn select_transactions_between(
+        &self,
+        chat_id: i64,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Transaction>, ServiceError> {
+        self.repository
+            .select_transactions_between(chat_id, from, to)
+            .await
+    }
+
+    async fn find_transaction_by_message_id(
+        &self,
+        chat_id: i64,
+        message_id: i32,
+    ) -> Result<Option<Transaction>, ServiceError> {
+        self.repository
+            .find_transaction_by_message_id(chat_id, message_id)
+            .await
+    }
+
+    async fn delete_transaction(
+        &self,
+        chat_id: i64,
+        id: i64,
+        user_id: Option<i64>,
+    ) -> Result<(), ServiceError> {
+        let transaction = self.get_transaction(chat_id, id).await?;
+        self.repository.delete_transaction(chat_id, id).await?;
+
+        self.actions_repository
+            .create_action(Action::new(
+                ActionKind::TransactionDeleted,
+                user_id,
+                Some(Snapshot::Transaction(transaction)),
+                None,
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn suggest_category(
+        &self,
+        chat_id: i64,
+        description: &str,
+        direction: Option<CategoryDirection>,
+    ) -> Result<Option<i64>, ServiceError> {
+        if description.trim().is_empty() {
+            return Ok(None);
+        }
+
+        self.repository
+            .find_category_by_description(chat_id, description.trim(), direction)
+            .await
+    }
+
+    async fn import_transactions(
+        &self,
+        timezone: Tz,
+        requests: Vec<ImportTransactionRequest>,
+    ) -> Result<ImportReport, ServiceError> {
+        let mut report = ImportReport::default();
+        let Some((chat_id, user_id)) = requests
+            .first()
+            .map(|request| (request.transaction.chat_id, request.transaction.user_id))
+        else {
+            return Ok(report);
+        };
+
+        let now = Utc::now();
+        let keys: Vec<ImportKey> = requests
+            .iter()
+            .map(|request| {
+                import_key(
+                    timezone,
+                    request.transaction.created_at.unwrap_or(now),
+                    request.transaction.amount,
+                    &request.transaction.description,
+                )
+            })
+            .collect();
+
+        // Loaded up front, so rows created by this import are not taken for duplicates.
+        let mut existing = Vec::new();
+        if let (Some(first_day), Some(last_day)) = (
+            keys.iter().map(|key| key.0).min(),
+            keys.iter().map(|key| key.0).max(),
+        ) {
+            let next_day = last_day
+                .checked_add_days(Days::new(1))
+                .ok_or("invalid date")?;
+            existing = self
+                .repository
+                .select_transactions_between(
+                    chat_id,
+                    dates::start_of_day(timezone, first_day),
+                    dates::start_of_day(timezone, next_day),
+                )
+                .await?
+                .iter()
+                .map(|transaction| {
+                    import_key(
+                        timezone,
+                        transaction.created_at,
+                        transaction.amount,
+                        &transaction.description,
+                    )
+                })
+                .collect();
+        }
+
+        let mut imported = Vec::new();
+        for (
+            ImportTransactionRequest {
+                transaction: mut request,
+                direction,
+            },
+            exists,
+        ) in requests.into_iter().zip(mark_existing(existing, &keys))
+        {
+            if exists {
+                report.skipped += 1;
+                continue;
+            }
+
+            if request.category_id.is_none() {
+                request.category_id = self
+                    .suggest_category(request.chat_id, &request.description, direction)
+                    .await?;
+            }
+
+            imported.push(new_transaction(request));
+        }
+
+        // All or nothing, so that a failed import can simply be retried.
+        let ids = self.repository.create_transactions(&imported).await?;
+        for (transaction, id) in imported.iter_mut().zip(ids) {
+            transaction.id = id;
+        }
+        report.imported = imported.len();
+
+        if !imported.is_empty() {
+            self.actions_repository
+                .create_action(Action::new(
+                    ActionKind::TransactionsImported,
+                    user_id,
+                    None,
+                    Some(Snapshot::Transactions(imported)),
+                ))
+                .await?;
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(amount: i64, description: &str) -> ImportKey {
+        (
+            NaiveDate::from_ymd_opt(2025, 12, 5).unwrap(),
+            amount,
+            description.to_string(),
+        )
+    }
+
+    #[test]
+    fn keeps_identical_rows_of_one_import() {
+        let incoming = [key(35000, "кофе"), key(35000, "кофе")];
+
+        assert_eq!(mark_existing(vec![], &incoming), vec![false, false]);
+    }
+
+    #[test]
+    fn matches_each_existing_transaction_once() {
+        let incoming = [key(35000, "кофе"), key(35000, "кофе"), key(12000, "хлеб")];
+
+        assert_eq!(
+            mark_existing(vec![key(35000, "кофе")], &incoming),
+            vec![true, false, false]
+        );
     }
 }
@@ -1,14 +1,12 @@
-use crate::proto::callback::v1::{CreateCategory, UpdateCategory};
-use std::collections::HashMap;
-use std::sync::Arc;
+use crate::errors::Error;
+use crate::proto::callback::v1::{CreateCategory, UpdateCategory, UpdateTransaction};
 use teloxide::dispatching::dialogue::InMemStorage;
 use teloxide::macros::BotCommands;
-use teloxide::prelude::{ChatId, Dialogue};
-use teloxide::types::MessageId;
-use tokio::sync::Mutex;
+use teloxide::prelude::Dialogue;
+use teloxide::types::{MessageId, User};
 
 pub type Dialog = Dialogue<State, InMemStorage<State>>;
-pub type HandlerResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+pub type HandlerResult = Result<(), Error>;
 
 #[derive(Clone, Default, Debug)]
 pub enum State {
@@ -22,6 +20,36 @@ pub enum State {
         answer_message_id: MessageId,
         callback: CreateCategory,
     },
+    UpdateTransaction {
+        answer_message_id: MessageId,
+        callback: UpdateTransaction,
+    },
+    /// An uploaded CSV file waiting for its columns to be mapped.
+    ImportTransactions {
+        rows: Vec<Vec<String>>,
+        columns: ImportColumns,
+    },
+    /// A recognized bank statement waiting for confirmation.
+    ImportStatement {
+        bank: Bank,
+        text: String,
+    },
+    /// Waiting for a backup file after `/restore`.
+    RestoreBackup,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bank {
+    Tinkoff,
+    Sberbank,
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct ImportColumns {
+    pub date: Option<usize>,
+    pub amount: Option<usize>,
+    /// `Some(None)` once the user chose to import without descriptions.
+    pub description: Option<Option<usize>>,
 }
 
 #[derive(BotCommands, Clone)]
@@ -32,6 +60,37 @@ pub enum State {
 pub enum Command {
     #[command(description = "display this text.")]
     Help,
+    #[command(description = "start with a preset set of categories.")]
+    Start,
     #[command(description = "show settings.")]
     Settings,
-}
\ No newline at end of file
+    #[command(description = "set the timezone, e.g. /timezone Europe/Moscow.")]
+    Timezone(String),
+    #[command(description = "undo the last action.")]
+    Undo,
+    #[command(description = "show the change log.")]
+    Log,
+    #[command(description = "show recent transactions.")]
+    History,
+    #[command(description = "pick categories for uncategorized transactions.")]
+    Uncategorized,
+    #[command(description = "show the monthly report.")]
+    Report,
+    #[command(description = "show a chart of this month's expenses.")]
+    Chart,
+    #[command(description = "show expenses and incomes over the last 12 months.")]
+    Trend,
+    #[command(description = "show who owes whom.")]
+    Debts,
+    #[command(description = "export transactions to CSV, e.g. /export 01.12.2025 31.12.2025.")]
+    Export(String),
+    #[command(description = "save all data of the chat to a file.")]
+    Backup,
+    #[command(description = "load a backup into an empty chat.")]
+    Restore,
+}
+
+/// Telegram user ids fit into `i64`, which is how they are stored.
+pub fn user_id(user: &User) -> i64 {
+    user.id.0 as i64
+}